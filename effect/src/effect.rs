@@ -5,7 +5,10 @@ use futures::{
     Future, FutureExt,
 };
 
-use higher::{apply::ApplyFn, Apply, Bifunctor, Bind, Functor, Pure};
+use higher::{
+    apply::ApplyFn, free::FreeFunctor, transformers::InnerMonad, Apply, Bifunctor, Bind, Functor,
+    Pure,
+};
 
 /// An effect monad.
 ///
@@ -244,10 +247,36 @@ where
     }
 }
 
+impl<'a, A: 'a> FreeFunctor<'a, A> for Effect<'a, A> {
+    fn fmap_target<T: 'a, U: 'a, F>(fa: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> U + 'a,
+    {
+        fa.fmap(f)
+    }
+}
+
+impl<'a, A: 'a> InnerMonad<'a, A> for Effect<'a, A> {
+    fn pure_target<T: Clone + 'a>(value: T) -> Self::Target<T> {
+        Effect::ready(value)
+    }
+
+    fn bind_target<T: 'a, U: 'a, F>(m: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> Self::Target<U> + 'a,
+    {
+        async move { f(m.await).await }.into()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::Effect;
-    use higher::{Pure, Traversable};
+    use higher::{
+        run,
+        transformers::{ContT, MonadTrans, ResultT},
+        Pure, Traversable,
+    };
 
     #[test]
     fn traverse_effect() {
@@ -258,4 +287,34 @@ mod test {
         let joined: Effect<'_, Vec<i32>> = efs.sequence();
         assert_eq!(joined.run(), vec![1, 2, 3]);
     }
+
+    #[test]
+    fn result_transformer_over_effect() {
+        let program: ResultT<&str, Effect<'_, Result<u32, &str>>> = run! {
+            x <= ResultT::lift(Effect::ready(2));
+            y <= ResultT::new(Effect::ready(Ok(40)));
+            yield x + y
+        };
+        assert_eq!(program.run_result().run(), Ok(42));
+        let program: ResultT<&str, Effect<'_, Result<u32, &str>>> = run! {
+            x <= ResultT::new(Effect::ready(Err::<u32, _>("nope")));
+            ResultT::lift(Effect::ready(x + 1))
+        };
+        assert_eq!(program.run_result().run(), Err("nope"));
+    }
+
+    #[test]
+    fn cont_transformer_over_effect() {
+        let program: ContT<'_, u32, Effect<'_, u32>, u32> = run! {
+            x <= ContT::lift_with(|| Effect::from(async { 20 }));
+            y <= ContT::call_cc(move |exit| {
+                run! {
+                    if x > 10 { exit(x + 1) } else { ContT::pure(()) };
+                    yield 0
+                }
+            });
+            yield x + y + 1
+        };
+        assert_eq!(program.eval_cont().run(), 42);
+    }
 }
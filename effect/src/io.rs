@@ -8,8 +8,8 @@ use std::path::Path;
 use futures::future::{self, Either};
 use futures::{future::LocalBoxFuture, Future, FutureExt};
 
-use higher::ApplicativeError;
 use higher::{apply::ApplyFn, Apply, Bifunctor, Bind, Functor, Pure};
+use higher::{free::FreeFunctor, transformers::InnerMonad, ApplicativeError};
 
 /// An IO monad.
 ///
@@ -383,6 +383,38 @@ impl<'a, A: 'a, E: 'a> ApplicativeError<'a, A, E> for IO<'a, A, E> {
     }
 }
 
+impl<'a, A: 'a, E: 'a> FreeFunctor<'a, A> for IO<'a, A, E> {
+    fn fmap_target<T: 'a, U: 'a, F>(fa: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> U + 'a,
+    {
+        fa.map_ok(f)
+    }
+}
+
+impl<'a, A: 'a, E: 'a> InnerMonad<'a, A> for IO<'a, A, E> {
+    fn pure_target<T: Clone + 'a>(value: T) -> Self::Target<T> {
+        IO::pure(value)
+    }
+
+    fn bind_target<T: 'a, U: 'a, F>(m: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> Self::Target<U> + 'a,
+    {
+        match m.state {
+            IOState::Error(error) => IO::throw_error(error),
+            IOState::Future(future) => async move {
+                match future.await {
+                    Ok(result) => f(result),
+                    Err(error) => IO::throw_error(error),
+                }
+                .await
+            }
+            .into(),
+        }
+    }
+}
+
 /// Print a string to the console.
 pub fn put_str<'a, S: AsRef<str> + 'a>(s: S) -> IO<'a, (), Error> {
     async move { stdout().write(s.as_ref().as_bytes()).map(|_| ()) }.into()
@@ -445,3 +477,56 @@ pub fn append_file<'a, P: 'a + AsRef<Path>, C: 'a + AsRef<[u8]>>(
     }
     .into()
 }
+
+#[cfg(test)]
+mod test {
+    use super::IO;
+    use higher::{
+        run,
+        transformers::{MonadTrans, OptionT, StateT},
+        ApplicativeError, Bind, Functor, MonadState,
+    };
+
+    #[test]
+    fn state_transformer_over_io() {
+        fn withdraw<'a>(amount: u32) -> StateT<'a, u32, IO<'a, ((), u32), String>> {
+            StateT::new(move |balance: u32| match balance.checked_sub(amount) {
+                Some(balance) => IO::from(async move { Ok(((), balance)) }),
+                None => IO::throw_error(format!("insufficient funds for {}", amount)),
+            })
+        }
+
+        let program = run! {
+            withdraw(10);
+            withdraw(20);
+            StateT::lift_with(|| IO::from(async { Ok("done") }))
+        };
+        assert_eq!(program.run_state(50).run(), Ok(("done", 20)));
+        assert_eq!(
+            program.run_state(25).run(),
+            Err("insufficient funds for 20".to_string())
+        );
+    }
+
+    #[test]
+    fn option_transformer_over_state_over_io() {
+        type Stack<'a, A> = OptionT<StateT<'a, Vec<u32>, IO<'a, (Option<A>, Vec<u32>), String>>>;
+
+        fn pop<'a>() -> Stack<'a, u32> {
+            Stack::<u32>::get().bind(|mut stack: Vec<u32>| match stack.pop() {
+                Some(value) => Stack::<()>::put(stack).fmap(move |()| value),
+                None => Stack::throw_error(()),
+            })
+        }
+
+        let program: Stack<'_, u32> = run! {
+            a <= pop();
+            b <= pop();
+            x <= OptionT::lift(StateT::lift_with(|| IO::from(async { Ok(2) })));
+            yield (a + b) * x
+        };
+        let program = program.run_option();
+        assert_eq!(program.run_state(vec![1, 2, 3]).run(), Ok((Some(10), vec![1])));
+        assert_eq!(program.run_state(vec![1]).run(), Ok((None, vec![])));
+    }
+}
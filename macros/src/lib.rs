@@ -8,70 +8,173 @@
 
 extern crate proc_macro;
 
-use std::collections::HashMap;
+use std::{cell::RefCell, collections::HashMap};
 
-use proc_macro2::{Span, TokenStream};
-use quote::{quote, quote_spanned};
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::{
-    parse_macro_input, punctuated::Punctuated, spanned::Spanned, token::Comma, Data, DataEnum,
-    DeriveInput, Field, Fields, FieldsNamed, FieldsUnnamed, GenericParam, Ident, Index, Type,
-    TypeParam,
+    parse::{Parse, ParseStream},
+    parse_macro_input, parse_quote,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token::Comma,
+    Data, DataEnum, DataStruct, DeriveInput, Field, Fields, FieldsNamed, FieldsUnnamed,
+    GenericArgument, GenericParam, Generics, Ident, Index, Lifetime, Member, PathArguments,
+    ReturnType, Token, Type, TypeImplTrait, TypeParam, TypeParamBound, TypeTraitObject,
 };
 
-fn type_params_replace(
-    input_params: &Punctuated<GenericParam, Comma>,
-    replace: &TypeParam,
-    with: Ident,
-) -> Punctuated<GenericParam, Comma> {
-    let mut output = input_params.clone();
-    for param in output.iter_mut() {
-        match param {
-            GenericParam::Type(ref mut type_param) if type_param == replace => {
-                type_param.ident = with;
-                break;
+fn report_error(span: Span, msg: &str) -> proc_macro::TokenStream {
+    (quote_spanned! {span => compile_error! {#msg}}).into()
+}
+
+/// One `key = Param` pair from a `#[functor(...)]` or `#[bifunctor(...)]`
+/// attribute.
+struct ParamChoice {
+    key: Ident,
+    value: Ident,
+}
+
+impl Parse for ParamChoice {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let key = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+        Ok(Self { key, value })
+    }
+}
+
+/// Read the type parameters picked out by an attribute like
+/// `#[functor(param = T)]`, in the order given by `keys`, or `None` if the
+/// type doesn't have the attribute.
+fn chosen_type_params<'a>(
+    input: &'a DeriveInput,
+    attr_name: &str,
+    keys: &[&str],
+) -> Result<Option<Vec<&'a TypeParam>>, proc_macro::TokenStream> {
+    let expected = keys
+        .iter()
+        .map(|key| format!("`{key} = ...`"))
+        .collect::<Vec<_>>()
+        .join(" and ");
+    let mut found = None;
+    for attr in &input.attrs {
+        if !attr.path.is_ident(attr_name) {
+            continue;
+        }
+        if found.is_some() {
+            return Err(report_error(
+                attr.span(),
+                &format!("a type can only have one #[{attr_name}] attribute"),
+            ));
+        }
+        let choices = match attr.parse_args_with(Punctuated::<ParamChoice, Comma>::parse_terminated)
+        {
+            Ok(choices) => choices,
+            Err(err) => return Err(err.to_compile_error().into()),
+        };
+        let mut chosen: Vec<Option<&TypeParam>> = vec![None; keys.len()];
+        for choice in &choices {
+            let slot = match keys.iter().position(|key| choice.key == key) {
+                Some(slot) => slot,
+                None => {
+                    return Err(report_error(
+                        choice.key.span(),
+                        &format!("unknown option `{}`; expected {expected}", choice.key),
+                    ))
+                }
+            };
+            if chosen[slot].is_some() {
+                return Err(report_error(
+                    choice.key.span(),
+                    &format!("`{}` is given more than once", choice.key),
+                ));
+            }
+            let param = match input
+                .generics
+                .type_params()
+                .find(|param| param.ident == choice.value)
+            {
+                Some(param) => param,
+                None => {
+                    return Err(report_error(
+                        choice.value.span(),
+                        &format!(
+                            "`{}` isn't a type parameter of `{}`",
+                            choice.value, input.ident
+                        ),
+                    ))
+                }
+            };
+            if chosen.contains(&Some(param)) {
+                return Err(report_error(
+                    choice.value.span(),
+                    &format!("`{}` can't be picked more than once", choice.value),
+                ));
+            }
+            chosen[slot] = Some(param);
+        }
+        match chosen.into_iter().collect::<Option<Vec<_>>>() {
+            Some(chosen) => found = Some(chosen),
+            None => {
+                return Err(report_error(
+                    attr.span(),
+                    &format!("#[{attr_name}] needs {expected}"),
+                ))
             }
-            _ => {}
         }
     }
-    output
+    Ok(found)
 }
 
-fn report_error(span: Span, msg: &str) -> proc_macro::TokenStream {
-    (quote_spanned! {span => compile_error! {#msg}}).into()
-}
+fn decide_functor_generic_type<'a>(
+    input: &'a DeriveInput,
+    trait_name: &str,
+) -> Result<&'a TypeParam, proc_macro::TokenStream> {
+    if let Some(chosen) = chosen_type_params(input, "functor", &["param"])? {
+        return Ok(chosen[0]);
+    }
 
-fn decide_functor_generic_type(input: &DeriveInput) -> Result<&TypeParam, proc_macro::TokenStream> {
     let mut generics_iter = input.generics.type_params();
     let generic_type = match generics_iter.next() {
         Some(t) => t,
         None => {
             return Err(report_error(
                 input.ident.span(),
-                "can't derive Functor for a type without type parameters",
+                &format!("can't derive {trait_name} for a type without type parameters"),
             ));
         }
     };
 
     if let Some(next_type_param) = generics_iter.next() {
+        let hint = if trait_name.starts_with("Functor") {
+            ", or did you mean Bifunctor?"
+        } else {
+            ""
+        };
         return Err(report_error(
             next_type_param.span(),
-            "can't derive Functor for a type with multiple type parameters; did you mean Bifunctor?",
+            &format!("can't tell which type parameter to derive {trait_name} over; pick one using #[functor(param = ...)]{hint}"),
         ));
     }
 
     Ok(generic_type)
 }
 
-fn decide_bifunctor_generic_types(
-    input: &DeriveInput,
-) -> Result<(&TypeParam, &TypeParam), proc_macro::TokenStream> {
+fn decide_bifunctor_generic_types<'a>(
+    input: &'a DeriveInput,
+    trait_name: &str,
+) -> Result<(&'a TypeParam, &'a TypeParam), proc_macro::TokenStream> {
+    if let Some(chosen) = chosen_type_params(input, "bifunctor", &["left", "right"])? {
+        return Ok((chosen[0], chosen[1]));
+    }
+
     let mut generics_iter = input.generics.type_params();
     let generic_type_a = match generics_iter.next() {
         Some(t) => t,
         None => {
             return Err(report_error(
                 input.ident.span(),
-                "can't derive Bifunctor for a type without type parameters",
+                &format!("can't derive {trait_name} for a type without type parameters"),
             ))
         }
     };
@@ -80,31 +183,153 @@ fn decide_bifunctor_generic_types(
         Some(t) => t,
         None => return Err(report_error(
             input.ident.span(),
-            "can't derive Bifunctor for a type with only one type parameter; did you mean Functor?",
+            &format!(
+                "can't derive {trait_name} for a type with only one type parameter; did you mean {}?",
+                if trait_name == "Profunctor" { "Contravariant" } else { "Functor" }
+            ),
         )),
     };
 
     if let Some(next_type_param) = generics_iter.next() {
         return Err(report_error(
             next_type_param.span(),
-            "can't derive Functor for a type with three or more type parameters",
+            &format!("can't tell which type parameters to derive {trait_name} over; pick them using #[bifunctor(left = ..., right = ...)]"),
         ));
     }
 
     Ok((generic_type_a, generic_type_b))
 }
 
-#[proc_macro_derive(Bifunctor)]
+/// Make sure the type parameters a functor maps over can be changed to any
+/// other type, which they can't if something puts bounds on them.
+fn check_unbounded(
+    input: &DeriveInput,
+    params: &[&TypeParam],
+    trait_name: &str,
+) -> Result<(), proc_macro::TokenStream> {
+    let msg = format!(
+        "can't derive {trait_name} over `{{}}` when it has bounds, because it needs to be able to map it to any type"
+    );
+    let type_map: HashMap<Ident, Ident> = params
+        .iter()
+        .map(|param| (param.ident.clone(), param.ident.clone()))
+        .collect();
+    let visitor = FieldVisitor::new(&type_map, false);
+    for param in params {
+        if !param.bounds.is_empty() {
+            return Err(report_error(
+                param.bounds.span(),
+                &msg.replace("{}", &param.ident.to_string()),
+            ));
+        }
+    }
+    if let Some(where_clause) = &input.generics.where_clause {
+        for predicate in &where_clause.predicates {
+            if visitor.mentions_any(predicate) {
+                return Err(visitor.error(predicate, &msg));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The generics for an impl of a trait with a `'derivedlifetime` parameter,
+/// which the mapped type parameters have to outlive.
+fn derived_generics(input: &DeriveInput, params: &[&TypeParam]) -> Generics {
+    let mut generics = input.generics.clone();
+    generics.params.insert(0, parse_quote!('derivedlifetime));
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        let ident = &param.ident;
+        where_clause
+            .predicates
+            .push(parse_quote!(#ident: 'derivedlifetime));
+    }
+    generics
+}
+
+/// Add `Clone` bounds for the parts of the fields which a ref map has to
+/// clone, because they don't mention the mapped type parameters but do
+/// mention other ones.
+fn add_clone_bounds(
+    input: &DeriveInput,
+    type_map: &HashMap<Ident, Ident>,
+    generics: &mut Generics,
+) {
+    fn visit<'t>(
+        input: &DeriveInput,
+        visitor: &FieldVisitor<'_>,
+        ty: &'t Type,
+        found: &mut Vec<&'t Type>,
+    ) {
+        match visitor.shape(ty) {
+            Ok(None) => {
+                if mentions_type_params(input, ty) && !found.contains(&ty) {
+                    found.push(ty);
+                }
+            }
+            Ok(Some(FieldShape::Nested(inner))) => visit(input, visitor, inner, found),
+            Ok(Some(FieldShape::Tuple(elems))) => {
+                for elem in elems {
+                    visit(input, visitor, elem, found);
+                }
+            }
+            // Errors get reported when generating the map itself.
+            Ok(Some(FieldShape::Param(_))) | Err(_) => {}
+        }
+    }
+
+    let fields: Vec<&Field> = match &input.data {
+        Data::Struct(data) => data.fields.iter().collect(),
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .flat_map(|variant| &variant.fields)
+            .collect(),
+        Data::Union(_) => Vec::new(),
+    };
+    let visitor = FieldVisitor::new(type_map, true);
+    let mut found = Vec::new();
+    for field in fields {
+        visit(input, &visitor, &field.ty, &mut found);
+    }
+    let where_clause = generics.make_where_clause();
+    for ty in found {
+        where_clause
+            .predicates
+            .push(parse_quote!(#ty: ::std::clone::Clone));
+    }
+}
+
+/// The type being derived for, with some of its type parameters replaced.
+fn retarget(input: &DeriveInput, replace: &[(&TypeParam, &Ident)]) -> TokenStream {
+    let name = &input.ident;
+    let args = input.generics.params.iter().map(|param| match param {
+        GenericParam::Type(param) => {
+            match replace.iter().find(|(from, _)| from.ident == param.ident) {
+                Some((_, to)) => to.to_token_stream(),
+                None => param.ident.to_token_stream(),
+            }
+        }
+        GenericParam::Lifetime(param) => param.lifetime.to_token_stream(),
+        GenericParam::Const(param) => param.ident.to_token_stream(),
+    });
+    quote!(#name<#(#args),*>)
+}
+
+#[proc_macro_derive(Bifunctor, attributes(bifunctor))]
 pub fn derive_bifunctor(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
-    let type_params = &input.generics.params;
-    let where_clause = input.generics.where_clause.as_ref().map(|c| &c.predicates);
 
-    let (generic_type_a, generic_type_b) = match decide_bifunctor_generic_types(&input) {
+    let (generic_type_a, generic_type_b) = match decide_bifunctor_generic_types(&input, "Bifunctor")
+    {
         Ok(t) => t,
         Err(err) => return err,
     };
+    if let Err(err) = check_unbounded(&input, &[generic_type_a, generic_type_b], "Bifunctor") {
+        return err;
+    }
 
     let type_map = HashMap::from([
         (
@@ -138,27 +363,34 @@ pub fn derive_bifunctor(input: proc_macro::TokenStream) -> proc_macro::TokenStre
             );
         }
     };
+    let bimap_impl = match bimap_impl {
+        Ok(body) => body,
+        Err(err) => return err,
+    };
 
-    let type_params_generic = type_params_replace(
-        &type_params_replace(
-            type_params,
-            generic_type_a,
-            Ident::new("DerivedTargetTypeA", Span::call_site()),
-        ),
-        generic_type_b,
-        Ident::new("DerivedTargetTypeB", Span::call_site()),
+    let target = retarget(
+        &input,
+        &[
+            (generic_type_a, &format_ident!("DerivedTargetTypeA")),
+            (generic_type_b, &format_ident!("DerivedTargetTypeB")),
+        ],
     );
+    let generics = derived_generics(&input, &[generic_type_a, generic_type_b]);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let generic_type_a = &generic_type_a.ident;
+    let generic_type_b = &generic_type_b.ident;
 
     quote!(
-        impl<'derivedlifetime, #type_params> ::higher::Bifunctor<'derivedlifetime, #generic_type_a, #generic_type_b> for #name<#type_params>
-                where #generic_type_a: 'derivedlifetime, #generic_type_b: 'derivedlifetime, #where_clause {
-            type Target<DerivedTargetTypeA, DerivedTargetTypeB> = #name<#type_params_generic> where DerivedTargetTypeA: 'derivedlifetime, DerivedTargetTypeB: 'derivedlifetime;
-            fn bimap<DerivedTypeA, DerivedTypeB, L, R>(self, left: L, right: R) -> Self::Target<DerivedTypeA, DerivedTypeB>
+        impl #impl_generics ::higher::Bifunctor<'derivedlifetime, #generic_type_a, #generic_type_b> for #name #ty_generics
+                #where_clause {
+            type Target<DerivedTargetTypeA, DerivedTargetTypeB> = #target where DerivedTargetTypeA: 'derivedlifetime, DerivedTargetTypeB: 'derivedlifetime;
+            fn bimap<DerivedTypeA, DerivedTypeB, DerivedLeft, DerivedRight>(self, left: DerivedLeft, right: DerivedRight) -> Self::Target<DerivedTypeA, DerivedTypeB>
             where
                 DerivedTypeA: 'derivedlifetime,
                 DerivedTypeB: 'derivedlifetime,
-                L: Fn(#generic_type_a) -> DerivedTypeA + 'derivedlifetime,
-                R: Fn(#generic_type_b) -> DerivedTypeB + 'derivedlifetime
+                DerivedLeft: Fn(#generic_type_a) -> DerivedTypeA + 'derivedlifetime,
+                DerivedRight: Fn(#generic_type_b) -> DerivedTypeB + 'derivedlifetime
             {
                 #bimap_impl
             }
@@ -167,17 +399,16 @@ pub fn derive_bifunctor(input: proc_macro::TokenStream) -> proc_macro::TokenStre
     .into()
 }
 
-#[proc_macro_derive(BifunctorRef)]
+#[proc_macro_derive(BifunctorRef, attributes(bifunctor))]
 pub fn derive_bifunctor_ref(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
-    let type_params = &input.generics.params;
-    let where_clause = input.generics.where_clause.as_ref().map(|c| &c.predicates);
 
-    let (generic_type_a, generic_type_b) = match decide_bifunctor_generic_types(&input) {
-        Ok(t) => t,
-        Err(err) => return err,
-    };
+    let (generic_type_a, generic_type_b) =
+        match decide_bifunctor_generic_types(&input, "BifunctorRef") {
+            Ok(t) => t,
+            Err(err) => return err,
+        };
 
     let type_map = HashMap::from([
         (
@@ -209,16 +440,27 @@ pub fn derive_bifunctor_ref(input: proc_macro::TokenStream) -> proc_macro::Token
             );
         }
     };
+    let bimap_impl = match bimap_impl {
+        Ok(body) => body,
+        Err(err) => return err,
+    };
+
+    let mut generics = derived_generics(&input, &[generic_type_a, generic_type_b]);
+    add_clone_bounds(&input, &type_map, &mut generics);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let generic_type_a = &generic_type_a.ident;
+    let generic_type_b = &generic_type_b.ident;
 
     quote!(
-        impl<'derivedlifetime, #type_params> ::higher::BifunctorRef<'derivedlifetime, #generic_type_a, #generic_type_b> for #name<#type_params>
-                where #generic_type_a: 'derivedlifetime, #generic_type_b: 'derivedlifetime, #where_clause {
-            fn bimap_ref<DerivedTypeA, DerivedTypeB, L, R>(&self, left: L, right: R) -> Self::Target<DerivedTypeA, DerivedTypeB>
+        impl #impl_generics ::higher::BifunctorRef<'derivedlifetime, #generic_type_a, #generic_type_b> for #name #ty_generics
+                #where_clause {
+            fn bimap_ref<DerivedTypeA, DerivedTypeB, DerivedLeft, DerivedRight>(&self, left: DerivedLeft, right: DerivedRight) -> Self::Target<DerivedTypeA, DerivedTypeB>
             where
                 DerivedTypeA: 'derivedlifetime,
                 DerivedTypeB: 'derivedlifetime,
-                L: Fn(&#generic_type_a) -> DerivedTypeA + 'derivedlifetime,
-                R: Fn(&#generic_type_b) -> DerivedTypeB + 'derivedlifetime
+                DerivedLeft: Fn(&#generic_type_a) -> DerivedTypeA + 'derivedlifetime,
+                DerivedRight: Fn(&#generic_type_b) -> DerivedTypeB + 'derivedlifetime
             {
                 #bimap_impl
             }
@@ -227,17 +469,18 @@ pub fn derive_bifunctor_ref(input: proc_macro::TokenStream) -> proc_macro::Token
     .into()
 }
 
-#[proc_macro_derive(Functor)]
+#[proc_macro_derive(Functor, attributes(functor))]
 pub fn derive_functor(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
-    let type_params = &input.generics.params;
-    let where_clause = input.generics.where_clause.as_ref().map(|c| &c.predicates);
 
-    let generic_type = match decide_functor_generic_type(&input) {
+    let generic_type = match decide_functor_generic_type(&input, "Functor") {
         Ok(t) => t,
         Err(err) => return err,
     };
+    if let Err(err) = check_unbounded(&input, &[generic_type], "Functor") {
+        return err;
+    }
 
     let type_map = HashMap::from([(
         generic_type.ident.clone(),
@@ -262,37 +505,54 @@ pub fn derive_functor(input: proc_macro::TokenStream) -> proc_macro::TokenStream
             return report_error(input.ident.span(), "can't derive Functor for a union type");
         }
     };
+    let fmap_impl = match fmap_impl {
+        Ok(body) => body,
+        Err(err) => return err,
+    };
 
-    let type_params_with_t = type_params_replace(
-        type_params,
-        generic_type,
-        Ident::new("DerivedTargetType", Span::call_site()),
+    let target = retarget(
+        &input,
+        &[(generic_type, &format_ident!("DerivedTargetType"))],
     );
+    let generics = derived_generics(&input, &[generic_type]);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let generic_type = &generic_type.ident;
 
     quote!(
-        impl<'derivedlifetime, #type_params> ::higher::Functor<'derivedlifetime, #generic_type> for #name<#type_params>
-                where #generic_type: 'derivedlifetime, #where_clause {
-            type Target<DerivedTargetType> = #name<#type_params_with_t> where DerivedTargetType: 'derivedlifetime;
-            fn fmap<DerivedType, F>(self, f: F) -> Self::Target<DerivedType>
+        impl #impl_generics ::higher::Functor<'derivedlifetime, #generic_type> for #name #ty_generics
+                #where_clause {
+            type Target<DerivedTargetType> = #target where DerivedTargetType: 'derivedlifetime;
+            fn fmap<DerivedType, DerivedFunction>(self, f: DerivedFunction) -> Self::Target<DerivedType>
             where
                 DerivedType: 'derivedlifetime,
-                F: Fn(#generic_type) -> DerivedType + 'derivedlifetime
+                DerivedFunction: Fn(#generic_type) -> DerivedType + 'derivedlifetime
             {
                 #fmap_impl
             }
         }
+
+        impl #impl_generics ::higher::free::FreeFunctor<'derivedlifetime, #generic_type> for #name #ty_generics
+                #where_clause {
+            fn fmap_target<DerivedTypeFrom, DerivedTypeTo, DerivedFunction>(fa: Self::Target<DerivedTypeFrom>, f: DerivedFunction) -> Self::Target<DerivedTypeTo>
+            where
+                DerivedTypeFrom: 'derivedlifetime,
+                DerivedTypeTo: 'derivedlifetime,
+                DerivedFunction: Fn(DerivedTypeFrom) -> DerivedTypeTo + 'derivedlifetime
+            {
+                ::higher::Functor::fmap(fa, f)
+            }
+        }
     )
     .into()
 }
 
-#[proc_macro_derive(FunctorRef)]
+#[proc_macro_derive(FunctorRef, attributes(functor))]
 pub fn derive_functor_ref(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
-    let type_params = &input.generics.params;
-    let where_clause = input.generics.where_clause.as_ref().map(|c| &c.predicates);
 
-    let generic_type = match decide_functor_generic_type(&input) {
+    let generic_type = match decide_functor_generic_type(&input, "FunctorRef") {
         Ok(t) => t,
         Err(err) => return err,
     };
@@ -321,14 +581,24 @@ pub fn derive_functor_ref(input: proc_macro::TokenStream) -> proc_macro::TokenSt
             );
         }
     };
+    let fmapref_impl = match fmapref_impl {
+        Ok(body) => body,
+        Err(err) => return err,
+    };
+
+    let mut generics = derived_generics(&input, &[generic_type]);
+    add_clone_bounds(&input, &type_map, &mut generics);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let generic_type = &generic_type.ident;
 
     quote!(
-        impl<'derivedlifetime, #type_params> ::higher::FunctorRef<'derivedlifetime, #generic_type> for #name<#type_params>
-                where #generic_type: 'derivedlifetime, #where_clause {
-            fn fmap_ref<DerivedType, F>(&self, f: F) -> Self::Target<DerivedType>
+        impl #impl_generics ::higher::FunctorRef<'derivedlifetime, #generic_type> for #name #ty_generics
+                #where_clause {
+            fn fmap_ref<DerivedType, DerivedFunction>(&self, f: DerivedFunction) -> Self::Target<DerivedType>
             where
                 DerivedType: 'derivedlifetime,
-                F: Fn(&#generic_type) -> DerivedType + 'derivedlifetime
+                DerivedFunction: Fn(&#generic_type) -> DerivedType + 'derivedlifetime
             {
                 #fmapref_impl
             }
@@ -337,214 +607,1992 @@ pub fn derive_functor_ref(input: proc_macro::TokenStream) -> proc_macro::TokenSt
     .into()
 }
 
-fn match_type_param<'a>(params: &'a HashMap<Ident, Ident>, ty: &Type) -> Option<&'a Ident> {
-    if let Type::Path(path) = ty {
-        if let Some(segment) = path.path.segments.iter().next() {
-            return params.get(&segment.ident);
-        }
-    }
-    None
-}
+#[proc_macro_derive(Foldable, attributes(functor))]
+pub fn derive_foldable(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
 
-fn filter_fields<P, F1, F2>(
-    fields: &Punctuated<Field, P>,
-    ty: &HashMap<Ident, Ident>,
-    transform: F1,
-    copy: F2,
-) -> Vec<TokenStream>
-where
-    F1: Fn(&Ident, &Ident) -> TokenStream,
-    F2: Fn(&Ident) -> TokenStream,
-{
-    fields
-        .iter()
-        .map(|field| {
-            if let Some(f) = match_type_param(ty, &field.ty) {
-                transform(&field.ident.clone().unwrap(), f)
-            } else {
-                copy(&field.ident.clone().unwrap())
+    let generic_type = match decide_functor_generic_type(&input, "Foldable") {
+        Ok(t) => t,
+        Err(err) => return err,
+    };
+
+    let type_map = HashMap::from([(
+        generic_type.ident.clone(),
+        Ident::new("f", Span::call_site()),
+    )]);
+
+    let init = quote!(init);
+    let empty = quote!(::std::default::Default::default());
+    let bodies = [
+        derive_fold(&input, &type_map, FoldKind::Right, false, &init),
+        derive_fold(&input, &type_map, FoldKind::Right, true, &init),
+        derive_fold(&input, &type_map, FoldKind::Left, false, &init),
+        derive_fold(&input, &type_map, FoldKind::Left, true, &init),
+        derive_fold(&input, &type_map, FoldKind::Map, false, &empty),
+        derive_fold(&input, &type_map, FoldKind::Map, true, &empty),
+    ];
+    let [foldr, foldr_ref, foldl, foldl_ref, fold_map, fold_map_ref] = match bodies {
+        [Ok(a), Ok(b), Ok(c), Ok(d), Ok(e), Ok(f)] => [a, b, c, d, e, f],
+        [Err(err), ..] => return err,
+        _ => unreachable!("every fold fails on the same fields"),
+    };
+
+    let generics = derived_generics(&input, &[generic_type]);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let generic_type = &generic_type.ident;
+
+    quote!(
+        impl #impl_generics ::higher::Foldable<'derivedlifetime, #generic_type> for #name #ty_generics
+                #where_clause {
+            fn foldr<DerivedType: 'derivedlifetime, DerivedFunction: 'derivedlifetime>(self, f: DerivedFunction, init: DerivedType) -> DerivedType
+            where
+                DerivedFunction: Fn(#generic_type, DerivedType) -> DerivedType
+            {
+                #foldr
             }
-        })
-        .collect()
-}
 
-fn derive_functor_named_struct(
-    name: &Ident,
-    fields: &FieldsNamed,
-    generic_types: &HashMap<Ident, Ident>,
-    as_ref: bool,
-) -> TokenStream {
-    let apply_fields = filter_fields(
-        &fields.named,
-        generic_types,
-        |field, function_name| {
-            if as_ref {
-                quote! {
-                    #field: #function_name(&self.#field),
-                }
-            } else {
-                quote! {
-                    #field: #function_name(self.#field),
-                }
+            fn foldr_ref<DerivedType: 'derivedlifetime, DerivedFunction: 'derivedlifetime>(&'derivedlifetime self, f: DerivedFunction, init: DerivedType) -> DerivedType
+            where
+                DerivedFunction: Fn(&'derivedlifetime #generic_type, DerivedType) -> DerivedType
+            {
+                #foldr_ref
             }
-        },
-        |field| {
-            quote! {
-                #field: self.#field,
+
+            fn foldl<DerivedType: 'derivedlifetime, DerivedFunction: 'derivedlifetime>(self, f: DerivedFunction, init: DerivedType) -> DerivedType
+            where
+                DerivedFunction: Fn(DerivedType, #generic_type) -> DerivedType
+            {
+                #foldl
             }
-        },
-    )
-    .into_iter();
-    quote! {
-        #name {
-            #(#apply_fields)*
-        }
-    }
-}
 
-fn derive_functor_unnamed_struct(
-    name: &Ident,
-    fields: &FieldsUnnamed,
-    generic_types: &HashMap<Ident, Ident>,
-    as_ref: bool,
-) -> TokenStream {
-    let fields = fields.unnamed.iter().enumerate().map(|(index, field)| {
-        let index = Index::from(index);
-        if let Some(function_name) = match_type_param(generic_types, &field.ty) {
-            if as_ref {
-                quote! { #function_name(&self.#index), }
-            } else {
-                quote! { #function_name(self.#index), }
+            fn foldl_ref<DerivedType: 'derivedlifetime, DerivedFunction: 'derivedlifetime>(&'derivedlifetime self, f: DerivedFunction, init: DerivedType) -> DerivedType
+            where
+                DerivedFunction: Fn(DerivedType, &'derivedlifetime #generic_type) -> DerivedType
+            {
+                #foldl_ref
+            }
+
+            fn fold_map<DerivedFunction: 'derivedlifetime, DerivedMonoid>(self, f: DerivedFunction) -> DerivedMonoid
+            where
+                DerivedFunction: Fn(#generic_type) -> DerivedMonoid,
+                DerivedMonoid: ::higher::Monoid
+            {
+                #fold_map
+            }
+
+            fn fold_map_ref<DerivedFunction: 'derivedlifetime, DerivedMonoid>(&'derivedlifetime self, f: DerivedFunction) -> DerivedMonoid
+            where
+                DerivedFunction: Fn(&'derivedlifetime #generic_type) -> DerivedMonoid,
+                DerivedMonoid: ::higher::Monoid + 'derivedlifetime
+            {
+                #fold_map_ref
             }
-        } else {
-            quote! { self.#index, }
         }
-    });
-    quote! { #name(#(#fields)*) }
+    )
+    .into()
 }
 
-fn derive_functor_enum(
-    name: &Ident,
-    data: &DataEnum,
-    generic_types: &HashMap<Ident, Ident>,
-    as_ref: bool,
-) -> TokenStream {
-    let variants = data.variants.iter().map(|variant| {
-        let ident = &variant.ident;
-        match &variant.fields {
-            Fields::Named(fields) => {
-                let args: Vec<Ident> = fields
-                    .named
-                    .iter()
-                    .map(|field| {
-                        Ident::new(
-                            &format!("arg_{}", field.ident.clone().unwrap()),
-                            field.ident.clone().unwrap().span(),
-                        )
-                    })
-                    .collect();
-                let apply =
-                    fields
-                        .named
-                        .iter()
-                        .zip(args.clone().into_iter())
-                        .map(|(field, arg)| {
-                            let name = &field.ident;
-                            if let Some(function_name) = match_type_param(generic_types, &field.ty)
-                            {
-                                if as_ref {
-                                    quote! { #name: #function_name(&#arg) }
-                                } else {
-                                    quote! { #name: #function_name(#arg) }
-                                }
-                            } else {
-                                quote! { #name: #arg }
-                            }
-                        });
-                let args = fields
-                    .named
-                    .iter()
-                    .zip(args.into_iter())
-                    .map(|(field, arg)| {
-                        let name = &field.ident;
-                        quote! { #name:#arg }
-                    });
-                quote! {
-                    #name::#ident { #(#args,)* } => #name::#ident { #(#apply,)* },
-                }
-            }
-            Fields::Unnamed(fields) => {
-                let args: Vec<Ident> = fields
-                    .unnamed
-                    .iter()
-                    .enumerate()
-                    .map(|(index, _)| Ident::new(&format!("arg{index}"), Span::call_site()))
-                    .collect();
-                let fields = fields.unnamed.iter().zip(args.iter()).map(|(field, arg)| {
-                    if let Some(function_name) = match_type_param(generic_types, &field.ty) {
-                        if as_ref {
-                            quote! { #function_name(&#arg) }
-                        } else {
-                            quote! { #function_name(#arg) }
-                        }
-                    } else {
-                        quote! { #arg }
-                    }
-                });
-                let args = args.iter();
-                quote! {
-                    #name::#ident(#(#args,)*) => #name::#ident(#(#fields,)*),
-                }
+#[proc_macro_derive(Traversable, attributes(functor))]
+pub fn derive_traversable(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let generic_type = match decide_functor_generic_type(&input, "Traversable") {
+        Ok(t) => t,
+        Err(err) => return err,
+    };
+    let body = match &input.data {
+        Data::Struct(data) => {
+            if data.fields.is_empty() {
+                return report_error(name.span(), "can't derive Traversable for an empty struct");
             }
-            Fields::Unit => quote! {
-                #name::#ident => #name::#ident,
-            },
+            derive_traverse_case(&quote!(#name), &data.fields, &generic_type.ident, false)
+                .map(|arm| quote! { match self { #arm } })
         }
-    });
-    quote! {
-        match self {
-            #(#variants)*
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let ident = &variant.ident;
+                derive_traverse_case(
+                    &quote!(#name::#ident),
+                    &variant.fields,
+                    &generic_type.ident,
+                    data.variants.len() > 1,
+                )
+            })
+            .collect::<Result<Vec<_>, proc_macro::TokenStream>>()
+            .map(|arms| quote! { match self { #(#arms)* } }),
+        Data::Union(_) => {
+            return report_error(name.span(), "can't derive Traversable for a union type");
         }
-    }
-}
+    };
+    let body = match body {
+        Ok(body) => body,
+        Err(err) => return err,
+    };
 
-#[cfg(test)]
-mod test {
-    use higher::{Bifunctor, BifunctorRef, Functor, FunctorRef};
+    // The shapes of the fields are copied using `FunctorRef`, which needs
+    // the same bounds.
+    let type_map = HashMap::from([(
+        generic_type.ident.clone(),
+        Ident::new("f", Span::call_site()),
+    )]);
+    let mut generics = derived_generics(&input, &[generic_type]);
+    add_clone_bounds(&input, &type_map, &mut generics);
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let generic_type = &generic_type.ident;
 
-    #[derive(PartialEq, Eq, Debug, Functor, FunctorRef)]
-    struct FunctorNamed<A> {
-        named: A,
-    }
+    quote!(
+        impl #impl_generics ::higher::Traversable<'derivedlifetime, #generic_type> for #name #ty_generics
+                #where_clause {
+            fn traverse<DerivedType: 'derivedlifetime, DerivedApplicative: 'derivedlifetime, DerivedFunction: 'derivedlifetime>(
+                self,
+                f: DerivedFunction,
+            ) -> DerivedApplicative::Target<Self::Target<DerivedType>>
+            where
+                DerivedType: Clone,
+                DerivedApplicative: ::higher::Applicative<'derivedlifetime, DerivedType>,
+                DerivedFunction: Fn(#generic_type) -> DerivedApplicative,
+                DerivedApplicative::Target<Self::Target<DerivedType>>: ::higher::Applicative<
+                        'derivedlifetime,
+                        Self::Target<DerivedType>,
+                        Target<Self::Target<DerivedType>> = DerivedApplicative::Target<Self::Target<DerivedType>>,
+                    > + ::higher::Applicative<
+                        'derivedlifetime,
+                        Self::Target<DerivedType>,
+                        Target<::higher::apply::ApplyFn<'derivedlifetime, DerivedType, Self::Target<DerivedType>>> = DerivedApplicative::Target<
+                            ::higher::apply::ApplyFn<'derivedlifetime, DerivedType, Self::Target<DerivedType>>,
+                        >,
+                    >
+            {
+                #body
+            }
+        }
+    )
+    .into()
+}
 
-    #[derive(PartialEq, Eq, Debug, Functor, FunctorRef)]
-    struct FunctorUnnamed<A>(A);
+#[proc_macro_derive(Contravariant, attributes(functor))]
+pub fn derive_contravariant(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
 
-    #[derive(PartialEq, Eq, Debug, Functor)]
-    #[allow(dead_code)]
-    enum FunctorEnum<A> {
-        Some(A),
-        SomeNumber(usize),
-        SomeOther(A),
-        None,
+    let fields = match product_fields(&input, "Contravariant") {
+        Ok(fields) => fields,
+        Err(err) => return err,
+    };
+    let generic_type = match decide_functor_generic_type(&input, "Contravariant") {
+        Ok(t) => t,
+        Err(err) => return err,
+    };
+    if let Err(err) = check_unbounded(&input, &[generic_type], "Contravariant") {
+        return err;
     }
 
-    #[test]
-    fn derive_functor() {
-        assert_eq!(
-            (FunctorNamed { named: 2u32 }).fmap(|x| x + 3),
-            FunctorNamed { named: 5u32 }
-        );
+    let inputs = HashMap::from([(
+        generic_type.ident.clone(),
+        Ident::new("f", Span::call_site()),
+    )]);
+    let outputs = HashMap::new();
+    let renames = HashMap::from([(generic_type.ident.clone(), format_ident!("DerivedType"))]);
+    let mapper = match VarianceMapper::new(
+        "Contravariant",
+        &input,
+        &fields,
+        &inputs,
+        &outputs,
+        &renames,
+    ) {
+        Ok(mapper) => mapper,
+        Err(err) => return err,
+    };
+    let lifetime = mapper.lifetime.clone();
+    let body = match mapper.body(&input, &fields) {
+        Ok(body) => body,
+        Err(err) => return err,
+    };
 
-        assert_eq!(FunctorUnnamed(2u32).fmap(|x| x + 3), FunctorUnnamed(5u32));
+    let target = retarget(
+        &input,
+        &[(generic_type, &format_ident!("DerivedTargetType"))],
+    );
+    let mut generics = input.generics.clone();
+    let ident = &generic_type.ident;
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#ident: #lifetime));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let generic_type = &generic_type.ident;
 
-        assert_eq!(
-            FunctorEnum::Some(2u32).fmap(|x| x + 3),
-            FunctorEnum::Some(5u32)
-        );
-        assert_eq!(
-            FunctorEnum::<u32>::SomeNumber(2).fmap(|x| x + 3),
-            FunctorEnum::<u32>::SomeNumber(2)
+    quote!(
+        impl #impl_generics ::higher::Contravariant<#lifetime, #generic_type> for #name #ty_generics
+                #where_clause {
+            type Target<DerivedTargetType> = #target where DerivedTargetType: #lifetime;
+            fn contramap<DerivedType, DerivedFunction>(self, f: DerivedFunction) -> Self::Target<DerivedType>
+            where
+                DerivedType: #lifetime,
+                DerivedFunction: Fn(DerivedType) -> #generic_type + #lifetime
+            {
+                #body
+            }
+        }
+    )
+    .into()
+}
+
+#[proc_macro_derive(Profunctor, attributes(bifunctor))]
+pub fn derive_profunctor(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let fields = match product_fields(&input, "Profunctor") {
+        Ok(fields) => fields,
+        Err(err) => return err,
+    };
+    let (generic_type_a, generic_type_b) =
+        match decide_bifunctor_generic_types(&input, "Profunctor") {
+            Ok(t) => t,
+            Err(err) => return err,
+        };
+    if let Err(err) = check_unbounded(&input, &[generic_type_a, generic_type_b], "Profunctor") {
+        return err;
+    }
+
+    let inputs = HashMap::from([(
+        generic_type_a.ident.clone(),
+        Ident::new("left", Span::call_site()),
+    )]);
+    let outputs = HashMap::from([(
+        generic_type_b.ident.clone(),
+        Ident::new("right", Span::call_site()),
+    )]);
+    let renames = HashMap::from([(generic_type_a.ident.clone(), format_ident!("DerivedTypeA"))]);
+    let mapper =
+        match VarianceMapper::new("Profunctor", &input, &fields, &inputs, &outputs, &renames) {
+            Ok(mapper) => mapper,
+            Err(err) => return err,
+        };
+    let lifetime = mapper.lifetime.clone();
+    let body = match mapper.body(&input, &fields) {
+        Ok(body) => body,
+        Err(err) => return err,
+    };
+
+    let target = retarget(
+        &input,
+        &[
+            (generic_type_a, &format_ident!("DerivedTargetTypeA")),
+            (generic_type_b, &format_ident!("DerivedTargetTypeB")),
+        ],
+    );
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for param in [generic_type_a, generic_type_b] {
+        let ident = &param.ident;
+        where_clause
+            .predicates
+            .push(parse_quote!(#ident: #lifetime));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let generic_type_a = &generic_type_a.ident;
+    let generic_type_b = &generic_type_b.ident;
+
+    quote!(
+        impl #impl_generics ::higher::Profunctor<#lifetime, #generic_type_a, #generic_type_b> for #name #ty_generics
+                #where_clause {
+            type Target<DerivedTargetTypeA, DerivedTargetTypeB> = #target where DerivedTargetTypeA: #lifetime, DerivedTargetTypeB: #lifetime;
+            fn dimap<DerivedTypeA, DerivedTypeB, DerivedLeft, DerivedRight>(self, left: DerivedLeft, right: DerivedRight) -> Self::Target<DerivedTypeA, DerivedTypeB>
+            where
+                DerivedTypeA: #lifetime,
+                DerivedTypeB: #lifetime,
+                DerivedLeft: Fn(DerivedTypeA) -> #generic_type_a + #lifetime,
+                DerivedRight: Fn(#generic_type_b) -> DerivedTypeB + #lifetime
+            {
+                #body
+            }
+        }
+    )
+    .into()
+}
+
+#[proc_macro_derive(Semigroup, attributes(semigroup))]
+pub fn derive_semigroup(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let fields = match product_fields(&input, "Semigroup") {
+        Ok(fields) => fields,
+        Err(err) => return err,
+    };
+
+    let mut generics = input.generics.clone();
+    let mut values = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let member = field_member(field, index);
+        let (ty, value) = match semigroup_strategy(field) {
+            Ok(Some(Strategy { wrapper, .. })) => {
+                let field_ty = &field.ty;
+                (
+                    quote!(#wrapper<#field_ty>),
+                    quote! { ::higher::Semigroup::mappend(#wrapper(self.#member), #wrapper(other.#member)).0 },
+                )
+            }
+            Ok(None) => {
+                let field_ty = &field.ty;
+                (
+                    quote!(#field_ty),
+                    quote! { ::higher::Semigroup::mappend(self.#member, other.#member) },
+                )
+            }
+            Err(err) => return err,
+        };
+        if mentions_type_params(&input, &field.ty) {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#ty: ::higher::Semigroup));
+        }
+        values.push(quote! { #member: #value });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote!(
+        impl #impl_generics ::higher::Semigroup for #name #ty_generics #where_clause {
+            fn mappend(self, other: Self) -> Self {
+                #name { #(#values,)* }
+            }
+        }
+    )
+    .into()
+}
+
+#[proc_macro_derive(Monoid, attributes(semigroup))]
+pub fn derive_monoid(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let fields = match product_fields(&input, "Monoid") {
+        Ok(fields) => fields,
+        Err(err) => return err,
+    };
+
+    let mut generics = input.generics.clone();
+    let mut values = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let member = field_member(field, index);
+        let field_ty = &field.ty;
+        let (ty, value) = match semigroup_strategy(field) {
+            Ok(Some(Strategy {
+                wrapper,
+                has_identity,
+            })) => {
+                if !has_identity {
+                    return report_error(
+                        field_span(field),
+                        "can't derive Monoid for a field using `max`, `min`, `first` or `last`, which have no identity value",
+                    );
+                }
+                (
+                    quote!(#wrapper<#field_ty>),
+                    quote! { <#wrapper<#field_ty> as ::std::default::Default>::default().0 },
+                )
+            }
+            Ok(None) => (
+                quote!(#field_ty),
+                quote! { <#field_ty as ::std::default::Default>::default() },
+            ),
+            Err(err) => return err,
+        };
+        if mentions_type_params(&input, &field.ty) {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#ty: ::std::default::Default));
+        }
+        values.push(quote! { #member: #value });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote!(
+        impl #impl_generics ::std::default::Default for #name #ty_generics #where_clause {
+            fn default() -> Self {
+                #name { #(#values,)* }
+            }
+        }
+    )
+    .into()
+}
+
+#[proc_macro_derive(Lenses)]
+pub fn derive_lenses(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let fields = match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        Data::Struct(_) => {
+            return report_error(
+                input.ident.span(),
+                "can't derive Lenses for a struct without named fields",
+            )
+        }
+        Data::Enum(_) => {
+            return report_error(
+                input.ident.span(),
+                "can't derive Lenses for an enum, try deriving Prisms instead",
+            )
+        }
+        Data::Union(_) => {
+            return report_error(input.ident.span(), "can't derive Lenses for a union type")
+        }
+    };
+
+    let mut methods = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        let vis = &field.vis;
+        let ty = &field.ty;
+        let clone = clone_bound(&input, ty);
+        let lens = format_ident!("{}_lens", ident);
+        let lens_doc = format!("A lens focusing on the `{ident}` field.");
+        methods.push(quote! {
+            #[doc = #lens_doc]
+            #vis fn #lens<'derivedlifetime>() -> ::higher::optics::Lens<'derivedlifetime, Self, #ty>
+            where
+                #clone
+            {
+                ::higher::optics::Lens::new(
+                    |s: &Self| ::std::clone::Clone::clone(&s.#ident),
+                    |mut s: Self, value| {
+                        s.#ident = value;
+                        s
+                    },
+                )
+            }
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote!(
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#methods)*
+        }
+    )
+    .into()
+}
+
+#[proc_macro_derive(Prisms)]
+pub fn derive_prisms(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let vis = &input.vis;
+
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        Data::Struct(_) => {
+            return report_error(
+                input.ident.span(),
+                "can't derive Prisms for a struct, try deriving Lenses instead",
+            )
+        }
+        Data::Union(_) => {
+            return report_error(input.ident.span(), "can't derive Prisms for a union type")
+        }
+    };
+
+    // A pattern for each variant, which also works as an expression to
+    // rebuild the variant from its bindings.
+    let patterns: Vec<TokenStream> = variants
+        .iter()
+        .map(|variant| {
+            let variant_name = &variant.ident;
+            let bindings =
+                (0..variant.fields.len()).map(|index| format_ident!("derived_{}", index));
+            match &variant.fields {
+                Fields::Named(fields) => {
+                    let members = fields.named.iter().map(|field| &field.ident);
+                    quote!(#variant_name { #(#members: #bindings),* })
+                }
+                Fields::Unnamed(_) => quote!(#variant_name(#(#bindings),*)),
+                Fields::Unit => quote!(#variant_name),
+            }
+        })
+        .collect();
+
+    let mut methods = Vec::new();
+    for (index, variant) in variants.iter().enumerate() {
+        let variant_name = &variant.ident;
+        let pattern = &patterns[index];
+        let types: Vec<&Type> = variant.fields.iter().map(|field| &field.ty).collect();
+        let bindings: Vec<Ident> = (0..types.len())
+            .map(|index| format_ident!("derived_{}", index))
+            .collect();
+        let clones = types.iter().map(|ty| clone_bound(&input, ty));
+
+        // The contents of a variant are a single value if it has one field,
+        // or a tuple otherwise.
+        let (payload, payload_ty, cloned) = if types.len() == 1 {
+            let (binding, ty) = (&bindings[0], types[0]);
+            (
+                quote!(#binding),
+                quote!(#ty),
+                quote!(::std::clone::Clone::clone(#binding)),
+            )
+        } else {
+            (
+                quote!((#(#bindings),*)),
+                quote!((#(#types),*)),
+                quote!((#(::std::clone::Clone::clone(#bindings)),*)),
+            )
+        };
+
+        let snake_name = snake_case(variant_name);
+        let prism = format_ident!("{}_prism", snake_name);
+        let prism_doc = format!("A prism focusing on the `{variant_name}` variant.");
+        methods.push(quote! {
+            #[doc = #prism_doc]
+            #vis fn #prism<'derivedlifetime>() -> ::higher::optics::Prism<'derivedlifetime, Self, #payload_ty>
+            where
+                #(#clones,)*
+            {
+                ::higher::optics::Prism::new(
+                    |s: &Self| match s {
+                        Self::#pattern => ::std::option::Option::Some(#cloned),
+                        #[allow(unreachable_patterns)]
+                        _ => ::std::option::Option::None,
+                    },
+                    |#payload| Self::#pattern,
+                )
+            }
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote!(
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#methods)*
+        }
+    )
+    .into()
+}
+
+/// Get the fields of a struct, which is the only kind of type with a
+/// field-wise `Semigroup`.
+fn product_fields<'a>(
+    input: &'a DeriveInput,
+    trait_name: &str,
+) -> Result<Vec<&'a Field>, proc_macro::TokenStream> {
+    match &input.data {
+        Data::Struct(data) => Ok(data.fields.iter().collect()),
+        Data::Enum(_) => Err(report_error(
+            input.ident.span(),
+            &format!("can't derive {trait_name} for an enum, only for structs"),
+        )),
+        Data::Union(_) => Err(report_error(
+            input.ident.span(),
+            &format!("can't derive {trait_name} for a union type"),
+        )),
+    }
+}
+
+/// The name of a struct field, or its index for a tuple struct.
+fn field_member(field: &Field, index: usize) -> Member {
+    match &field.ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index::from(index)),
+    }
+}
+
+fn field_span(field: &Field) -> Span {
+    match &field.ident {
+        Some(ident) => ident.span(),
+        None => field.ty.span(),
+    }
+}
+
+fn mentions_type_params(input: &DeriveInput, ty: &Type) -> bool {
+    FieldVisitor::new(&type_param_map(input), false).mentions_any(ty)
+}
+
+fn type_param_map(input: &DeriveInput) -> HashMap<Ident, Ident> {
+    input
+        .generics
+        .type_params()
+        .map(|param| (param.ident.clone(), param.ident.clone()))
+        .collect()
+}
+
+/// Require a field type to be `Clone`.
+///
+/// If the type doesn't mention any type parameters, the bound is made
+/// higher-ranked so that it's only checked when the method using it is
+/// called, rather than failing to compile for a field which isn't `Clone`.
+fn clone_bound(input: &DeriveInput, ty: &Type) -> TokenStream {
+    if mentions_type_params(input, ty) {
+        quote!(#ty: ::std::clone::Clone)
+    } else {
+        quote!(for<'derivedclone> #ty: ::std::clone::Clone)
+    }
+}
+
+/// Turn a variant name like `HttpError` into `http_error`.
+fn snake_case(ident: &Ident) -> String {
+    let name = ident.to_string();
+    let chars: Vec<char> = name.trim_start_matches("r#").chars().collect();
+    let mut output = String::new();
+    for (index, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && index > 0 {
+            let prev = chars[index - 1];
+            let next_lower = chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_lower) {
+                output.push('_');
+            }
+        }
+        output.extend(c.to_lowercase());
+    }
+    output
+}
+
+fn rename_idents(tokens: TokenStream, renames: &HashMap<Ident, Ident>) -> TokenStream {
+    let mut output = TokenStream::new();
+    let mut lifetime = false;
+    for token in tokens {
+        let token = match token {
+            TokenTree::Group(group) => {
+                let mut renamed =
+                    Group::new(group.delimiter(), rename_idents(group.stream(), renames));
+                renamed.set_span(group.span());
+                TokenTree::Group(renamed)
+            }
+            TokenTree::Ident(ident) if !lifetime => match renames.get(&ident) {
+                Some(renamed) => TokenTree::Ident(renamed.clone()),
+                None => TokenTree::Ident(ident),
+            },
+            token => token,
+        };
+        lifetime = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == '\'');
+        output.extend(Some(token));
+    }
+    output
+}
+
+/// A newtype to wrap a field in, to pick which `Semigroup` it uses.
+struct Strategy {
+    wrapper: TokenStream,
+    /// Whether the newtype's default value is an identity for `mappend`.
+    has_identity: bool,
+}
+
+/// Read the `#[semigroup(...)]` attribute on a field, if it has one.
+fn semigroup_strategy(field: &Field) -> Result<Option<Strategy>, proc_macro::TokenStream> {
+    let mut strategy = None;
+    for attr in &field.attrs {
+        if !attr.path.is_ident("semigroup") {
+            continue;
+        }
+        if strategy.is_some() {
+            return Err(report_error(
+                attr.span(),
+                "a field can only have one #[semigroup] attribute",
+            ));
+        }
+        let ident: Ident = match attr.parse_args() {
+            Ok(ident) => ident,
+            Err(err) => return Err(err.to_compile_error().into()),
+        };
+        let (wrapper, has_identity) = match ident.to_string().as_str() {
+            "additive" => (quote!(::higher::monoid::Additive), true),
+            "multiplicative" => (quote!(::higher::monoid::Multiplicative), true),
+            "max" => (quote!(::higher::semigroup::Max), false),
+            "min" => (quote!(::higher::semigroup::Min), false),
+            "first" => (quote!(::higher::semigroup::First), false),
+            "last" => (quote!(::higher::semigroup::Last), false),
+            _ => {
+                return Err(report_error(
+                    ident.span(),
+                    "unknown semigroup strategy; expected one of `additive`, `multiplicative`, `max`, `min`, `first` or `last`",
+                ))
+            }
+        };
+        strategy = Some(Strategy {
+            wrapper,
+            has_identity,
+        });
+    }
+    Ok(strategy)
+}
+
+/// How a field refers to the type parameters it mentions.
+enum FieldShape<'t> {
+    /// The field is a type parameter.
+    Param(&'t Ident),
+    /// The field is a functor, foldable or traversable of the given type.
+    Nested(&'t Type),
+    /// The field is a tuple.
+    Tuple(&'t Punctuated<Type, Comma>),
+}
+
+/// Which fold a [`FieldVisitor`] is building.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FoldKind {
+    Right,
+    Left,
+    Map,
+}
+
+/// Builds the expressions which map or fold over the type parameters found
+/// inside a field, recursing through tuples, arrays and other containers.
+struct FieldVisitor<'a> {
+    functions: &'a HashMap<Ident, Ident>,
+    as_ref: bool,
+    /// The lifetime the closures built for nested types live for.
+    lifetime: TokenStream,
+    /// Functions which get captured by a nested closure, and so need to be
+    /// shared through an `Rc`.
+    captured: RefCell<Vec<Ident>>,
+}
+
+impl<'a> FieldVisitor<'a> {
+    fn new(functions: &'a HashMap<Ident, Ident>, as_ref: bool) -> Self {
+        Self {
+            functions,
+            as_ref,
+            lifetime: quote!('derivedlifetime),
+            captured: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn with_lifetime(mut self, lifetime: &Lifetime) -> Self {
+        self.lifetime = lifetime.to_token_stream();
+        self
+    }
+
+    /// Find the type parameters mentioned in a type, in order.
+    fn mentions(&self, tokens: TokenStream) -> Vec<Ident> {
+        let mut found = Vec::new();
+        let mut lifetime = false;
+        for token in tokens {
+            match token {
+                TokenTree::Group(group) => {
+                    for ident in self.mentions(group.stream()) {
+                        if !found.contains(&ident) {
+                            found.push(ident);
+                        }
+                    }
+                }
+                TokenTree::Punct(punct) => lifetime = punct.as_char() == '\'',
+                TokenTree::Ident(ident) => {
+                    if !lifetime && self.functions.contains_key(&ident) && !found.contains(&ident) {
+                        found.push(ident);
+                    }
+                    lifetime = false;
+                }
+                TokenTree::Literal(_) => lifetime = false,
+            }
+        }
+        found
+    }
+
+    fn mentions_any(&self, tokens: &impl ToTokens) -> bool {
+        !self.mentions(tokens.to_token_stream()).is_empty()
+    }
+
+    fn error<T: ToTokens>(&self, tokens: T, msg: &str) -> proc_macro::TokenStream {
+        let param = self.mentions(tokens.to_token_stream()).remove(0);
+        report_error(tokens.span(), &msg.replace("{}", &param.to_string()))
+    }
+
+    /// Make sure a function type doesn't take the type parameter as an
+    /// argument.
+    fn check_inputs<'b, I>(&self, inputs: I) -> Result<(), proc_macro::TokenStream>
+    where
+        I: IntoIterator<Item = &'b Type>,
+    {
+        for input in inputs {
+            if self.mentions_any(input) {
+                return Err(self.error(
+                    input,
+                    "type parameter `{}` appears in a contravariant position here",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Work out how a field type contains the type parameters, or return
+    /// `None` if it doesn't contain any.
+    fn shape<'t>(&self, ty: &'t Type) -> Result<Option<FieldShape<'t>>, proc_macro::TokenStream> {
+        if !self.mentions_any(ty) {
+            return Ok(None);
+        }
+        match ty {
+            Type::Paren(inner) => self.shape(&inner.elem),
+            Type::Group(inner) => self.shape(&inner.elem),
+            Type::Path(path) => {
+                if path.qself.is_none() {
+                    if let Some(ident) = path.path.get_ident() {
+                        return Ok(Some(FieldShape::Param(ident)));
+                    }
+                }
+                let (last, init) = match path.path.segments.last() {
+                    Some(last) => (last, path.path.segments.len() - 1),
+                    None => return Ok(None),
+                };
+                let prefix_mentions = path.qself.iter().any(|qself| self.mentions_any(&qself.ty))
+                    || path
+                        .path
+                        .segments
+                        .iter()
+                        .take(init)
+                        .any(|segment| self.mentions_any(segment));
+                if prefix_mentions {
+                    return Err(self.error(ty, "can't derive through an associated type of `{}`"));
+                }
+                let mut inner = None;
+                match &last.arguments {
+                    PathArguments::AngleBracketed(args) => {
+                        for arg in &args.args {
+                            if !self.mentions_any(arg) {
+                                continue;
+                            }
+                            match arg {
+                                GenericArgument::Type(arg) if inner.is_none() => inner = Some(arg),
+                                _ => return Err(self.error(
+                                    ty,
+                                    "type parameter `{}` can only appear in one type argument here",
+                                )),
+                            }
+                        }
+                    }
+                    PathArguments::Parenthesized(args) => {
+                        self.check_inputs(&args.inputs)?;
+                    }
+                    PathArguments::None => {}
+                }
+                match inner {
+                    Some(inner) => Ok(Some(FieldShape::Nested(inner))),
+                    None => {
+                        Err(self.error(ty, "don't know how to derive through this use of `{}`"))
+                    }
+                }
+            }
+            Type::Array(array) => Ok(Some(FieldShape::Nested(&array.elem))),
+            Type::Tuple(tuple) => Ok(Some(FieldShape::Tuple(&tuple.elems))),
+            Type::BareFn(function) => {
+                self.check_inputs(function.inputs.iter().map(|arg| &arg.ty))?;
+                Err(self.error(ty, "can't derive through `{}` inside a function pointer"))
+            }
+            Type::TraitObject(TypeTraitObject { bounds, .. })
+            | Type::ImplTrait(TypeImplTrait { bounds, .. }) => {
+                for bound in bounds {
+                    if let TypeParamBound::Trait(bound) = bound {
+                        for segment in &bound.path.segments {
+                            if let PathArguments::Parenthesized(args) = &segment.arguments {
+                                self.check_inputs(&args.inputs)?;
+                            }
+                        }
+                    }
+                }
+                Err(self.error(ty, "can't derive through `{}` inside a trait object"))
+            }
+            Type::Reference(_) | Type::Ptr(_) => {
+                Err(self.error(ty, "can't derive through `{}` behind a reference"))
+            }
+            _ => Err(self.error(ty, "don't know how to derive through this use of `{}`")),
+        }
+    }
+
+    /// Note that the functions for the type parameters in `inner` are used
+    /// inside a nested closure, and return the statements which give the
+    /// closure its own handle on them.
+    fn capture(&self, inner: &Type) -> TokenStream {
+        let functions: Vec<Ident> = self
+            .mentions(inner.to_token_stream())
+            .iter()
+            .map(|param| self.functions[param].clone())
+            .collect();
+        let mut captured = self.captured.borrow_mut();
+        for function in &functions {
+            if !captured.contains(function) {
+                captured.push(function.clone());
+            }
+        }
+        quote! { #(let #functions = ::std::rc::Rc::clone(&#functions);)* }
+    }
+
+    /// Box up a closure for a nested container behind a `dyn Fn`, so that
+    /// recursive types like `Box<Self>` don't instantiate the derived
+    /// methods with an ever growing closure type.
+    fn erase(&self, captures: TokenStream, closure: TokenStream, args: TokenStream) -> TokenStream {
+        let lifetime = &self.lifetime;
+        quote! {
+            {
+                #captures
+                let erased: ::std::boxed::Box<dyn Fn(#args) -> _ + #lifetime> =
+                    ::std::boxed::Box::new(#closure);
+                erased
+            }
+        }
+    }
+
+    fn tuple_names(&self, elems: &Punctuated<Type, Comma>, depth: usize) -> Vec<Ident> {
+        (0..elems.len())
+            .map(|index| Ident::new(&format!("derived_tuple_{depth}_{index}"), Span::call_site()))
+            .collect()
+    }
+
+    /// Map a field value using the functions for the type parameters it
+    /// contains, or return `None` if it doesn't contain any.
+    ///
+    /// The value is owned, or a reference if we're deriving one of the `Ref`
+    /// traits.
+    fn map(
+        &self,
+        ty: &Type,
+        value: TokenStream,
+        depth: usize,
+    ) -> Result<Option<TokenStream>, proc_macro::TokenStream> {
+        Ok(Some(match self.shape(ty)? {
+            None => return Ok(None),
+            Some(FieldShape::Param(param)) => {
+                let function = &self.functions[param];
+                quote! { #function(#value) }
+            }
+            Some(FieldShape::Nested(inner)) => {
+                let name = Ident::new(&format!("derived_value_{depth}"), Span::call_site());
+                let mapped = self
+                    .map(inner, quote!(#name), depth + 1)?
+                    .expect("inner type mentions a type parameter");
+                let captures = self.capture(inner);
+                let closure = if self.as_ref {
+                    self.erase(captures, quote!(move |#name: &_| #mapped), quote!(&_))
+                } else {
+                    self.erase(captures, quote!(move |#name| #mapped), quote!(_))
+                };
+                if self.as_ref {
+                    quote! { ::higher::FunctorRef::fmap_ref(#value, #closure) }
+                } else {
+                    quote! { ::higher::Functor::fmap(#value, #closure) }
+                }
+            }
+            Some(FieldShape::Tuple(elems)) => {
+                let names = self.tuple_names(elems, depth);
+                let elems = elems
+                    .iter()
+                    .zip(names.iter())
+                    .map(|(elem, name)| {
+                        Ok(match self.map(elem, quote!(#name), depth + 1)? {
+                            Some(mapped) => mapped,
+                            None => self.copy(quote!(#name)),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, proc_macro::TokenStream>>()?;
+                quote! {
+                    {
+                        let (#(#names,)*) = #value;
+                        (#(#elems,)*)
+                    }
+                }
+            }
+        }))
+    }
+
+    /// Fold a field value into an accumulator, or return `None` if it
+    /// doesn't contain any type parameters. Tuple elements are folded in
+    /// declaration order, or in reverse for a right fold.
+    fn fold(
+        &self,
+        ty: &Type,
+        value: TokenStream,
+        acc: TokenStream,
+        kind: FoldKind,
+        depth: usize,
+    ) -> Result<Option<TokenStream>, proc_macro::TokenStream> {
+        Ok(Some(match self.shape(ty)? {
+            None => return Ok(None),
+            Some(FieldShape::Param(param)) => {
+                let function = &self.functions[param];
+                match kind {
+                    FoldKind::Right => quote! { #function(#value, #acc) },
+                    FoldKind::Left => quote! { #function(#acc, #value) },
+                    FoldKind::Map => {
+                        quote! { ::higher::Semigroup::mappend(#acc, #function(#value)) }
+                    }
+                }
+            }
+            Some(FieldShape::Nested(inner)) => {
+                let name = Ident::new(&format!("derived_value_{depth}"), Span::call_site());
+                let inner_acc = Ident::new(&format!("derived_acc_{depth}"), Span::call_site());
+                let folded = self
+                    .fold(inner, quote!(#name), quote!(#inner_acc), kind, depth + 1)?
+                    .expect("inner type mentions a type parameter");
+                let captures = self.capture(inner);
+                let suffix = if self.as_ref { "_ref" } else { "" };
+                // Ref folds hand out references which live as long as the
+                // structure, and the closure has to say so, or patterns in
+                // it would expect an owned value.
+                let lifetime = &self.lifetime;
+                let (param, arg) = if self.as_ref {
+                    (quote!(#name: &#lifetime _), quote!(&#lifetime _))
+                } else {
+                    (quote!(#name), quote!(_))
+                };
+                match kind {
+                    FoldKind::Right => {
+                        let method = format_ident!("foldr{}", suffix);
+                        let closure = self.erase(
+                            captures,
+                            quote!(move |#param, #inner_acc| #folded),
+                            quote!(#arg, _),
+                        );
+                        quote! { ::higher::Foldable::#method(#value, #closure, #acc) }
+                    }
+                    FoldKind::Left => {
+                        let method = format_ident!("foldl{}", suffix);
+                        let closure = self.erase(
+                            captures,
+                            quote!(move |#inner_acc, #param| #folded),
+                            quote!(_, #arg),
+                        );
+                        quote! { ::higher::Foldable::#method(#value, #closure, #acc) }
+                    }
+                    FoldKind::Map => {
+                        let method = format_ident!("fold_map{}", suffix);
+                        let closure = self.erase(
+                            captures,
+                            quote! {
+                                move |#param| {
+                                    let #inner_acc = ::std::default::Default::default();
+                                    #folded
+                                }
+                            },
+                            quote!(#arg),
+                        );
+                        quote! {
+                            ::higher::Semigroup::mappend(
+                                #acc,
+                                ::higher::Foldable::#method(#value, #closure),
+                            )
+                        }
+                    }
+                }
+            }
+            Some(FieldShape::Tuple(elems)) => {
+                let acc_name = Ident::new(&format!("derived_acc_{depth}"), Span::call_site());
+                let mut names = Vec::new();
+                let mut steps = Vec::new();
+                for (elem, name) in elems.iter().zip(self.tuple_names(elems, depth)) {
+                    match self.fold(elem, quote!(#name), quote!(#acc_name), kind, depth + 1)? {
+                        Some(step) => {
+                            steps.push(quote! { let #acc_name = #step; });
+                            names.push(quote!(#name));
+                        }
+                        None => names.push(quote!(_)),
+                    }
+                }
+                if kind == FoldKind::Right {
+                    steps.reverse();
+                }
+                quote! {
+                    {
+                        let (#(#names,)*) = #value;
+                        let #acc_name = #acc;
+                        #(#steps)*
+                        #acc_name
+                    }
+                }
+            }
+        }))
+    }
+
+    /// Pass on a value which doesn't contain any type parameters.
+    fn copy(&self, value: TokenStream) -> TokenStream {
+        if self.as_ref {
+            quote! { ::std::clone::Clone::clone(#value) }
+        } else {
+            value
+        }
+    }
+
+    /// Borrow a field accessed through `self`, if we're deriving one of the
+    /// `Ref` traits.
+    fn access(&self, value: TokenStream) -> TokenStream {
+        if self.as_ref {
+            quote! { &#value }
+        } else {
+            value
+        }
+    }
+
+    /// Map or copy a field, given an expression for its owned value.
+    fn field(&self, ty: &Type, value: TokenStream) -> Result<TokenStream, proc_macro::TokenStream> {
+        self.bound_field(ty, self.access(value))
+    }
+
+    /// Map or copy a field bound by a pattern match, which is already a
+    /// reference if we're deriving one of the `Ref` traits.
+    fn bound_field(
+        &self,
+        ty: &Type,
+        value: TokenStream,
+    ) -> Result<TokenStream, proc_macro::TokenStream> {
+        Ok(match self.map(ty, value.clone(), 0)? {
+            Some(mapped) => mapped,
+            None => self.copy(value),
+        })
+    }
+
+    /// Wrap up the generated expression, sharing any functions which are
+    /// captured by nested closures.
+    fn finish(self, body: TokenStream) -> TokenStream {
+        let captured = self.captured.into_inner();
+        quote! {
+            #(let #captured = ::std::rc::Rc::new(#captured);)*
+            #body
+        }
+    }
+}
+
+/// A boxed closure, like `Box<dyn Fn(A) -> B + 'a>`.
+struct BoxedFunction<'t> {
+    /// The function to box up a new closure with.
+    constructor: TokenStream,
+    /// Whether the closure is `FnMut`, and so has to be called mutably.
+    mutable: bool,
+    inputs: &'t Punctuated<Type, Comma>,
+    output: Option<&'t Type>,
+    lifetime: Option<&'t Lifetime>,
+}
+
+impl<'t> BoxedFunction<'t> {
+    fn parse(ty: &'t Type) -> Option<Self> {
+        let segment = match ty {
+            Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+            Type::Paren(inner) => return Self::parse(&inner.elem),
+            Type::Group(inner) => return Self::parse(&inner.elem),
+            _ => return None,
+        };
+        let constructor = match segment.ident.to_string().as_str() {
+            "Box" => quote!(::std::boxed::Box::new),
+            "Rc" => quote!(::std::rc::Rc::new),
+            _ => return None,
+        };
+        let object = match &segment.arguments {
+            PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+                GenericArgument::Type(Type::TraitObject(object)) => object,
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let mut function = None;
+        let mut lifetime = None;
+        for bound in &object.bounds {
+            if let TypeParamBound::Lifetime(bound) = bound {
+                lifetime = Some(bound);
+            }
+            if let TypeParamBound::Trait(bound) = bound {
+                let segment = bound.path.segments.last()?;
+                if let PathArguments::Parenthesized(args) = &segment.arguments {
+                    let mutable = match segment.ident.to_string().as_str() {
+                        "Fn" | "FnOnce" => false,
+                        "FnMut" => true,
+                        _ => return None,
+                    };
+                    function = Some((mutable, args));
+                }
+            }
+        }
+        let (mutable, args) = function?;
+        Some(Self {
+            constructor,
+            mutable,
+            inputs: &args.inputs,
+            output: match &args.output {
+                ReturnType::Default => None,
+                ReturnType::Type(_, ty) => Some(ty),
+            },
+            lifetime,
+        })
+    }
+}
+
+/// Builds the fields of a `Contravariant` or `Profunctor` value, mostly by
+/// wrapping its boxed functions in new ones which map their inputs and
+/// outputs.
+struct VarianceMapper<'a> {
+    trait_name: &'a str,
+    /// Maps the type parameters which appear as function inputs.
+    inputs: FieldVisitor<'a>,
+    /// Maps the type parameters which appear as function outputs.
+    outputs: FieldVisitor<'a>,
+    /// What the input type parameters are called in the new value.
+    renames: &'a HashMap<Ident, Ident>,
+    /// The lifetime the impl is for.
+    lifetime: Lifetime,
+    /// Functions which get captured, and so need to be shared through an
+    /// `Rc`.
+    captured: RefCell<Vec<Ident>>,
+}
+
+impl<'a> VarianceMapper<'a> {
+    /// The lifetime of the impl is the lifetime of the boxed functions if
+    /// there are any, which all have to agree. Otherwise it's the type's
+    /// lifetime parameter, if it has one, or `'static`.
+    fn new(
+        trait_name: &'a str,
+        input: &DeriveInput,
+        fields: &[&Field],
+        inputs: &'a HashMap<Ident, Ident>,
+        outputs: &'a HashMap<Ident, Ident>,
+        renames: &'a HashMap<Ident, Ident>,
+    ) -> Result<Self, proc_macro::TokenStream> {
+        let inputs = FieldVisitor::new(inputs, false);
+        let outputs = FieldVisitor::new(outputs, false);
+        let mut lifetime: Option<Lifetime> = None;
+        for field in fields {
+            if !inputs.mentions_any(&field.ty) && !outputs.mentions_any(&field.ty) {
+                continue;
+            }
+            let function = match BoxedFunction::parse(&field.ty) {
+                Some(function) => function,
+                None => continue,
+            };
+            let found = function
+                .lifetime
+                .cloned()
+                .unwrap_or_else(|| parse_quote!('static));
+            match &lifetime {
+                Some(lifetime) if *lifetime != found => {
+                    return Err(report_error(
+                        field.ty.span(),
+                        &format!(
+                            "can't derive {trait_name} when boxed functions live for different lifetimes; this one lives for `{found}`, but an earlier one lives for `{lifetime}`"
+                        ),
+                    ))
+                }
+                Some(_) => {}
+                None => lifetime = Some(found),
+            }
+        }
+        let lifetime = match lifetime {
+            Some(lifetime) => lifetime,
+            None => match input.generics.lifetimes().next() {
+                Some(param) => param.lifetime.clone(),
+                None => parse_quote!('static),
+            },
+        };
+
+        Ok(Self {
+            trait_name,
+            inputs: inputs.with_lifetime(&lifetime),
+            outputs: outputs.with_lifetime(&lifetime),
+            renames,
+            lifetime,
+            captured: RefCell::new(Vec::new()),
+        })
+    }
+
+    fn mentions_any(&self, tokens: &impl ToTokens) -> bool {
+        self.inputs.mentions_any(tokens) || self.outputs.mentions_any(tokens)
+    }
+
+    fn error<T: ToTokens>(&self, tokens: T, msg: &str) -> proc_macro::TokenStream {
+        let msg = msg.replace("{trait}", self.trait_name);
+        if self.inputs.mentions_any(&tokens) {
+            self.inputs.error(tokens, &msg)
+        } else {
+            self.outputs.error(tokens, &msg)
+        }
+    }
+
+    /// Take a copy of the functions a type needs, to move into a closure.
+    fn capture(&self, tokens: &impl ToTokens) -> TokenStream {
+        let mut functions = Vec::new();
+        for visitor in [&self.inputs, &self.outputs] {
+            for param in visitor.mentions(tokens.to_token_stream()) {
+                functions.push(visitor.functions[&param].clone());
+            }
+        }
+        let mut captured = self.captured.borrow_mut();
+        for function in &functions {
+            if !captured.contains(function) {
+                captured.push(function.clone());
+            }
+        }
+        quote! { #(let #functions = ::std::rc::Rc::clone(&#functions);)* }
+    }
+
+    fn field(&self, ty: &Type, value: TokenStream) -> Result<TokenStream, proc_macro::TokenStream> {
+        if !self.mentions_any(ty) {
+            return Ok(value);
+        }
+        if let Some(function) = BoxedFunction::parse(ty) {
+            return self.function(ty, &function, value);
+        }
+        if let Type::BareFn(_) = ty {
+            return Err(self.error(
+                ty,
+                "can't derive {trait} through a function pointer mentioning `{}`, because it can't capture the function to map with; try a boxed closure like `Box<dyn Fn(..)>` instead",
+            ));
+        }
+        if let Type::Path(path) = ty {
+            if path.qself.is_none()
+                && path.path.segments.last().map(|segment| &segment.ident)
+                    == Some(&format_ident!("PhantomData"))
+            {
+                return Ok(quote!(::std::marker::PhantomData));
+            }
+        }
+        let captures = self.capture(ty);
+        if !self.inputs.mentions_any(ty) {
+            let mapped = self
+                .outputs
+                .map(ty, value, 0)?
+                .expect("type mentions a type parameter");
+            return Ok(quote! {
+                {
+                    #captures
+                    #mapped
+                }
+            });
+        }
+        self.nested(ty, value, captures)
+    }
+
+    /// Wrap a boxed function in a new one which maps its inputs and output.
+    fn function(
+        &self,
+        ty: &Type,
+        function: &BoxedFunction<'_>,
+        value: TokenStream,
+    ) -> Result<TokenStream, proc_macro::TokenStream> {
+        for input in function.inputs {
+            if self.outputs.mentions_any(input) {
+                return Err(self.outputs.error(
+                    input,
+                    "type parameter `{}` appears in a contravariant position here",
+                ));
+            }
+        }
+        if let Some(output) = function.output {
+            if self.inputs.mentions_any(output) {
+                return Err(self.inputs.error(
+                    output,
+                    "type parameter `{}` appears in a covariant position here",
+                ));
+            }
+        }
+
+        let names: Vec<Ident> = (0..function.inputs.len())
+            .map(|index| format_ident!("derived_arg_{}", index))
+            .collect();
+        let types = function
+            .inputs
+            .iter()
+            .map(|ty| rename_idents(ty.to_token_stream(), self.renames));
+        let args = function
+            .inputs
+            .iter()
+            .zip(&names)
+            .map(|(ty, name)| {
+                Ok(match self.inputs.map(ty, quote!(#name), 0)? {
+                    Some(mapped) => mapped,
+                    None => quote!(#name),
+                })
+            })
+            .collect::<Result<Vec<_>, proc_macro::TokenStream>>()?;
+        let call = quote!(derived_function(#(#args),*));
+        let body = match function.output {
+            Some(output) => match self.outputs.map(output, quote!(derived_result), 0)? {
+                Some(mapped) => quote! {
+                    {
+                        let derived_result = #call;
+                        #mapped
+                    }
+                },
+                None => call,
+            },
+            None => call,
+        };
+
+        let captures = self.capture(ty);
+        let binding = if function.mutable {
+            quote!(mut derived_function)
+        } else {
+            quote!(derived_function)
+        };
+        let constructor = &function.constructor;
+        Ok(quote! {
+            {
+                #captures
+                let #binding = #value;
+                #constructor(move |#(#names: #types),*| #body)
+            }
+        })
+    }
+
+    /// Map a field which is itself `Contravariant` or a `Profunctor`.
+    fn nested(
+        &self,
+        ty: &Type,
+        value: TokenStream,
+        captures: TokenStream,
+    ) -> Result<TokenStream, proc_macro::TokenStream> {
+        let mut args = Vec::new();
+        if let Type::Path(path) = ty {
+            if let Some(PathArguments::AngleBracketed(generics)) =
+                path.path.segments.last().map(|segment| &segment.arguments)
+            {
+                for arg in &generics.args {
+                    if let GenericArgument::Type(arg) = arg {
+                        if self.mentions_any(arg) {
+                            args.push(arg);
+                        }
+                    }
+                }
+            }
+        }
+        match args.as_slice() {
+            [arg] if !self.outputs.mentions_any(arg) => {
+                let closure = self.closure(arg, true)?;
+                Ok(quote! {
+                    {
+                        #captures
+                        ::higher::Contravariant::contramap(#value, #closure)
+                    }
+                })
+            }
+            [left, right]
+                if !self.outputs.mentions_any(left) && !self.inputs.mentions_any(right) =>
+            {
+                let left = self.closure(left, true)?;
+                let right = self.closure(right, false)?;
+                Ok(quote! {
+                    {
+                        #captures
+                        ::higher::Profunctor::dimap(#value, #left, #right)
+                    }
+                })
+            }
+            _ => Err(self.error(
+                ty,
+                "don't know how to derive {trait} through this use of `{}`",
+            )),
+        }
+    }
+
+    /// A closure which maps a type argument of a nested field, which takes
+    /// the new type if it's an input, or the old type if it's an output.
+    fn closure(&self, ty: &Type, input: bool) -> Result<TokenStream, proc_macro::TokenStream> {
+        let (visitor, arg) = if input {
+            (
+                &self.inputs,
+                rename_idents(ty.to_token_stream(), self.renames),
+            )
+        } else {
+            (&self.outputs, ty.to_token_stream())
+        };
+        let mapped = visitor
+            .map(ty, quote!(derived_value), 0)?
+            .expect("type mentions a type parameter");
+        Ok(quote!(move |derived_value: #arg| #mapped))
+    }
+
+    /// Build the body of `contramap` or `dimap`.
+    fn body(
+        self,
+        input: &DeriveInput,
+        fields: &[&Field],
+    ) -> Result<TokenStream, proc_macro::TokenStream> {
+        let mut values = Vec::new();
+        for (index, field) in fields.iter().enumerate() {
+            let member = field_member(field, index);
+            let value = self.field(&field.ty, quote!(self.#member))?;
+            values.push(quote! { #member: #value });
+        }
+        let name = &input.ident;
+        let captured = self.captured.into_inner();
+        Ok(quote! {
+            #(let #captured = ::std::rc::Rc::new(#captured);)*
+            #name { #(#values,)* }
+        })
+    }
+}
+
+fn derive_functor_named_struct(
+    name: &Ident,
+    fields: &FieldsNamed,
+    generic_types: &HashMap<Ident, Ident>,
+    as_ref: bool,
+) -> Result<TokenStream, proc_macro::TokenStream> {
+    let mapper = FieldVisitor::new(generic_types, as_ref);
+    let apply_fields = fields
+        .named
+        .iter()
+        .map(|field| {
+            let ident = &field.ident;
+            let value = mapper.field(&field.ty, quote!(self.#ident))?;
+            Ok(quote! { #ident: #value, })
+        })
+        .collect::<Result<Vec<_>, proc_macro::TokenStream>>()?;
+    Ok(mapper.finish(quote! {
+        #name {
+            #(#apply_fields)*
+        }
+    }))
+}
+
+fn derive_functor_unnamed_struct(
+    name: &Ident,
+    fields: &FieldsUnnamed,
+    generic_types: &HashMap<Ident, Ident>,
+    as_ref: bool,
+) -> Result<TokenStream, proc_macro::TokenStream> {
+    let mapper = FieldVisitor::new(generic_types, as_ref);
+    let fields = fields
+        .unnamed
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let index = Index::from(index);
+            let value = mapper.field(&field.ty, quote!(self.#index))?;
+            Ok(quote! { #value, })
+        })
+        .collect::<Result<Vec<_>, proc_macro::TokenStream>>()?;
+    Ok(mapper.finish(quote! { #name(#(#fields)*) }))
+}
+
+fn derive_functor_enum(
+    name: &Ident,
+    data: &DataEnum,
+    generic_types: &HashMap<Ident, Ident>,
+    as_ref: bool,
+) -> Result<TokenStream, proc_macro::TokenStream> {
+    let mapper = FieldVisitor::new(generic_types, as_ref);
+    let variants = data
+        .variants
+        .iter()
+        .map(|variant| {
+            let ident = &variant.ident;
+            Ok(match &variant.fields {
+                Fields::Named(fields) => {
+                    let args: Vec<Ident> = fields
+                        .named
+                        .iter()
+                        .map(|field| {
+                            Ident::new(
+                                &format!("arg_{}", field.ident.clone().unwrap()),
+                                field.ident.clone().unwrap().span(),
+                            )
+                        })
+                        .collect();
+                    let apply = fields
+                        .named
+                        .iter()
+                        .zip(args.clone())
+                        .map(|(field, arg)| {
+                            let name = &field.ident;
+                            let value = mapper.bound_field(&field.ty, quote!(#arg))?;
+                            Ok(quote! { #name: #value })
+                        })
+                        .collect::<Result<Vec<_>, proc_macro::TokenStream>>()?;
+                    let args = fields.named.iter().zip(args).map(|(field, arg)| {
+                        let name = &field.ident;
+                        quote! { #name:#arg }
+                    });
+                    quote! {
+                        #name::#ident { #(#args,)* } => #name::#ident { #(#apply,)* },
+                    }
+                }
+                Fields::Unnamed(fields) => {
+                    let args: Vec<Ident> = fields
+                        .unnamed
+                        .iter()
+                        .enumerate()
+                        .map(|(index, _)| Ident::new(&format!("arg{index}"), Span::call_site()))
+                        .collect();
+                    let fields = fields
+                        .unnamed
+                        .iter()
+                        .zip(args.iter())
+                        .map(|(field, arg)| mapper.bound_field(&field.ty, quote!(#arg)))
+                        .collect::<Result<Vec<_>, proc_macro::TokenStream>>()?;
+                    let args = args.iter();
+                    quote! {
+                        #name::#ident(#(#args,)*) => #name::#ident(#(#fields,)*),
+                    }
+                }
+                Fields::Unit => quote! {
+                    #name::#ident => #name::#ident,
+                },
+            })
+        })
+        .collect::<Result<Vec<_>, proc_macro::TokenStream>>()?;
+    Ok(mapper.finish(quote! {
+        match self {
+            #(#variants)*
+        }
+    }))
+}
+
+/// Build the body of one of the `Foldable` methods, which folds the fields
+/// containing the type parameter in declaration order, or in reverse for a
+/// right fold.
+fn derive_fold(
+    input: &DeriveInput,
+    functions: &HashMap<Ident, Ident>,
+    kind: FoldKind,
+    as_ref: bool,
+    init: &TokenStream,
+) -> Result<TokenStream, proc_macro::TokenStream> {
+    let name = &input.ident;
+    let visitor = FieldVisitor::new(functions, as_ref);
+    let acc = Ident::new("derived_acc", Span::call_site());
+    let fold_fields = |fields: Vec<(&Type, TokenStream)>| {
+        let mut steps = Vec::new();
+        for (ty, value) in fields {
+            if let Some(step) = visitor.fold(ty, value, quote!(#acc), kind, 0)? {
+                steps.push(quote! { let #acc = #step; });
+            }
+        }
+        if kind == FoldKind::Right {
+            steps.reverse();
+        }
+        Ok::<_, proc_macro::TokenStream>(quote! {
+            let #acc = #init;
+            #(#steps)*
+            #acc
+        })
+    };
+    let body = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fold_fields(
+                fields
+                    .named
+                    .iter()
+                    .map(|field| {
+                        let ident = &field.ident;
+                        (&field.ty, visitor.access(quote!(self.#ident)))
+                    })
+                    .collect(),
+            )?,
+            Fields::Unnamed(fields) => fold_fields(
+                fields
+                    .unnamed
+                    .iter()
+                    .enumerate()
+                    .map(|(index, field)| {
+                        let index = Index::from(index);
+                        (&field.ty, visitor.access(quote!(self.#index)))
+                    })
+                    .collect(),
+            )?,
+            Fields::Unit => {
+                return Err(report_error(
+                    name.span(),
+                    "can't derive Foldable for an empty struct",
+                ));
+            }
+        },
+        Data::Enum(data) => {
+            let variants = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let ident = &variant.ident;
+                    Ok(match &variant.fields {
+                        Fields::Named(fields) => {
+                            let mut bindings = Vec::new();
+                            let mut values = Vec::new();
+                            for field in &fields.named {
+                                if visitor.mentions_any(&field.ty) {
+                                    let field_name = field.ident.clone().unwrap();
+                                    let arg = format_ident!("arg_{}", field_name);
+                                    bindings.push(quote! { #field_name: #arg });
+                                    values.push((&field.ty, quote!(#arg)));
+                                }
+                            }
+                            let body = fold_fields(values)?;
+                            quote! {
+                                #name::#ident { #(#bindings,)* .. } => { #body }
+                            }
+                        }
+                        Fields::Unnamed(fields) => {
+                            let mut bindings = Vec::new();
+                            let mut values = Vec::new();
+                            for (index, field) in fields.unnamed.iter().enumerate() {
+                                if visitor.mentions_any(&field.ty) {
+                                    let arg = format_ident!("arg{}", index);
+                                    bindings.push(quote!(#arg));
+                                    values.push((&field.ty, quote!(#arg)));
+                                } else {
+                                    bindings.push(quote!(_));
+                                }
+                            }
+                            let body = fold_fields(values)?;
+                            quote! {
+                                #name::#ident(#(#bindings,)*) => { #body }
+                            }
+                        }
+                        Fields::Unit => quote! {
+                            #name::#ident => #init,
+                        },
+                    })
+                })
+                .collect::<Result<Vec<_>, proc_macro::TokenStream>>()?;
+            quote! {
+                match self {
+                    #(#variants)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(report_error(
+                name.span(),
+                "can't derive Foldable for a union type",
+            ));
+        }
+    };
+    Ok(visitor.finish(body))
+}
+
+/// Build the match arm which traverses one struct or enum variant.
+///
+/// `traverse` only gets an applicative over the finished structure to
+/// accumulate into, so the fields can't be traversed on their own and
+/// put together afterwards. Instead, the elements are folded out of each
+/// field and passed to `f` in order, and the first result fills in every
+/// element of a copy of the fields' shapes. Each later result replaces the
+/// element at its position in a copy of the structure so far. Fields which
+/// don't mention the type parameter are cloned into each copy.
+///
+/// An applicative like `Vec` can use a partial result more than once, so
+/// each step has to copy it rather than update it in place, and traversing
+/// takes time quadratic in the number of elements.
+fn derive_traverse_case(
+    path: &TokenStream,
+    fields: &Fields,
+    generic_type: &Ident,
+    other_variants: bool,
+) -> Result<TokenStream, proc_macro::TokenStream> {
+    let functions = |function: &str| {
+        HashMap::from([(
+            generic_type.clone(),
+            Ident::new(function, Span::call_site()),
+        )])
+    };
+    let (unit, push, fill, absurd, copy, replace) = (
+        functions("derived_unit"),
+        functions("derived_push"),
+        functions("derived_fill"),
+        functions("derived_absurd"),
+        functions("derived_copy"),
+        functions("derived_replace"),
+    );
+    let shaper = FieldVisitor::new(&unit, true);
+    let folder = FieldVisitor::new(&push, false);
+    let filler = FieldVisitor::new(&fill, true);
+    let emptier = FieldVisitor::new(&absurd, true);
+    let copier = FieldVisitor::new(&copy, true);
+    let replacer = FieldVisitor::new(&replace, true);
+
+    let bindings: Vec<Ident> = (0..fields.len())
+        .map(|index| format_ident!("derived_{}", index))
+        .collect();
+    let construct = |values: Vec<TokenStream>| match fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote! { #path { #(#names: #values,)* } }
+        }
+        Fields::Unnamed(_) => quote! { #path(#(#values,)*) },
+        Fields::Unit => quote! { #path },
+    };
+    let pattern = construct(bindings.iter().map(|binding| quote!(#binding)).collect());
+
+    let mut shapes = Vec::new();
+    let mut elements = Vec::new();
+    let (mut filled, mut emptied, mut updated, mut moved) =
+        (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    let mut traversed = 0usize;
+    for (field, binding) in fields.iter().zip(&bindings) {
+        let ty = &field.ty;
+        if !shaper.mentions_any(ty) {
+            filled.push(quote! { ::std::clone::Clone::clone(&#binding) });
+            emptied.push(quote!(#binding));
+            updated.push(quote! { ::std::clone::Clone::clone(#binding) });
+            moved.push(quote!(#binding));
+            continue;
+        }
+        let shape = format_ident!("derived_shape_{}", traversed);
+        let shaped = shaper.bound_field(ty, quote!(&#binding))?;
+        shapes.push(quote! { let #shape = #shaped; });
+        let folded = folder
+            .fold(
+                ty,
+                quote!(#binding),
+                quote!(::std::vec::Vec::new()),
+                FoldKind::Left,
+                0,
+            )?
+            .expect("field mentions the type parameter");
+        elements.push(quote! {
+            derived_elements.extend(
+                #folded
+                    .into_iter()
+                    .enumerate()
+                    .map(|(position, element)| (#traversed, position, element)),
+            );
+        });
+        filled.push(filler.bound_field(ty, quote!(&#shape))?);
+        emptied.push(emptier.bound_field(ty, quote!(&#shape))?);
+        let replaced = replacer.bound_field(ty, quote!(#binding))?;
+        let copied = copier.bound_field(ty, quote!(#binding))?;
+        updated.push(quote! {
+            if field == #traversed { #replaced } else { #copied }
+        });
+        moved.push(quote!(#binding));
+        traversed += 1;
+    }
+
+    if traversed == 0 {
+        let finished = construct(moved);
+        return Ok(quote! {
+            #pattern => ::higher::Pure::pure(#finished),
+        });
+    }
+
+    let filled = filler.finish(construct(filled));
+    let emptied = emptier.finish(construct(emptied));
+    let updated = construct(updated);
+    let others = other_variants.then(|| {
+        quote! {
+            _ => unreachable!("a partial result is always the variant being traversed"),
+        }
+    });
+    let updated = copier.finish(replacer.finish(quote! {
+        match partial {
+            #pattern => #updated,
+            #others
+        }
+    }));
+    let shapes = shaper.finish(quote! { #(#shapes)* });
+    let elements = folder.finish(quote! { #(#elements)* });
+
+    Ok(quote! {
+        #pattern => {
+            let derived_unit = |_: &#generic_type| ();
+            #shapes
+            let derived_push = |mut elements: ::std::vec::Vec<#generic_type>, element: #generic_type| {
+                elements.push(element);
+                elements
+            };
+            let mut derived_elements = ::std::vec::Vec::new();
+            #elements
+            let mut derived_elements = derived_elements.into_iter();
+            let derived_first = match derived_elements.next() {
+                Some((_, _, first)) => first,
+                None => {
+                    let derived_absurd = |_: &()| -> DerivedType {
+                        unreachable!("the structure has no elements")
+                    };
+                    return ::higher::Pure::pure({ #emptied });
+                }
+            };
+            let mut derived_result = ::higher::Functor::fmap(
+                f(derived_first),
+                move |value: DerivedType| -> Self::Target<DerivedType> {
+                    let derived_fill = move |_: &()| ::std::clone::Clone::clone(&value);
+                    #filled
+                },
+            );
+            let derived_update = |partial: &Self::Target<DerivedType>,
+                                  field: usize,
+                                  position: usize,
+                                  value: DerivedType|
+             -> Self::Target<DerivedType> {
+                let derived_copy = |old: &DerivedType| ::std::clone::Clone::clone(old);
+                let derived_replace = {
+                    let count = ::std::cell::Cell::new(0);
+                    move |old: &DerivedType| {
+                        let current = count.get();
+                        count.set(current + 1);
+                        if current == position {
+                            ::std::clone::Clone::clone(&value)
+                        } else {
+                            ::std::clone::Clone::clone(old)
+                        }
+                    }
+                };
+                #updated
+            };
+            for (field, position, element) in derived_elements {
+                let partial = ::higher::Functor::fmap(
+                    derived_result,
+                    move |partial: Self::Target<DerivedType>| {
+                        ::higher::apply::ApplyFn::from(move |value: DerivedType| {
+                            derived_update(&partial, field, position, value)
+                        })
+                    },
+                );
+                derived_result = ::higher::Apply::apply(f(element), partial);
+            }
+            derived_result
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use higher::{
+        func::Func,
+        optics::{Lens, Then},
+        Bifunctor, BifunctorRef, Contravariant, Foldable, Functor, FunctorRef, Lenses, Monoid,
+        Prisms, Profunctor, Semigroup, Traversable,
+    };
+    use std::rc::Rc;
+
+    #[derive(PartialEq, Eq, Debug, Functor, FunctorRef)]
+    struct FunctorNamed<A> {
+        named: A,
+    }
+
+    #[derive(PartialEq, Eq, Debug, Functor, FunctorRef)]
+    struct FunctorUnnamed<A>(A);
+
+    #[derive(PartialEq, Eq, Debug, Functor)]
+    #[allow(dead_code)]
+    enum FunctorEnum<A> {
+        Some(A),
+        SomeNumber(usize),
+        SomeOther(A),
+        None,
+    }
+
+    #[test]
+    fn derive_functor() {
+        assert_eq!(
+            (FunctorNamed { named: 2u32 }).fmap(|x| x + 3),
+            FunctorNamed { named: 5u32 }
+        );
+
+        assert_eq!(FunctorUnnamed(2u32).fmap(|x| x + 3), FunctorUnnamed(5u32));
+
+        assert_eq!(
+            FunctorEnum::Some(2u32).fmap(|x| x + 3),
+            FunctorEnum::Some(5u32)
+        );
+        assert_eq!(
+            FunctorEnum::<u32>::SomeNumber(2).fmap(|x| x + 3),
+            FunctorEnum::<u32>::SomeNumber(2)
         );
         assert_eq!(
             FunctorEnum::SomeOther(2u32).fmap(|x| x + 3),
@@ -553,6 +2601,101 @@ mod test {
         assert_eq!(FunctorEnum::<u32>::None.fmap(|x| x + 3), FunctorEnum::None);
     }
 
+    #[derive(PartialEq, Eq, Debug, Functor, FunctorRef)]
+    struct FunctorNested<A> {
+        list: Vec<A>,
+        maybe: Option<Box<A>>,
+        pair: (A, u8, Vec<A>),
+        array: [A; 2],
+        grid: Vec<Vec<A>>,
+        label: String,
+    }
+
+    #[derive(PartialEq, Eq, Debug, Functor, FunctorRef)]
+    enum FunctorNestedEnum<A> {
+        Many(Vec<A>, usize),
+        Named { first: Option<A>, rest: Vec<(A, A)> },
+    }
+
+    #[test]
+    fn derive_functor_through_nested_types() {
+        let nested = FunctorNested {
+            list: vec![1, 2],
+            maybe: Some(Box::new(3)),
+            pair: (4, 5, vec![6]),
+            array: [7, 8],
+            grid: vec![vec![9], vec![]],
+            label: "label".to_string(),
+        };
+        let expected = FunctorNested {
+            list: vec!["1", "2"],
+            maybe: Some(Box::new("3")),
+            pair: ("4", 5, vec!["6"]),
+            array: ["7", "8"],
+            grid: vec![vec!["9"], vec![]],
+            label: "label".to_string(),
+        };
+        assert_eq!(
+            nested.fmap_ref(|x| x.to_string()),
+            expected.fmap_ref(|x| x.to_string())
+        );
+        assert_eq!(nested.fmap(|x| x * 2).grid, vec![vec![18], vec![]]);
+
+        assert_eq!(
+            FunctorNestedEnum::Many(vec![1, 2], 3).fmap(|x| x + 1),
+            FunctorNestedEnum::Many(vec![2, 3], 3)
+        );
+        assert_eq!(
+            FunctorNestedEnum::Named {
+                first: None,
+                rest: vec![(1, 2)]
+            }
+            .fmap_ref(|x| x * 10),
+            FunctorNestedEnum::Named {
+                first: None,
+                rest: vec![(10, 20)]
+            }
+        );
+    }
+
+    #[derive(Clone, Functor)]
+    enum Command<A> {
+        Say(String, A),
+        Stop,
+    }
+
+    #[test]
+    fn derive_functor_for_free() {
+        use higher::{free::Free, run};
+
+        fn say<'a>(message: &str) -> Free<'a, Command<()>, ()> {
+            Free::lift_f(Command::Say(message.to_string(), ()))
+        }
+
+        fn stop<'a>() -> Free<'a, Command<()>, ()> {
+            Free::lift_f(Command::Stop)
+        }
+
+        let program = run! {
+            say("one");
+            say("two");
+            stop();
+            say("three")
+        };
+        let mut said = Vec::new();
+        let mut next = program;
+        while let Err(instruction) = next.resume() {
+            match instruction {
+                Command::Say(message, rest) => {
+                    said.push(message);
+                    next = rest;
+                }
+                Command::Stop => break,
+            }
+        }
+        assert_eq!(said, vec!["one", "two"]);
+    }
+
     #[derive(PartialEq, Eq, Debug, Bifunctor, BifunctorRef)]
     struct BifunctorNamed<A, B> {
         a: A,
@@ -571,6 +2714,13 @@ mod test {
         Nothing,
     }
 
+    #[derive(PartialEq, Eq, Debug, Bifunctor, BifunctorRef)]
+    struct BifunctorNested<A, B> {
+        lefts: Vec<A>,
+        right: Option<B>,
+        both: (A, B),
+    }
+
     #[test]
     fn derive_bifunctor() {
         assert_eq!(
@@ -599,5 +2749,384 @@ mod test {
             BifunctorEnum::<u32, u8>::Nothing.bimap(|x| x + 3, |x| x + 4),
             BifunctorEnum::Nothing
         );
+
+        let nested = BifunctorNested {
+            lefts: vec![1, 2],
+            right: Some('a'),
+            both: (3, 'b'),
+        };
+        assert_eq!(
+            nested.bimap_ref(|x| x * 2, |c| c.to_ascii_uppercase()),
+            BifunctorNested {
+                lefts: vec![2, 4],
+                right: Some('A'),
+                both: (6, 'B'),
+            }
+        );
+        assert_eq!(nested.bimap(|x| x + 1, |_| ()).lefts, vec![2, 3]);
+    }
+
+    #[derive(PartialEq, Eq, Debug, Clone, Functor, FunctorRef, Foldable, Traversable)]
+    #[functor(param = A)]
+    struct Labels<'s, A, K, const N: usize>
+    where
+        K: Ord,
+    {
+        title: &'s str,
+        labels: Vec<(A, K)>,
+        counts: [A; N],
+    }
+
+    #[derive(PartialEq, Eq, Debug, Bifunctor, BifunctorRef)]
+    #[bifunctor(left = L, right = R)]
+    enum Outcome<R, X, L> {
+        Left(L),
+        Right(R),
+        Other(X),
+    }
+
+    #[test]
+    fn derive_with_chosen_type_params() {
+        let labels = Labels {
+            title: "scores",
+            labels: vec![(1, 'a'), (2, 'b')],
+            counts: [3, 4],
+        };
+        assert_eq!(
+            labels.fmap_ref(|x| x * 10),
+            Labels {
+                title: "scores",
+                labels: vec![(10, 'a'), (20, 'b')],
+                counts: [30, 40],
+            }
+        );
+        assert_eq!(labels.foldr_ref(|x, acc| x + acc, 0), 10);
+        assert_eq!(
+            labels
+                .clone()
+                .traverse(|x| u8::try_from(x).ok())
+                .map(|labels| labels.counts),
+            Some([3u8, 4])
+        );
+        assert_eq!(labels.fmap(|x| x.to_string()).counts, ["3", "4"]);
+
+        let left: Outcome<u8, (), char> = Outcome::Left('a');
+        assert_eq!(
+            left.bimap_ref(|c| c.to_ascii_uppercase(), |x| x + 1),
+            Outcome::Left('A')
+        );
+        assert_eq!(
+            Outcome::<u8, &str, char>::Right(1).bimap(|c| c as u32, |x| x + 1),
+            Outcome::Right(2)
+        );
+        assert_eq!(
+            Outcome::<u8, &str, char>::Other("other").bimap(|c| c as u32, |x| x + 1),
+            Outcome::Other("other")
+        );
+    }
+
+    #[derive(PartialEq, Eq, Debug, Clone, Functor, FunctorRef, Foldable, Traversable)]
+    enum Expr<A> {
+        Lit(A),
+        Add(Box<Expr<A>>, Box<Expr<A>>),
+        Call { name: String, args: Vec<A> },
+        Pair((A, u8, Option<A>)),
+        Empty,
+    }
+
+    #[derive(PartialEq, Eq, Debug, Clone, Functor, FunctorRef, Foldable, Traversable)]
+    struct Program<A>(Vec<Expr<A>>, usize);
+
+    #[derive(PartialEq, Eq, Debug, Clone, Foldable)]
+    struct Tally<A>(Vec<(A, u8)>);
+
+    fn program() -> Program<i32> {
+        Program(
+            vec![
+                Expr::Add(Box::new(Expr::Lit(1)), Box::new(Expr::Empty)),
+                Expr::Call {
+                    name: "f".to_string(),
+                    args: vec![2, 3],
+                },
+                Expr::Pair((4, 0, Some(5))),
+            ],
+            7,
+        )
+    }
+
+    #[test]
+    fn derive_foldable() {
+        let program = program();
+        assert_eq!(program.foldl_ref(|acc, x| acc * 10 + x, 0), 12345);
+        assert_eq!(program.foldr_ref(|x, acc| acc * 10 + x, 0), 54321);
+        assert_eq!(program.fold_map_ref(|x| x.to_string()), "12345");
+        assert_eq!(
+            Expr::Pair((1, 2, None)).foldr(|x, acc: Vec<i32>| [vec![x], acc].concat(), vec![]),
+            vec![1]
+        );
+        assert_eq!(program.fold_map(|x| vec![x]), vec![1, 2, 3, 4, 5]);
+        assert_eq!(Expr::<i32>::Empty.foldl(|acc, x| acc + x, 0), 0);
+
+        let tally = Tally(vec![(1, 0), (2, 0), (3, 0)]);
+        assert_eq!(tally.foldl_ref(|acc, x| acc * 10 + x, 0), 123);
+        assert_eq!(tally.foldr_ref(|x, acc| acc * 10 + x, 0), 321);
+        assert_eq!(tally.fold_map_ref(|x| x.to_string()), "123");
+    }
+
+    #[derive(PartialEq, Eq, Debug, Clone, Functor, Foldable, Traversable)]
+    struct Entry<A> {
+        key: String,
+        value: A,
+        fallback: A,
+    }
+
+    #[test]
+    fn derive_traversable() {
+        let halved = program().traverse(|x| Some(x * 2));
+        assert_eq!(halved, Some(program().fmap(|x| x * 2)));
+        assert_eq!(
+            program().traverse(|x| if x < 5 { Ok(x) } else { Err(x) }),
+            Err(5)
+        );
+        assert_eq!(Expr::<i32>::Empty.traverse(|x| vec![x]), vec![Expr::Empty]);
+
+        let choices = Expr::Call {
+            name: "g".to_string(),
+            args: vec![1, 2],
+        }
+        .traverse(|x| vec![x, -x]);
+        assert_eq!(choices.len(), 4);
+        assert_eq!(
+            choices[1],
+            Expr::Call {
+                name: "g".to_string(),
+                args: vec![1, -2],
+            }
+        );
+        assert_eq!(
+            Expr::Pair((1, 0, Some(2))).traverse(|x| vec![x, -x])[2],
+            Expr::Pair((-1, 0, Some(2)))
+        );
+
+        let entry = Entry {
+            key: "k".to_string(),
+            value: 1,
+            fallback: 2,
+        };
+        let entries = entry.traverse(|x| vec![x, -x]);
+        assert_eq!(entries.len(), 4);
+        assert_eq!(
+            entries[1],
+            Entry {
+                key: "k".to_string(),
+                value: 1,
+                fallback: -2,
+            }
+        );
+    }
+
+    #[derive(PartialEq, Eq, Debug, Semigroup, Monoid)]
+    struct Metrics {
+        #[semigroup(additive)]
+        requests: u32,
+        #[semigroup(multiplicative)]
+        scale: u8,
+        log: Vec<String>,
+    }
+
+    #[derive(PartialEq, Eq, Debug, Semigroup)]
+    struct Peaks<T>(#[semigroup(max)] T, #[semigroup(min)] T, Vec<T>);
+
+    #[derive(PartialEq, Eq, Debug, Semigroup)]
+    struct Span {
+        #[semigroup(first)]
+        start: u32,
+        #[semigroup(last)]
+        end: u32,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Semigroup, Monoid)]
+    struct Counted<T> {
+        #[semigroup(additive)]
+        count: T,
+        items: Vec<T>,
+    }
+
+    #[test]
+    fn derive_semigroup_and_monoid() {
+        let metrics = Metrics {
+            requests: 2,
+            scale: 3,
+            log: vec!["a".to_string()],
+        };
+        let more = Metrics {
+            requests: 5,
+            scale: 2,
+            log: vec!["b".to_string()],
+        };
+        assert_eq!(
+            metrics.mappend(more),
+            Metrics {
+                requests: 7,
+                scale: 6,
+                log: vec!["a".to_string(), "b".to_string()],
+            }
+        );
+        assert_eq!(
+            Metrics::default(),
+            Metrics {
+                requests: 0,
+                scale: 1,
+                log: vec![],
+            }
+        );
+
+        assert_eq!(
+            Peaks(3, 3, vec![3]).mappend(Peaks(5, 5, vec![5])),
+            Peaks(5, 3, vec![3, 5])
+        );
+
+        assert_eq!(
+            Span { start: 1, end: 2 }.mappend(Span { start: 3, end: 4 }),
+            Span { start: 1, end: 4 }
+        );
+
+        let counted = Counted {
+            count: 2,
+            items: vec![1, 1],
+        };
+        assert_eq!(Counted::default().mappend(counted.clone()), counted);
+        assert_eq!(counted.clone().mappend(Counted::default()), counted);
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Lenses)]
+    struct Labelled<T> {
+        label: String,
+        value: T,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Lenses)]
+    struct Shipment<T> {
+        id: u32,
+        contents: Labelled<T>,
+    }
+
+    #[derive(Clone, PartialEq, Eq, Debug, Prisms)]
+    enum Reply<T, E> {
+        Done(T),
+        Failed { code: u16, reason: E },
+        Pending,
+    }
+
+    #[test]
+    fn derive_lenses() {
+        let shipment = Shipment {
+            id: 1,
+            contents: Labelled {
+                label: "box".to_string(),
+                value: 5,
+            },
+        };
+        let value = Shipment::contents_lens().then(Labelled::value_lens());
+        assert_eq!(value.view(&shipment), 5);
+        let shipment = value.over(shipment, |value| value * 2);
+        assert_eq!(shipment.contents.value, 10);
+
+        let id = Shipment::<u32>::id_lens();
+        assert_eq!(id.set(shipment.clone(), 7).id, 7);
+        assert_eq!(
+            Shipment::contents_lens()
+                .then(Labelled::label_lens())
+                .view(&shipment),
+            "box"
+        );
+    }
+
+    #[test]
+    fn derive_prisms() {
+        let done: Reply<i32, String> = Reply::Done(3);
+        assert_eq!(Reply::done_prism().preview(&done), Some(3));
+        assert_eq!(Reply::failed_prism().preview(&done), None);
+        assert_eq!(Reply::pending_prism().preview(&done), None);
+        assert_eq!(
+            Reply::<i32, String>::pending_prism().preview(&Reply::Pending),
+            Some(())
+        );
+
+        let failed: Reply<i32, String> = Reply::failed_prism().review((500, "oops".to_string()));
+        let code = Reply::failed_prism().then(Lens::first());
+        assert_eq!(code.preview(&failed), Some(500));
+        assert_eq!(code.preview(&done), None);
+        assert_eq!(
+            Reply::done_prism().set(failed.clone(), 5),
+            Reply::Failed {
+                code: 500,
+                reason: "oops".to_string()
+            }
+        );
+
+        assert_eq!(
+            Reply::done_prism().over(done, |value| value + 1),
+            Reply::Done(4)
+        );
+    }
+
+    #[derive(Contravariant)]
+    struct Encoder<A>(Box<dyn Fn(A) -> Vec<u8>>);
+
+    #[derive(Contravariant)]
+    struct Checks<'a, A> {
+        name: &'static str,
+        check: Box<dyn Fn(A, usize) -> bool + 'a>,
+        batch: Rc<dyn Fn(Vec<A>) -> usize + 'a>,
+    }
+
+    #[derive(Profunctor)]
+    struct Handler<'a, I, O> {
+        run: Box<dyn Fn(I) -> Option<O> + 'a>,
+        fallback: O,
+        validate: Rc<dyn Fn(&str) -> bool + 'a>,
+    }
+
+    #[derive(Profunctor)]
+    struct Pipeline<I, O>(Func<'static, I, O>, Encoder<I>);
+
+    #[test]
+    fn derive_contravariant() {
+        let encoder = Encoder(Box::new(|n: u32| n.to_be_bytes().to_vec()));
+        let encoder = encoder.contramap(|c: char| c as u32);
+        assert_eq!((encoder.0)('a'), vec![0, 0, 0, 97]);
+
+        let checks = Checks {
+            name: "longer",
+            check: Box::new(|len: usize, limit| len > limit),
+            batch: Rc::new(|lens: Vec<usize>| lens.into_iter().sum()),
+        };
+        let checks = checks.contramap(|s: &str| s.len());
+        assert_eq!(checks.name, "longer");
+        assert!((checks.check)("abc", 2));
+        assert!(!(checks.check)("abc", 3));
+        assert_eq!((checks.batch)(vec!["a", "bc"]), 3);
+    }
+
+    #[test]
+    fn derive_profunctor() {
+        let handler = Handler {
+            run: Box::new(|i: i32| i.checked_add(1)),
+            fallback: 0,
+            validate: Rc::new(|s: &str| !s.is_empty()),
+        };
+        let handler = handler.dimap(|s: &str| s.len() as i32, |o| o.to_string());
+        assert_eq!((handler.run)("abc"), Some("4".to_string()));
+        assert_eq!(handler.fallback, "0");
+        assert!((handler.validate)("x"));
+
+        let pipeline = Pipeline(
+            Func::new(|i: u8| i as u32 * 2),
+            Encoder(Box::new(|i: u8| vec![i])),
+        );
+        let pipeline = pipeline.dimap(|s: String| s.len() as u8, |o| o + 1);
+        assert_eq!(pipeline.0.call("ab".to_string()), 5);
+        assert_eq!((pipeline.1 .0)("abc".to_string()), vec![3]);
     }
 }
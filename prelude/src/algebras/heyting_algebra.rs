@@ -14,7 +14,7 @@ impl HeytingAlgebra for bool {
     const FALSE: Self = false;
 
     fn implies(self, other: Self) -> Self {
-        !(self || other)
+        !self || other
     }
 
     fn conj(self, other: Self) -> Self {
@@ -9,7 +9,7 @@ where
     M: Functor<'a, A> + Extend<A> + IntoIterator<Item = A>,
 {
     fn alt(mut self, other: Self) -> Self {
-        self.extend(other.into_iter());
+        self.extend(other);
         self
     }
 }
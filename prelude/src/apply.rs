@@ -1,9 +1,10 @@
 use std::{
     collections::{LinkedList, VecDeque},
     rc::Rc,
+    task::Poll,
 };
 
-use crate::{functor::FunctorRef, repeat, run, Bind, Functor, Pure};
+use crate::{functor::FunctorRef, repeat, run, Bind, Functor, Pure, Semigroup};
 
 /// An `ApplyFn` is a function from `A` to `B` wrapped in something Rust's
 /// type system can more easily digest. Arguments for
@@ -80,10 +81,10 @@ pub trait Apply<'a, A: 'a>: Functor<'a, A> {
         b.apply(self.fmap(|x: A| ApplyFn::from(repeat(x))))
     }
 
-    fn apply_second<B: 'a>(self, b: Self::Target<B>) -> Self::Target<B>
+    fn apply_second<B>(self, b: Self::Target<B>) -> Self::Target<B>
     where
         Self: Sized,
-        B: Clone,
+        B: Clone + 'a,
 
         Self::Target<B>: Apply<'a, B, Target<ApplyFn<'a, A, B>> = Self::Target<ApplyFn<'a, A, B>>>,
     {
@@ -129,9 +130,9 @@ pub trait Apply<'a, A: 'a>: Functor<'a, A> {
 ///     }
 /// }
 /// ```
-pub fn ap<'a, A: 'a, B: 'a, M: 'a>(mf: M::Target<ApplyFn<'a, A, B>>, ma: M) -> M::Target<B>
+pub fn ap<'a, A: 'a, B: 'a, M>(mf: M::Target<ApplyFn<'a, A, B>>, ma: M) -> M::Target<B>
 where
-    M: Bind<'a, A> + Pure<A> + FunctorRef<'a, A, Target<A> = M> + Clone,
+    M: Bind<'a, A> + Pure<A> + FunctorRef<'a, A, Target<A> = M> + Clone + 'a,
 
     M::Target<B>: Pure<B>,
     M::Target<ApplyFn<'a, A, B>>: Bind<'a, ApplyFn<'a, A, B>, Target<B> = M::Target<B>>,
@@ -153,10 +154,10 @@ where
 /// This generic implmentation requires that the type `A` implements
 /// [`Clone`](Clone), as each value of `A` may need to be passed to the function
 /// multiple times, depending on the structure of the applicative.
-pub fn lift2<'a, A: 'a, B: 'a, C: 'a, F: 'a, M>(f: &'a F, a: M, b: M::Target<B>) -> M::Target<C>
+pub fn lift2<'a, A, B: 'a, C: 'a, F, M>(f: &'a F, a: M, b: M::Target<B>) -> M::Target<C>
 where
-    A: Clone,
-    F: Fn(A, B) -> C,
+    A: Clone + 'a,
+    F: Fn(A, B) -> C + 'a,
     M: Apply<'a, A>,
 
     M::Target<B>: Apply<'a, B, Target<C> = M::Target<C>>
@@ -189,6 +190,38 @@ where
     }
 }
 
+impl<'a, A: 'a> Apply<'a, A> for Box<A> {
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        Box::new(f.apply_fn(*self))
+    }
+}
+
+impl<'a, A: 'a> Apply<'a, A> for Poll<A> {
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        match (f, self) {
+            (Poll::Ready(f), Poll::Ready(x)) => Poll::Ready(f.apply_fn(x)),
+            _ => Poll::Pending,
+        }
+    }
+}
+
+/// The writer-style tuple combines its first components, with the function's
+/// coming before the argument's.
+impl<'a, W: Semigroup, A: 'a> Apply<'a, A> for (W, A) {
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        (f.0.mappend(self.0), f.1.apply_fn(self.1))
+    }
+}
+
 macro_rules! impl_apply_for_list {
     ($type:ident) => {
         impl<'a, A: 'a> Apply<'a, A> for $type<A>
@@ -14,25 +14,25 @@ pub trait Bifunctor<'a, A: 'a, B: 'a> {
 
     /// Map a `Bifunctor<A, B>` to a `Bifunctor<C, D>` using a function from `A`
     /// to `C` and a function from `B` to `D`.
-    fn bimap<C: 'a, D: 'a, L: 'a, R: 'a>(self, left: L, right: R) -> Self::Target<C, D>
+    fn bimap<C: 'a, D: 'a, L, R>(self, left: L, right: R) -> Self::Target<C, D>
     where
-        L: Fn(A) -> C,
-        R: Fn(B) -> D;
+        L: Fn(A) -> C + 'a,
+        R: Fn(B) -> D + 'a;
 
     /// Map only the left hand side of the bifunctor from `A` to `C`.
-    fn lmap<C: 'a, L: 'a>(self, left: L) -> Self::Target<C, B>
+    fn lmap<C: 'a, L>(self, left: L) -> Self::Target<C, B>
     where
         Self: Sized,
-        L: Fn(A) -> C,
+        L: Fn(A) -> C + 'a,
     {
         self.bimap(left, identity)
     }
 
     /// Map only the right hand side of the bifunctor from `B` to `D`.
-    fn rmap<D: 'a, R: 'a>(self, right: R) -> Self::Target<A, D>
+    fn rmap<D: 'a, R>(self, right: R) -> Self::Target<A, D>
     where
         Self: Sized,
-        R: Fn(B) -> D,
+        R: Fn(B) -> D + 'a,
     {
         self.bimap(identity, right)
     }
@@ -41,10 +41,10 @@ pub trait Bifunctor<'a, A: 'a, B: 'a> {
 pub trait BifunctorRef<'a, A: 'a, B: 'a>: Bifunctor<'a, A, B> {
     /// Map a `Bifunctor<A, B>` to a `Bifunctor<C, D>` using a function from `&A`
     /// to `C` and a function from `&B` to `D`.
-    fn bimap_ref<C: 'a, D: 'a, L: 'a, R: 'a>(&self, left: L, right: R) -> Self::Target<C, D>
+    fn bimap_ref<C: 'a, D: 'a, L, R>(&self, left: L, right: R) -> Self::Target<C, D>
     where
-        L: Fn(&A) -> C,
-        R: Fn(&B) -> D;
+        L: Fn(&A) -> C + 'a,
+        R: Fn(&B) -> D + 'a;
 }
 
 impl<'a, A: 'a, B: 'a> Bifunctor<'a, A, B> for Result<A, B> {
@@ -65,10 +65,10 @@ impl<'a, A: 'a, B: 'a> Bifunctor<'a, A, B> for Result<A, B> {
 }
 
 impl<'a, A: 'a, B: 'a> BifunctorRef<'a, A, B> for Result<A, B> {
-    fn bimap_ref<C: 'a, D: 'a, L: 'a, R: 'a>(&self, left: L, right: R) -> Self::Target<C, D>
+    fn bimap_ref<C: 'a, D: 'a, L, R>(&self, left: L, right: R) -> Self::Target<C, D>
     where
-        L: Fn(&A) -> C,
-        R: Fn(&B) -> D,
+        L: Fn(&A) -> C + 'a,
+        R: Fn(&B) -> D + 'a,
     {
         match self {
             Ok(a) => Ok(left(a)),
@@ -97,20 +97,20 @@ impl<'a, A: 'a, B: 'a, const N: usize> Bifunctor<'a, A, B> for [(A, B); N] {
 impl<'a, A: 'a, B: 'a> Bifunctor<'a, A, B> for (A, B) {
     type Target<T: 'a, U: 'a> = (T, U);
 
-    fn bimap<C: 'a, D: 'a, L: 'a, R: 'a>(self, left: L, right: R) -> Self::Target<C, D>
+    fn bimap<C: 'a, D: 'a, L, R>(self, left: L, right: R) -> Self::Target<C, D>
     where
-        L: Fn(A) -> C,
-        R: Fn(B) -> D,
+        L: Fn(A) -> C + 'a,
+        R: Fn(B) -> D + 'a,
     {
         (left(self.0), right(self.1))
     }
 }
 
 impl<'a, A: 'a, B: 'a> BifunctorRef<'a, A, B> for (A, B) {
-    fn bimap_ref<C: 'a, D: 'a, L: 'a, R: 'a>(&self, left: L, right: R) -> Self::Target<C, D>
+    fn bimap_ref<C: 'a, D: 'a, L, R>(&self, left: L, right: R) -> Self::Target<C, D>
     where
-        L: Fn(&A) -> C,
-        R: Fn(&B) -> D,
+        L: Fn(&A) -> C + 'a,
+        R: Fn(&B) -> D + 'a,
     {
         (left(&self.0), right(&self.1))
     }
@@ -131,10 +131,10 @@ impl<'a, A: 'a, B: 'a> Bifunctor<'a, A, B> for Vec<(A, B)> {
 }
 
 impl<'a, A: 'a, B: 'a> BifunctorRef<'a, A, B> for Vec<(A, B)> {
-    fn bimap_ref<C: 'a, D: 'a, L: 'a, R: 'a>(&self, left: L, right: R) -> Self::Target<C, D>
+    fn bimap_ref<C: 'a, D: 'a, L, R>(&self, left: L, right: R) -> Self::Target<C, D>
     where
-        L: Fn(&A) -> C,
-        R: Fn(&B) -> D,
+        L: Fn(&A) -> C + 'a,
+        R: Fn(&B) -> D + 'a,
     {
         self.iter().map(|(a, b)| (left(a), right(b))).collect()
     }
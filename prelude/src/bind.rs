@@ -1,9 +1,12 @@
-use std::collections::{LinkedList, VecDeque};
+use std::{
+    collections::{LinkedList, VecDeque},
+    task::Poll,
+};
 
 #[cfg(feature = "futures")]
 use futures::{channel::mpsc, stream::LocalBoxStream};
 
-use crate::{run, Functor, Pure};
+use crate::{run, Functor, Pure, Semigroup};
 
 /// `Bind` lets you chain computations together.
 ///
@@ -26,9 +29,9 @@ pub trait Bind<'a, A: 'a>: Functor<'a, A> {
     /// return value from `f`, of type `M<B>`, is effectively the original
     /// computation, `M<A>`, followed by the `M<B>` from the callback function
     /// when the result of `M<A>` is ready.
-    fn bind<B: 'a, F: 'a>(self, f: F) -> Self::Target<B>
+    fn bind<B: 'a, F>(self, f: F) -> Self::Target<B>
     where
-        F: Fn(A) -> Self::Target<B>;
+        F: Fn(A) -> Self::Target<B> + 'a;
 
     /// Turn an `M<A>` into an `M<()>` and a
     /// [`Stream<Item = A>`](futures::stream::Stream) that will yield values of
@@ -70,9 +73,9 @@ pub trait Bind<'a, A: 'a>: Functor<'a, A> {
 /// `lift_m1` provides a default implementation for
 /// [`Functor::fmap`](crate::Functor::fmap) using only [`Bind`](Bind) and
 /// [`Pure`](Pure).
-pub fn lift_m1<'a, M, A: 'a, B: 'a, F: 'a>(f: F, a: M) -> M::Target<B>
+pub fn lift_m1<'a, M, A: 'a, B: 'a, F>(f: F, a: M) -> M::Target<B>
 where
-    F: Fn(A) -> B,
+    F: Fn(A) -> B + 'a,
     M: Bind<'a, A>,
     M::Target<B>: Pure<B>,
 {
@@ -83,9 +86,9 @@ where
 }
 
 impl<'a, A: 'a> Bind<'a, A> for Option<A> {
-    fn bind<B: 'a, F: 'a>(self, f: F) -> Self::Target<B>
+    fn bind<B: 'a, F>(self, f: F) -> Self::Target<B>
     where
-        F: Fn(A) -> Self::Target<B>,
+        F: Fn(A) -> Self::Target<B> + 'a,
     {
         self.and_then(f)
     }
@@ -107,9 +110,9 @@ impl<'a, A: 'a> Bind<'a, A> for Option<A> {
 }
 
 impl<'a, A: 'a, E> Bind<'a, A> for Result<A, E> {
-    fn bind<B: 'a, F: 'a>(self, f: F) -> Self::Target<B>
+    fn bind<B: 'a, F>(self, f: F) -> Self::Target<B>
     where
-        F: Fn(A) -> Self::Target<B>,
+        F: Fn(A) -> Self::Target<B> + 'a,
     {
         self.and_then(f)
     }
@@ -130,12 +133,45 @@ impl<'a, A: 'a, E> Bind<'a, A> for Result<A, E> {
     }
 }
 
+impl<'a, A: 'a> Bind<'a, A> for Box<A> {
+    fn bind<B: 'a, F>(self, f: F) -> Self::Target<B>
+    where
+        F: Fn(A) -> Self::Target<B> + 'a,
+    {
+        f(*self)
+    }
+}
+
+impl<'a, A: 'a> Bind<'a, A> for Poll<A> {
+    fn bind<B: 'a, F>(self, f: F) -> Self::Target<B>
+    where
+        F: Fn(A) -> Self::Target<B> + 'a,
+    {
+        match self {
+            Poll::Ready(value) => f(value),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// The writer-style tuple appends the first component of the result of `f`
+/// to its own.
+impl<'a, W: Semigroup, A: 'a> Bind<'a, A> for (W, A) {
+    fn bind<B: 'a, F>(self, f: F) -> Self::Target<B>
+    where
+        F: Fn(A) -> Self::Target<B> + 'a,
+    {
+        let (w, b) = f(self.1);
+        (self.0.mappend(w), b)
+    }
+}
+
 macro_rules! impl_bind_from_iter {
     ($type:ident) => {
         impl<'a, A: 'a> Bind<'a, A> for $type<A> {
-            fn bind<B: 'a, F: 'a>(self, f: F) -> Self::Target<B>
+            fn bind<B: 'a, F>(self, f: F) -> Self::Target<B>
             where
-                F: Fn(A) -> Self::Target<B>,
+                F: Fn(A) -> Self::Target<B> + 'a,
             {
                 self.into_iter().flat_map(|v| f(v).into_iter()).collect()
             }
@@ -0,0 +1,42 @@
+use crate::Extend;
+
+/// A `Comonad` is the dual of a [`Monad`](crate::Monad): an
+/// [`Extend`](Extend) which also lets you [`extract`](Comonad::extract) the
+/// value at the current position.
+///
+/// Where a monad lets you put a value into a context with
+/// [`pure`](crate::Pure::pure) but not get it out again, a comonad lets you
+/// get the value out of a context, but not put one in.
+///
+/// Instances should satisfy the following laws:
+/// * `w.extend(|w| w.extract()) == w`
+/// * `w.extend(f).extract() == f(w)`
+/// * `w.extend(f).extend(g) == w.extend(|w| g(w.extend(f)))`
+pub trait Comonad<'a, A: 'a>: Extend<'a, A> {
+    /// Get the value at the current position out of the context.
+    fn extract(self) -> A;
+}
+
+impl<'a, E, A: 'a> Comonad<'a, A> for (E, A)
+where
+    E: Clone,
+{
+    fn extract(self) -> A {
+        self.1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Comonad, Extend};
+
+    #[test]
+    fn tuple_comonad_laws() {
+        let w = ("context", 5usize);
+        let f = |(e, a): (&str, usize)| e.len() + a;
+        let g = |(e, a): (&str, usize)| a * e.len();
+        assert_eq!(w.extend(|w| w.extract()), w);
+        assert_eq!(w.extend(f).extract(), f(w));
+        assert_eq!(w.extend(f).extend(g), w.extend(|w| g(w.extend(f))));
+    }
+}
@@ -0,0 +1,423 @@
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    rc::Rc,
+};
+
+use crate::{
+    apply::ApplyFn,
+    free::{FreeFunctor, FreeFunctorRef},
+    Applicative, Apply, Foldable, Functor, FunctorRef, Monoid, Pure, Traversable,
+};
+
+/// The composition of two functors.
+///
+/// A `Compose<F, G, A>` wraps a value of type `F`, which is a functor of `G`,
+/// which is in turn a functor of `A`, so that the two can be used together as
+/// a single functor of `A`. For instance, `Compose<Option<Vec<A>>, Vec<A>, A>`
+/// maps over every `A` inside an `Option<Vec<A>>`.
+///
+/// Rust has no way to say that mapping over `F::Target<T>` produces an
+/// `F::Target<U>` for an arbitrary functor `F`, so [`Apply`](Apply) and
+/// [`Traversable`](Traversable) need the two functors to implement
+/// [`FreeFunctorRef`](FreeFunctorRef), which lets them map over the whole
+/// family of [`Target`](Functor::Target) types.
+///
+/// ```
+/// # use higher::{compose::Compose, Functor, Traversable};
+/// let nested: Compose<Option<Vec<i32>>, Vec<i32>, i32> = Compose::new(Some(vec![1, 2, 3]));
+/// assert_eq!(nested.clone().fmap(|x| x * 2).get_compose(), Some(vec![2, 4, 6]));
+///
+/// let halves = nested.traverse(|x| if x < 10 { Ok(x) } else { Err(x) });
+/// assert_eq!(halves.map(Compose::get_compose), Ok(Some(vec![1, 2, 3])));
+/// ```
+pub struct Compose<F, G, A>(pub F, PhantomData<fn() -> (G, A)>);
+
+impl<F, G, A> Compose<F, G, A> {
+    /// Construct a `Compose` from a functor of functors.
+    pub fn new(value: F) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// Get the wrapped functor of functors.
+    pub fn get_compose(self) -> F {
+        self.0
+    }
+}
+
+impl<F: Clone, G, A> Clone for Compose<F, G, A> {
+    fn clone(&self) -> Self {
+        Self::new(self.0.clone())
+    }
+}
+
+impl<F: Copy, G, A> Copy for Compose<F, G, A> {}
+
+impl<F: std::fmt::Debug, G, A> std::fmt::Debug for Compose<F, G, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Compose").field(&self.0).finish()
+    }
+}
+
+impl<F: Default, G, A> Default for Compose<F, G, A> {
+    fn default() -> Self {
+        Self::new(F::default())
+    }
+}
+
+impl<F: PartialEq, G, A> PartialEq for Compose<F, G, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<F: Eq, G, A> Eq for Compose<F, G, A> {}
+
+impl<F: PartialOrd, G, A> PartialOrd for Compose<F, G, A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<F: Ord, G, A> Ord for Compose<F, G, A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<F: Hash, G, A> Hash for Compose<F, G, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<'a, F, G, A> Functor<'a, A> for Compose<F, G, A>
+where
+    F: Functor<'a, G>,
+    G: Functor<'a, A> + 'a,
+    A: 'a,
+{
+    type Target<T: 'a> = Compose<F::Target<G::Target<T>>, G::Target<T>, T>;
+
+    fn fmap<B, T>(self, f: T) -> Self::Target<B>
+    where
+        B: 'a,
+        T: Fn(A) -> B + 'a,
+    {
+        let f = Rc::new(f);
+        Compose::new(self.0.fmap(move |g: G| {
+            let f = f.clone();
+            g.fmap(move |a| f(a))
+        }))
+    }
+}
+
+impl<'a, F, G, A> FreeFunctor<'a, A> for Compose<F, G, A>
+where
+    F: FreeFunctor<'a, G>,
+    G: FreeFunctor<'a, A> + 'a,
+    A: 'a,
+{
+    fn fmap_target<T: 'a, U: 'a, H>(fa: Self::Target<T>, f: H) -> Self::Target<U>
+    where
+        H: Fn(T) -> U + 'a,
+    {
+        let f = Rc::new(f);
+        Compose::new(F::fmap_target::<G::Target<T>, G::Target<U>, _>(
+            fa.0,
+            move |g| {
+                let f = f.clone();
+                G::fmap_target(g, move |t| f(t))
+            },
+        ))
+    }
+}
+
+impl<'a, F, G, A> FunctorRef<'a, A> for Compose<F, G, A>
+where
+    F: FunctorRef<'a, G>,
+    G: FunctorRef<'a, A> + 'a,
+    A: 'a,
+{
+    fn fmap_ref<B, T>(&self, f: T) -> Self::Target<B>
+    where
+        B: 'a,
+        T: Fn(&A) -> B + 'a,
+    {
+        let f = Rc::new(f);
+        Compose::new(self.0.fmap_ref(move |g: &G| {
+            let f = f.clone();
+            g.fmap_ref(move |a| f(a))
+        }))
+    }
+}
+
+impl<F, G, A> Pure<A> for Compose<F, G, A>
+where
+    F: Pure<G>,
+    G: Pure<A>,
+{
+    fn pure(value: A) -> Self {
+        Compose::new(F::pure(G::pure(value)))
+    }
+}
+
+impl<'a, F, G, A> Apply<'a, A> for Compose<F, G, A>
+where
+    F: Apply<'a, G> + FreeFunctor<'a, G>,
+    G: Apply<'a, A> + FreeFunctorRef<'a, A> + 'a,
+    A: 'a,
+{
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        let functions = F::fmap_target(f.0, |functions: G::Target<ApplyFn<'a, A, B>>| {
+            ApplyFn::from(move |g: G| {
+                g.apply::<B>(G::fmap_ref_target::<ApplyFn<'a, A, B>, _, _>(
+                    &functions,
+                    Clone::clone,
+                ))
+            })
+        });
+        Compose::new(self.0.apply(functions))
+    }
+}
+
+impl<'a, F, G, A> Foldable<'a, A> for Compose<F, G, A>
+where
+    F: Foldable<'a, G>,
+    G: Foldable<'a, A> + 'a,
+    A: 'a,
+{
+    fn foldr<B, T>(self, f: T, init: B) -> B
+    where
+        B: 'a,
+        T: Fn(A, B) -> B + 'a,
+    {
+        let f = Rc::new(f);
+        self.0.foldr(
+            move |g: G, acc| {
+                let f = f.clone();
+                g.foldr(move |a, b| f(a, b), acc)
+            },
+            init,
+        )
+    }
+
+    fn foldr_ref<B, T>(&'a self, f: T, init: B) -> B
+    where
+        B: 'a,
+        T: Fn(&'a A, B) -> B + 'a,
+    {
+        let f = Rc::new(f);
+        self.0.foldr_ref(
+            move |g: &'a G, acc| {
+                let f = f.clone();
+                g.foldr_ref(move |a, b| f(a, b), acc)
+            },
+            init,
+        )
+    }
+
+    fn foldl<B, T>(self, f: T, init: B) -> B
+    where
+        B: 'a,
+        T: Fn(B, A) -> B + 'a,
+    {
+        let f = Rc::new(f);
+        self.0.foldl(
+            move |acc, g: G| {
+                let f = f.clone();
+                g.foldl(move |b, a| f(b, a), acc)
+            },
+            init,
+        )
+    }
+
+    fn foldl_ref<B, T>(&'a self, f: T, init: B) -> B
+    where
+        B: 'a,
+        T: Fn(B, &'a A) -> B + 'a,
+    {
+        let f = Rc::new(f);
+        self.0.foldl_ref(
+            move |acc, g: &'a G| {
+                let f = f.clone();
+                g.foldl_ref(move |b, a| f(b, a), acc)
+            },
+            init,
+        )
+    }
+
+    fn fold_map<T, M>(self, f: T) -> M
+    where
+        T: Fn(A) -> M + 'a,
+        M: Monoid,
+    {
+        let f = Rc::new(f);
+        self.0.fold_map(move |g: G| {
+            let f = f.clone();
+            g.fold_map(move |a| f(a))
+        })
+    }
+
+    fn fold_map_ref<T, M>(&'a self, f: T) -> M
+    where
+        T: Fn(&'a A) -> M + 'a,
+        M: Monoid + 'a,
+    {
+        let f = Rc::new(f);
+        self.0.fold_map_ref(move |g: &'a G| {
+            let f = f.clone();
+            g.fold_map_ref(move |a| f(a))
+        })
+    }
+}
+
+impl<'a, F, G, A> Traversable<'a, A> for Compose<F, G, A>
+where
+    F: Traversable<'a, G> + FreeFunctorRef<'a, G> + 'a,
+    G: Traversable<'a, A> + FreeFunctorRef<'a, A> + 'a,
+    A: 'a,
+{
+    /// Traverse the composed structure from left to right.
+    ///
+    /// [`traverse`](Traversable::traverse) only promises that `M` is an
+    /// applicative over `Self::Target<B>`, not over `G::Target<B>`, and an
+    /// implementation can't ask for more than that, so the inner functors
+    /// can't be traversed in `M` directly. Instead, a nested traversal in the
+    /// writer applicative takes the elements out of the structure and leaves
+    /// its shape behind, and the results of `f` are put back into the shape
+    /// one position at a time. Each of those steps maps over the whole
+    /// shape, so this takes quadratic time in the number of elements.
+    fn traverse<B, M, T>(self, f: T) -> M::Target<Self::Target<B>>
+    where
+        B: Clone + 'a,
+        M: Applicative<'a, B> + 'a,
+
+        M::Target<Self::Target<B>>: Applicative<'a, Self::Target<B>, Target<Self::Target<B>> = M::Target<Self::Target<B>>>
+            + Applicative<
+                'a,
+                Self::Target<B>,
+                Target<ApplyFn<'a, B, Self::Target<B>>> = M::Target<
+                    ApplyFn<'a, B, Self::Target<B>>,
+                >,
+            >,
+        T: Fn(A) -> M + 'a,
+    {
+        // The inner shapes go behind an `Rc` because the outer traversal
+        // needs to clone them, and nothing says `G::Target<()>` is `Clone`.
+        let (elements, shape) = self
+            .0
+            .traverse(|g: G| g.traverse(|a| (vec![a], ())).fmap(Rc::new));
+        let mut elements = elements.into_iter();
+        let first = match elements.next() {
+            Some(first) => first,
+            None => {
+                return Pure::pure(Compose::new(F::fmap_target(
+                    shape,
+                    |g: Rc<G::Target<()>>| {
+                        G::fmap_ref_target(&*g, |()| -> B {
+                            unreachable!("the structure has no elements")
+                        })
+                    },
+                )))
+            }
+        };
+        // Start with every hole filled with the first result, then replace
+        // them one position at a time with the rest.
+        let shape = Rc::new(shape);
+        let mut result = f(first).fmap(move |b: B| {
+            Compose::new(F::fmap_ref_target(&*shape, move |g: &Rc<G::Target<()>>| {
+                let b = b.clone();
+                G::fmap_ref_target(&**g, move |()| b.clone())
+            }))
+        });
+        for (index, a) in elements.enumerate() {
+            let set = result.fmap(move |fgb: Self::Target<B>| {
+                ApplyFn::from(move |b: B| {
+                    let position = Rc::new(Cell::new(0));
+                    Compose::new(F::fmap_ref_target(&fgb.0, move |g| {
+                        let (position, b) = (position.clone(), b.clone());
+                        G::fmap_ref_target(g, move |current: &B| {
+                            let current_position = position.get();
+                            position.set(current_position + 1);
+                            if current_position == index + 1 {
+                                b.clone()
+                            } else {
+                                current.clone()
+                            }
+                        })
+                    }))
+                })
+            });
+            result = f(a).apply(set);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Compose;
+    use crate::{Apply, Foldable, Functor, Pure, Traversable};
+
+    type OptionVec = Compose<Option<Vec<i32>>, Vec<i32>, i32>;
+
+    #[test]
+    fn functor_and_applicative() {
+        let value: OptionVec = Compose::new(Some(vec![1, 2]));
+        assert_eq!(
+            value.clone().fmap(|x| x + 1).get_compose(),
+            Some(vec![2, 3])
+        );
+        assert_eq!(OptionVec::pure(5).get_compose(), Some(vec![5]));
+
+        let functions = Compose::new(Some(vec![
+            (|x: i32| x + 10).into(),
+            (|x: i32| x * 10).into(),
+        ]));
+        assert_eq!(
+            value.apply(functions).get_compose(),
+            Some(vec![11, 12, 10, 20])
+        );
+    }
+
+    #[test]
+    fn fold_and_traverse() {
+        let value: Compose<Vec<Vec<i32>>, Vec<i32>, i32> =
+            Compose::new(vec![vec![1, 2], vec![], vec![3]]);
+        assert_eq!(value.clone().foldr(|x, acc| x + acc * 10, 0), 321);
+
+        let doubled = value.clone().traverse(|x| Some(x * 2));
+        assert_eq!(
+            doubled.map(Compose::get_compose),
+            Some(vec![vec![2, 4], vec![], vec![6]])
+        );
+        assert_eq!(value.traverse(|x| if x < 3 { Some(x) } else { None }), None);
+
+        let empty: OptionVec = Compose::new(Some(vec![]));
+        assert_eq!(
+            empty.traverse(Ok::<_, ()>).map(Compose::get_compose),
+            Ok(Some(vec![]))
+        );
+
+        let products = Compose::<Option<Vec<i32>>, Vec<i32>, i32>::new(Some(vec![1, 2]))
+            .traverse(|x| vec![x, -x])
+            .into_iter()
+            .map(Compose::get_compose)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            products,
+            vec![
+                Some(vec![1, 2]),
+                Some(vec![1, -2]),
+                Some(vec![-1, 2]),
+                Some(vec![-1, -2])
+            ]
+        );
+    }
+}
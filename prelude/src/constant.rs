@@ -0,0 +1,235 @@
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+use crate::{
+    apply::ApplyFn,
+    contra::Contravariant,
+    free::{FreeFunctor, FreeFunctorRef},
+    Applicative, Apply, Foldable, Functor, FunctorRef, Monoid, Pure, Semigroup, Traversable,
+};
+
+/// The `Const` functor.
+///
+/// A `Const<C, A>` holds a value of type `C` and ignores its type argument
+/// `A` entirely, so mapping over it does nothing at all. Its
+/// [`Apply`](Apply) instance combines the constant values using their
+/// [`Semigroup`](Semigroup), which makes it an [`Applicative`](Applicative)
+/// whenever `C` is a [`Monoid`](Monoid). This is what lets
+/// [`traverse`](Traversable::traverse) collect a summary of a structure
+/// instead of rebuilding it: see
+/// [`fold_map_default`](crate::traversable::fold_map_default).
+///
+/// ```
+/// # use higher::{constant::Const, monoid::Additive, Traversable};
+/// let total = vec![1, 2, 3].traverse(|x| Const::<_, ()>::new(Additive(x)));
+/// assert_eq!(total.get_const(), Additive(6));
+/// ```
+pub struct Const<C, A>(pub C, PhantomData<fn() -> A>);
+
+impl<C, A> Const<C, A> {
+    /// Construct a `Const` from a value.
+    pub fn new(value: C) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// Get the constant value.
+    pub fn get_const(self) -> C {
+        self.0
+    }
+}
+
+impl<C: Clone, A> Clone for Const<C, A> {
+    fn clone(&self) -> Self {
+        Self::new(self.0.clone())
+    }
+}
+
+impl<C: Copy, A> Copy for Const<C, A> {}
+
+impl<C: std::fmt::Debug, A> std::fmt::Debug for Const<C, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Const").field(&self.0).finish()
+    }
+}
+
+impl<C: Default, A> Default for Const<C, A> {
+    fn default() -> Self {
+        Self::new(C::default())
+    }
+}
+
+impl<C: PartialEq, A> PartialEq for Const<C, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<C: Eq, A> Eq for Const<C, A> {}
+
+impl<C: PartialOrd, A> PartialOrd for Const<C, A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl<C: Ord, A> Ord for Const<C, A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+impl<C: Hash, A> Hash for Const<C, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl<'a, C, A: 'a> Functor<'a, A> for Const<C, A> {
+    type Target<T: 'a> = Const<C, T>;
+
+    fn fmap<B, F>(self, _f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        Const::new(self.0)
+    }
+}
+
+impl<'a, C: Clone, A: 'a> FunctorRef<'a, A> for Const<C, A> {
+    fn fmap_ref<B, F>(&self, _f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(&A) -> B + 'a,
+    {
+        Const::new(self.0.clone())
+    }
+}
+
+impl<'a, C, A: 'a> FreeFunctor<'a, A> for Const<C, A> {
+    fn fmap_target<T: 'a, U: 'a, F>(fa: Self::Target<T>, _f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> U + 'a,
+    {
+        Const::new(fa.0)
+    }
+}
+
+impl<'a, C: Clone, A: 'a> FreeFunctorRef<'a, A> for Const<C, A> {
+    fn fmap_ref_target<T: 'a, U: 'a, F>(fa: &Self::Target<T>, _f: F) -> Self::Target<U>
+    where
+        F: Fn(&T) -> U + 'a,
+    {
+        Const::new(fa.0.clone())
+    }
+}
+
+impl<'a, C, A: 'a> Contravariant<'a, A> for Const<C, A> {
+    type Target<T: 'a> = Const<C, T>;
+
+    fn contramap<B, F>(self, _f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(B) -> A + 'a,
+    {
+        Const::new(self.0)
+    }
+}
+
+impl<C: Monoid, A> Pure<A> for Const<C, A> {
+    fn pure(_value: A) -> Self {
+        Const::new(C::default())
+    }
+}
+
+impl<'a, C: Semigroup, A: 'a> Apply<'a, A> for Const<C, A> {
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        Const::new(f.0.mappend(self.0))
+    }
+}
+
+impl<'a, C, A: 'a> Foldable<'a, A> for Const<C, A> {
+    fn foldr<B, F>(self, _f: F, init: B) -> B
+    where
+        B: 'a,
+        F: Fn(A, B) -> B + 'a,
+    {
+        init
+    }
+
+    fn foldr_ref<B, F>(&'a self, _f: F, init: B) -> B
+    where
+        B: 'a,
+        F: Fn(&'a A, B) -> B + 'a,
+    {
+        init
+    }
+
+    fn foldl<B, F>(self, _f: F, init: B) -> B
+    where
+        B: 'a,
+        F: Fn(B, A) -> B + 'a,
+    {
+        init
+    }
+
+    fn foldl_ref<B, F>(&'a self, _f: F, init: B) -> B
+    where
+        B: 'a,
+        F: Fn(B, &'a A) -> B + 'a,
+    {
+        init
+    }
+
+    fn fold_map<F, M>(self, _f: F) -> M
+    where
+        F: Fn(A) -> M + 'a,
+        M: Monoid,
+    {
+        M::default()
+    }
+
+    fn fold_map_ref<F, M>(&'a self, _f: F) -> M
+    where
+        F: Fn(&'a A) -> M + 'a,
+        M: Monoid,
+    {
+        M::default()
+    }
+}
+
+impl<'a, C, A: 'a> Traversable<'a, A> for Const<C, A> {
+    fn traverse<B: 'a, M, F>(self, _f: F) -> M::Target<Self::Target<B>>
+    where
+        M: Applicative<'a, B>,
+        M::Target<Self::Target<B>>: Applicative<'a, Self::Target<B>>,
+        F: Fn(A) -> M + 'a,
+    {
+        Pure::pure(Const::new(self.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Const;
+    use crate::{apply::ApplyFn, Apply, Foldable, Functor, Pure, Traversable};
+
+    #[test]
+    fn accumulates_without_mapping() {
+        let a: Const<Vec<i32>, i32> = Const::new(vec![1]);
+        let f: Const<Vec<i32>, ApplyFn<'_, i32, i32>> = Const::new(vec![2]);
+        assert_eq!(a.apply(f), Const::new(vec![2, 1]));
+        assert_eq!(Const::<Vec<i32>, i32>::pure(5), Const::new(vec![]));
+
+        let a: Const<String, i32> = Const::new("x".to_string());
+        assert_eq!(a.clone().fmap(|x| x + 1).get_const(), "x");
+        assert_eq!(a.clone().foldr(|x, y| x + y, 0), 0);
+        assert_eq!(a.clone().traverse(Some), Some(Const::new("x".to_string())));
+    }
+}
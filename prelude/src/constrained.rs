@@ -0,0 +1,436 @@
+//! Functors over containers which put bounds on their elements.
+//!
+//! Types like [`BTreeSet`](BTreeSet) or [`HashSet`](HashSet) can't implement
+//! [`Functor`](crate::Functor), because a `BTreeSet<B>` can only be built
+//! when `B: Ord`, and `Functor::fmap` has to work for any `B` at all. The
+//! traits in this module carry the container's bound with them: each
+//! operation requires that `Self::Target<B>` can be collected from
+//! `Self::Item<B>`, which holds exactly when `B` satisfies the container's
+//! bound, whether that's `Ord` or `Hash + Eq` or nothing at all.
+//!
+//! The bound is expressed through [`FromIterator`](FromIterator) rather than
+//! as an associated constraint, like a `type Bound` naming `Ord`, because
+//! Rust has no way to abstract over a trait: an associated type can only
+//! name a marker standing in for `Ord`, and an implementation given
+//! `B: Satisfies<OrdMarker>` still can't conclude that `B: Ord` when it comes
+//! to build the `BTreeSet<B>`. Building the container is all an
+//! implementation needs the bound for, and `FromIterator` states exactly
+//! that, while still letting callers write `B: Ord` and have the compiler
+//! take care of the rest.
+//!
+//! Maps are functors over their values, with their keys left untouched, and
+//! [`CBifunctor`](CBifunctor)s over their keys and values together.
+//!
+//! The [`crun!`](crate::crun) macro provides the same do notation as
+//! [`run!`](crate::run) for these traits.
+//!
+//! ```
+//! # use std::collections::BTreeSet;
+//! # use higher::constrained::{CBind, CFunctor};
+//! let set = BTreeSet::from([1, 2, 3]);
+//! let set = set.cfmap(|x| x % 2);
+//! assert_eq!(set, BTreeSet::from([0, 1]));
+//! let set = set.cbind(|x| BTreeSet::from([x, x + 10]));
+//! assert_eq!(set, BTreeSet::from([0, 1, 10, 11]));
+//! ```
+
+use std::{
+    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::Monoid;
+
+/// A [`Functor`](crate::Functor) whose element type is constrained.
+///
+/// The constraint is whatever it takes for `Self::Target<B>` to implement
+/// `FromIterator<Self::Item<B>>`; see the [module docs](self) for why it
+/// isn't a separate associated type.
+pub trait CFunctor<'a, A: 'a> {
+    /// The container type with its elements changed to `T`.
+    type Target<T: 'a>: CFunctor<'a, T, Target<A> = Self>;
+
+    /// What a `Target<T>` is collected from: `T` for sets, `(K, T)` for
+    /// maps.
+    type Item<T: 'a>;
+
+    /// Map a function over the elements, collecting the results into a
+    /// container which meets the bound on `B`.
+    fn cfmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+        Self::Target<B>: FromIterator<Self::Item<B>>;
+}
+
+/// A [`Bind`](crate::Bind) whose element type is constrained.
+pub trait CBind<'a, A: 'a>: CFunctor<'a, A> {
+    /// Apply `f` to each element and combine the resulting containers.
+    ///
+    /// Sets take the union of the results. Maps keep, for each key, the
+    /// value under the same key in the map that key's value was mapped to,
+    /// dropping the key if it isn't there.
+    fn cbind<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> Self::Target<B> + 'a,
+        Self::Target<B>: FromIterator<Self::Item<B>>;
+}
+
+/// A [`Bifunctor`](crate::Bifunctor) whose element types are constrained.
+pub trait CBifunctor<'a, A: 'a, B: 'a> {
+    /// The container type with its elements changed to `T` and `U`.
+    type Target<T: 'a, U: 'a>: CBifunctor<'a, T, U, Target<A, B> = Self>;
+
+    /// Map functions over both sides of each element, collecting the results
+    /// into a container which meets the bounds on `C` and `D`.
+    ///
+    /// For maps, this maps the keys with `left` and the values with `right`.
+    /// If two keys map to the same new key, the later entry wins.
+    fn cbimap<C, D, L, R>(self, left: L, right: R) -> Self::Target<C, D>
+    where
+        C: 'a,
+        D: 'a,
+        L: Fn(A) -> C + 'a,
+        R: Fn(B) -> D + 'a,
+        Self::Target<C, D>: FromIterator<(C, D)>;
+}
+
+/// A [`Foldable`](crate::Foldable) whose element type is constrained.
+///
+/// Folding doesn't need the bound, but it goes with the other constrained
+/// traits so that these containers can be used with them throughout. Note
+/// that hashed containers and [`BinaryHeap`](BinaryHeap) fold in an
+/// unspecified order.
+pub trait CFoldable<'a, A: 'a> {
+    /// Fold the elements from the right.
+    fn cfoldr<B, F>(self, f: F, init: B) -> B
+    where
+        F: Fn(A, B) -> B;
+
+    /// Fold references to the elements from the right.
+    fn cfoldr_ref<B, F>(&'a self, f: F, init: B) -> B
+    where
+        F: Fn(&'a A, B) -> B;
+
+    /// Fold the elements from the left.
+    fn cfoldl<B, F>(self, f: F, init: B) -> B
+    where
+        F: Fn(B, A) -> B;
+
+    /// Fold references to the elements from the left.
+    fn cfoldl_ref<B, F>(&'a self, f: F, init: B) -> B
+    where
+        F: Fn(B, &'a A) -> B;
+
+    /// Map each element to a [`Monoid`](Monoid) and combine the results.
+    fn cfold_map<M, F>(self, f: F) -> M
+    where
+        Self: Sized,
+        M: Monoid,
+        F: Fn(A) -> M,
+    {
+        self.cfoldl(|acc: M, item| acc.mappend(f(item)), M::default())
+    }
+
+    /// Map a reference to each element to a [`Monoid`](Monoid) and combine
+    /// the results.
+    fn cfold_map_ref<M, F>(&'a self, f: F) -> M
+    where
+        M: Monoid,
+        F: Fn(&'a A) -> M,
+    {
+        self.cfoldl_ref(|acc: M, item| acc.mappend(f(item)), M::default())
+    }
+}
+
+macro_rules! impl_constrained_for_set {
+    ($type:ident) => {
+        impl<'a, A: 'a> CFunctor<'a, A> for $type<A> {
+            type Target<T: 'a> = $type<T>;
+            type Item<T: 'a> = T;
+
+            fn cfmap<B, F>(self, f: F) -> Self::Target<B>
+            where
+                B: 'a,
+                F: Fn(A) -> B + 'a,
+                Self::Target<B>: FromIterator<B>,
+            {
+                self.into_iter().map(f).collect()
+            }
+        }
+
+        impl<'a, A: 'a> CBind<'a, A> for $type<A> {
+            fn cbind<B, F>(self, f: F) -> Self::Target<B>
+            where
+                B: 'a,
+                F: Fn(A) -> Self::Target<B> + 'a,
+                Self::Target<B>: FromIterator<B>,
+            {
+                self.into_iter().flat_map(f).collect()
+            }
+        }
+
+        impl<'a, A: 'a> CFoldable<'a, A> for $type<A> {
+            fn cfoldr<B, F>(self, f: F, init: B) -> B
+            where
+                F: Fn(A, B) -> B,
+            {
+                let items: Vec<A> = self.into_iter().collect();
+                items.into_iter().rfold(init, |acc, item| f(item, acc))
+            }
+
+            fn cfoldr_ref<B, F>(&'a self, f: F, init: B) -> B
+            where
+                F: Fn(&'a A, B) -> B,
+            {
+                let items: Vec<&'a A> = self.iter().collect();
+                items.into_iter().rfold(init, |acc, item| f(item, acc))
+            }
+
+            fn cfoldl<B, F>(self, f: F, init: B) -> B
+            where
+                F: Fn(B, A) -> B,
+            {
+                self.into_iter().fold(init, f)
+            }
+
+            fn cfoldl_ref<B, F>(&'a self, f: F, init: B) -> B
+            where
+                F: Fn(B, &'a A) -> B,
+            {
+                self.iter().fold(init, f)
+            }
+        }
+    };
+}
+
+impl_constrained_for_set!(HashSet);
+impl_constrained_for_set!(BTreeSet);
+impl_constrained_for_set!(BinaryHeap);
+
+macro_rules! impl_constrained_for_map {
+    ($type:ident, $($key_bound:tt)*) => {
+        impl<'a, K: 'a, V: 'a> CFunctor<'a, V> for $type<K, V> {
+            type Target<T: 'a> = $type<K, T>;
+            type Item<T: 'a> = (K, T);
+
+            fn cfmap<B, F>(self, f: F) -> Self::Target<B>
+            where
+                B: 'a,
+                F: Fn(V) -> B + 'a,
+                Self::Target<B>: FromIterator<(K, B)>,
+            {
+                self.into_iter().map(|(key, value)| (key, f(value))).collect()
+            }
+        }
+
+        impl<'a, K: $($key_bound)* + 'a, V: 'a> CBind<'a, V> for $type<K, V> {
+            fn cbind<B, F>(self, f: F) -> Self::Target<B>
+            where
+                B: 'a,
+                F: Fn(V) -> Self::Target<B> + 'a,
+                Self::Target<B>: FromIterator<(K, B)>,
+            {
+                self.into_iter()
+                    .filter_map(|(key, value)| f(value).remove(&key).map(|b| (key, b)))
+                    .collect()
+            }
+        }
+
+        impl<'a, K: 'a, V: 'a> CBifunctor<'a, K, V> for $type<K, V> {
+            type Target<T: 'a, U: 'a> = $type<T, U>;
+
+            fn cbimap<C, D, L, R>(self, left: L, right: R) -> Self::Target<C, D>
+            where
+                C: 'a,
+                D: 'a,
+                L: Fn(K) -> C + 'a,
+                R: Fn(V) -> D + 'a,
+                Self::Target<C, D>: FromIterator<(C, D)>,
+            {
+                self.into_iter()
+                    .map(|(key, value)| (left(key), right(value)))
+                    .collect()
+            }
+        }
+
+        impl<'a, K: 'a, V: 'a> CFoldable<'a, V> for $type<K, V> {
+            fn cfoldr<B, F>(self, f: F, init: B) -> B
+            where
+                F: Fn(V, B) -> B,
+            {
+                let values: Vec<V> = self.into_values().collect();
+                values.into_iter().rfold(init, |acc, value| f(value, acc))
+            }
+
+            fn cfoldr_ref<B, F>(&'a self, f: F, init: B) -> B
+            where
+                F: Fn(&'a V, B) -> B,
+            {
+                let values: Vec<&'a V> = self.values().collect();
+                values.into_iter().rfold(init, |acc, value| f(value, acc))
+            }
+
+            fn cfoldl<B, F>(self, f: F, init: B) -> B
+            where
+                F: Fn(B, V) -> B,
+            {
+                self.into_values().fold(init, f)
+            }
+
+            fn cfoldl_ref<B, F>(&'a self, f: F, init: B) -> B
+            where
+                F: Fn(B, &'a V) -> B,
+            {
+                self.values().fold(init, f)
+            }
+        }
+    };
+}
+
+impl_constrained_for_map!(HashMap, Hash + Eq);
+impl_constrained_for_map!(BTreeMap, Ord);
+
+/// Monadic do notation for [`CBind`](CBind).
+///
+/// This works exactly like [`run!`](crate::run), but chains its expressions
+/// together using [`cbind`](CBind::cbind) instead of
+/// [`bind`](crate::Bind::bind), so it can be used with sets and maps. A
+/// final `yield` still uses [`pure`](crate::Pure::pure), so it's only
+/// available for the sets.
+///
+/// ```
+/// # use std::collections::BTreeSet;
+/// # use higher::crun;
+/// let sums = crun! {
+///     x <= BTreeSet::from([1, 2]);
+///     y <= BTreeSet::from([10, 20]);
+///     yield x + y
+/// };
+/// assert_eq!(sums, BTreeSet::from([11, 12, 21, 22]));
+/// ```
+#[macro_export]
+macro_rules! crun {
+    ($binding:tt <= <$coerce:ty> $comp:expr; $($tail:tt)*) => { crun!{$binding <= [] <$coerce> $comp; $($tail)*} };
+
+    ($binding:tt <= [$($shadow_clone:ident),*] <$coerce:ty> $comp:expr; $($tail:tt)*) => {
+        {
+            macro_rules! verify_pat { ($_:pat_param) => {}; } verify_pat!($binding);
+            $(let $shadow_clone = $shadow_clone.clone();)*
+            $crate::constrained::CBind::cbind::<$coerce, _>($comp, move |$binding| crun!($($tail)*))
+        }
+    };
+
+    ($binding:tt <= $comp:expr; $($tail:tt)*) => { crun!{$binding <= [] $comp; $($tail)*} };
+
+    ($binding:tt <= [$($shadow_clone:ident),*] $comp:expr; $($tail:tt)*) => {
+        {
+            macro_rules! verify_pat { ($_:pat_param) => {}; } verify_pat!($binding);
+            $(let $shadow_clone = $shadow_clone.clone();)*
+            $crate::constrained::CBind::cbind($comp, move |$binding| crun!($($tail)*))
+        }
+    };
+
+    (<$coerce:ty> $comp:expr; $($tail:tt)*) => { crun!{[] <$coerce> $comp; $($tail)*} };
+
+    ([$($shadow_clone:ident),*] <$coerce:ty> $comp:expr; $($tail:tt)*) => {
+        {
+            $(let $shadow_clone = $shadow_clone.clone();)*
+            $crate::constrained::CBind::cbind::<$coerce, _>($comp, move |_| crun!($($tail)*))
+        }
+    };
+
+    ($comp:expr; $($tail:tt)*) => {crun!{[] $comp; $($tail)*}};
+
+    ([$($shadow_clone:ident),*] $comp:expr; $($tail:tt)*) => {
+        {
+            $(let $shadow_clone = $shadow_clone.clone();)*
+            $crate::constrained::CBind::cbind($comp, move |_| crun!($($tail)*))
+        }
+    };
+
+    (yield $result:expr) => { crun!{[] yield $result} };
+
+    ([$($shadow_clone:ident),*] yield $result:expr) => {
+        {
+            $(let $shadow_clone = $shadow_clone.clone();)*
+            $crate::Pure::pure($result)
+        }
+    };
+
+    ($result:expr) => { crun!{[] $result} };
+
+    ([$($shadow_clone:ident),*] $result:expr) => {
+        {
+            $(let $shadow_clone = $shadow_clone.clone();)*
+            $result
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet};
+
+    use super::{CBifunctor, CBind, CFoldable, CFunctor};
+
+    #[test]
+    fn sets() {
+        let set = HashSet::from([-1, 1, 2]);
+        assert_eq!(set.clone().cfmap(|x: i32| x.abs()), HashSet::from([1, 2]));
+        assert_eq!(set.cfold_map_ref(|x: &i32| vec![*x]).len(), 3);
+
+        let heap = BinaryHeap::from([3, 1, 2]).cbind(|x| BinaryHeap::from([x, -x]));
+        assert_eq!(heap.into_sorted_vec(), vec![-3, -2, -1, 1, 2, 3]);
+
+        let set = BTreeSet::from([1, 2, 3]);
+        assert_eq!(set.cfoldr_ref(|x, acc| acc - x, 0), -6);
+        assert_eq!(
+            set.cfoldr(|x, acc| format!("{}{}", acc, x), String::new()),
+            "321"
+        );
+    }
+
+    #[test]
+    fn maps() {
+        let map = BTreeMap::from([("a", 1), ("b", 2)]);
+        assert_eq!(
+            map.clone().cfmap(|x| x.to_string()),
+            BTreeMap::from([("a", "1".to_string()), ("b", "2".to_string())])
+        );
+        let bound = map
+            .clone()
+            .cbind(|x| BTreeMap::from([("a", x * 10), ("c", x * 100)]));
+        assert_eq!(bound, BTreeMap::from([("a", 10)]));
+        let swapped = map.clone().cbimap(|k| k.len(), |v| v > 1);
+        assert_eq!(swapped, BTreeMap::from([(1, true)]));
+        assert_eq!(map.cfold_map(|x: i32| vec![x]), vec![1, 2]);
+
+        let map = HashMap::from([(1, 'a'), (2, 'b')]);
+        let map = map.cbimap(|k| k * 2, |v| v);
+        assert_eq!(map.get(&4), Some(&'b'));
+        assert_eq!(map.cfoldl_ref(|acc, _| acc + 1, 0), 2);
+        assert_eq!(
+            map.cfmap(|c| c.to_ascii_uppercase()),
+            HashMap::from([(2, 'A'), (4, 'B')])
+        );
+    }
+
+    #[test]
+    fn do_notation() {
+        let pairs = crun! {
+            x <= BTreeSet::from([1, 2]);
+            y <= BTreeSet::from(['a', 'b']);
+            yield (x, y)
+        };
+        assert_eq!(pairs.len(), 4);
+
+        let empty = crun! {
+            x <= HashSet::from([1, 2, 3, 4]);
+            HashSet::<()>::new();
+            yield x
+        };
+        assert_eq!(empty, HashSet::new());
+    }
+}
@@ -0,0 +1,237 @@
+use std::rc::Rc;
+
+use crate::{apply::ApplyFn, Apply, Bind, Functor, Pure};
+
+/// A `Cont` monad.
+///
+/// This wraps a computation in continuation passing style: a function which,
+/// instead of returning a value of type `A`, takes a continuation
+/// `Fn(A) -> R` and passes the value along to it, producing the final result
+/// `R`. Because a `Cont` gets to decide whether, and how often, it calls its
+/// continuation, it can express all sorts of control flow, like early exits
+/// using [`call_cc`](Cont::call_cc), and delimited continuations using
+/// [`reset`](Cont::reset) and [`shift`](Cont::shift).
+///
+/// ```
+/// # use higher::{run, cont::Cont, Pure};
+/// fn safe_div<'a>(a: u32, b: u32) -> Cont<'a, String, u32> {
+///     Cont::call_cc(move |exit| {
+///         run! {
+///             if b == 0 { exit(0) } else { Cont::pure(()) };
+///             yield a / b
+///         }
+///     })
+/// }
+///
+/// let program = run! {
+///     x <= safe_div(84, 2);
+///     y <= safe_div(x, 0);
+///     yield x + y
+/// };
+/// assert_eq!(program.run_cont(|result| format!("got {}", result)), "got 42");
+/// ```
+pub struct Cont<'a, R, A> {
+    run: Rc<dyn Fn(Rc<dyn Fn(A) -> R + 'a>) -> R + 'a>,
+}
+
+impl<'a, R: 'a, A: 'a> Cont<'a, R, A> {
+    /// Construct a `Cont` from a function which takes a continuation and
+    /// produces a final result.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(Rc<dyn Fn(A) -> R + 'a>) -> R + 'a,
+    {
+        Self { run: Rc::new(f) }
+    }
+
+    /// Run the computation, passing its result to the given continuation.
+    pub fn run_cont<K>(&self, k: K) -> R
+    where
+        K: Fn(A) -> R + 'a,
+    {
+        (self.run)(Rc::new(k))
+    }
+
+    fn run_rc(&self, k: Rc<dyn Fn(A) -> R + 'a>) -> R {
+        (self.run)(k)
+    }
+
+    /// Call a function with the current continuation.
+    ///
+    /// The function receives an escape function, which returns a computation
+    /// that discards its own continuation and passes the value it's given
+    /// straight to the continuation of the `call_cc` instead. This lets you
+    /// exit early from the middle of a computation.
+    pub fn call_cc<B: 'a, F>(f: F) -> Self
+    where
+        A: Clone,
+        F: Fn(Rc<dyn Fn(A) -> Cont<'a, R, B> + 'a>) -> Self + 'a,
+    {
+        Self::new(move |k| {
+            let exit = k.clone();
+            f(Rc::new(move |value: A| {
+                let exit = exit.clone();
+                Cont::new(move |_| exit(value.clone()))
+            }))
+            .run_rc(k)
+        })
+    }
+
+    /// Capture the continuation up to the nearest enclosing
+    /// [`reset`](Cont::reset).
+    ///
+    /// The function receives the captured continuation as a plain function,
+    /// which it can call as many times as it likes, and returns a computation
+    /// whose result becomes the result of the enclosing
+    /// [`reset`](Cont::reset).
+    pub fn shift<F>(f: F) -> Self
+    where
+        F: Fn(Rc<dyn Fn(A) -> R + 'a>) -> Cont<'a, R, R> + 'a,
+    {
+        Self::new(move |k| f(k).eval_cont())
+    }
+}
+
+impl<'a, R: 'a> Cont<'a, R, R> {
+    /// Run the computation with the identity continuation, returning its
+    /// result.
+    pub fn eval_cont(&self) -> R {
+        self.run_cont(|value| value)
+    }
+
+    /// Delimit the continuations captured by any [`shift`](Cont::shift)
+    /// inside this computation, turning it into a computation which simply
+    /// produces its result.
+    pub fn reset<Q: 'a>(self) -> Cont<'a, Q, R> {
+        Cont::new(move |k| k(self.eval_cont()))
+    }
+}
+
+impl<'a, R, A> Clone for Cont<'a, R, A> {
+    fn clone(&self) -> Self {
+        Self {
+            run: self.run.clone(),
+        }
+    }
+}
+
+impl<'a, R, A> std::fmt::Debug for Cont<'a, R, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Cont<{}, {}>",
+            std::any::type_name::<R>(),
+            std::any::type_name::<A>()
+        )
+    }
+}
+
+impl<'a, R: 'a, A: 'a> Functor<'a, A> for Cont<'a, R, A> {
+    type Target<T: 'a> = Cont<'a, R, T>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        let f = Rc::new(f);
+        Cont::new(move |k: Rc<dyn Fn(B) -> R + 'a>| {
+            let f = f.clone();
+            self.run_rc(Rc::new(move |value| k(f(value))))
+        })
+    }
+}
+
+impl<'a, R: 'a, A: 'a> Pure<A> for Cont<'a, R, A>
+where
+    A: Clone,
+{
+    fn pure(value: A) -> Self {
+        Self::new(move |k| k(value.clone()))
+    }
+}
+
+impl<'a, R: 'a, A: 'a> Apply<'a, A> for Cont<'a, R, A> {
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        Cont::new(move |k: Rc<dyn Fn(B) -> R + 'a>| {
+            let this = self.clone();
+            f.run_rc(Rc::new(move |func: ApplyFn<'a, A, B>| {
+                let k = k.clone();
+                this.run_rc(Rc::new(move |value| k(func.apply_fn(value))))
+            }))
+        })
+    }
+}
+
+impl<'a, R: 'a, A: 'a> Bind<'a, A> for Cont<'a, R, A> {
+    fn bind<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> Self::Target<B> + 'a,
+    {
+        let f = Rc::new(f);
+        Cont::new(move |k: Rc<dyn Fn(B) -> R + 'a>| {
+            let f = f.clone();
+            self.run_rc(Rc::new(move |value| f(value).run_rc(k.clone())))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Cont;
+    use crate::{apply::lift2, run, Functor, Pure};
+
+    #[test]
+    fn call_cc_exits_early() {
+        fn find_first<'a>(values: Vec<i32>) -> Cont<'a, Option<i32>, Option<i32>> {
+            Cont::call_cc(move |exit| {
+                let mut program = Cont::pure(());
+                for value in values.clone() {
+                    let exit = exit.clone();
+                    program = run! {
+                        program.clone();
+                        if value < 0 { exit(Some(value)) } else { Cont::pure(()) }
+                    };
+                }
+                program.fmap(|_| None)
+            })
+        }
+
+        assert_eq!(find_first(vec![1, -2, 3, -4]).eval_cont(), Some(-2));
+        assert_eq!(find_first(vec![1, 2, 3]).eval_cont(), None);
+    }
+
+    #[test]
+    fn shift_and_reset() {
+        // 1 + reset(2 * shift(k => k(k(10)))) = 1 + 2 * 2 * 10
+        let inner: Cont<'_, i32, i32> = Cont::shift(|k| Cont::pure(k(k(10)))).fmap(|x| x * 2);
+        let program: Cont<'_, i32, i32> = inner.reset().fmap(|x| x + 1);
+        assert_eq!(program.eval_cont(), 41);
+
+        // A shift which never calls its continuation aborts up to the reset.
+        let aborted: Cont<'_, Vec<i32>, Vec<i32>> = run! {
+            x <= Cont::shift(|_| Cont::pure(vec![0]));
+            yield vec![x, x]
+        };
+        let program: Cont<'_, usize, Vec<i32>> = aborted.reset();
+        assert_eq!(program.run_cont(|values| values.len()), 1);
+    }
+
+    #[test]
+    fn functor_and_apply() {
+        let value: Cont<'_, String, i32> = Cont::pure(2).fmap(|x| x * 20);
+        let sum = lift2(&|a: i32, b: i32| a + b, value, Cont::new(|k| k(2)));
+        assert_eq!(sum.run_cont(|result| result.to_string()), "42");
+        // A continuation can be called more than once.
+        let both: Cont<'_, Vec<i32>, i32> = Cont::new(|k| {
+            let mut result: Vec<i32> = k(1);
+            result.extend(k(2));
+            result
+        });
+        assert_eq!(both.fmap(|x| x * 10).run_cont(|x| vec![x]), vec![10, 20]);
+    }
+}
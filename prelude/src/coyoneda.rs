@@ -0,0 +1,330 @@
+use std::{
+    cell::OnceCell,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet},
+    hash::Hash,
+    rc::Rc,
+};
+
+use crate::{Foldable, Functor, Monoid};
+
+/// The free [`Functor`](Functor) over any iterable container.
+///
+/// A `Coyoneda<'a, F, A>` holds a container `F` together with a function
+/// from its elements to `A`. Mapping over it doesn't touch the container at
+/// all, it just composes the new function onto the one it already has, so
+/// any chain of [`fmap`](Functor::fmap)s is fused into a single pass which
+/// only runs when you [`lower`](Coyoneda::lower) it back into a container.
+///
+/// This is how you map over types like [`HashSet`](HashSet) or
+/// [`BTreeMap`](BTreeMap), which can't be [`Functor`](Functor)s themselves
+/// because they put bounds on their element types: the intermediate types
+/// in a `Coyoneda` can be anything, and the bounds are only checked when
+/// lowering into the final container.
+///
+/// ```
+/// # use std::collections::{BTreeSet, HashSet};
+/// # use higher::{coyoneda::Coyoneda, Functor};
+/// let set = HashSet::from([1, 2, 3, 4]);
+/// let halves: BTreeSet<i32> = Coyoneda::lift(set)
+///     .fmap(|x| x as f32)
+///     .fmap(|x| (x / 2.0).floor() as i32)
+///     .lower_btree_set();
+/// assert_eq!(halves, BTreeSet::from([0, 1, 2]));
+/// ```
+pub struct Coyoneda<'a, F: IntoIterator, A> {
+    value: F,
+    f: Rc<dyn Fn(F::Item) -> A + 'a>,
+    memo: OnceCell<Vec<A>>,
+}
+
+impl<'a, F: IntoIterator, A> Coyoneda<'a, F, A> {
+    /// Lift a container into a `Coyoneda` with the given function.
+    pub fn new<G>(value: F, f: G) -> Self
+    where
+        G: Fn(F::Item) -> A + 'a,
+    {
+        Self {
+            value,
+            f: Rc::new(f),
+            memo: OnceCell::new(),
+        }
+    }
+
+    /// Run the accumulated function over the container, collecting the
+    /// results into any container which can hold them.
+    pub fn lower<C>(self) -> C
+    where
+        C: FromIterator<A>,
+    {
+        let f = self.f;
+        self.value.into_iter().map(|item| f(item)).collect()
+    }
+
+    /// Lower into a [`Vec`](Vec), which has no bounds on its elements.
+    pub fn lower_vec(self) -> Vec<A> {
+        self.lower()
+    }
+
+    /// Lower into a [`HashSet`](HashSet).
+    pub fn lower_hash_set(self) -> HashSet<A>
+    where
+        A: Hash + Eq,
+    {
+        self.lower()
+    }
+
+    /// Lower into a [`BTreeSet`](BTreeSet).
+    pub fn lower_btree_set(self) -> BTreeSet<A>
+    where
+        A: Ord,
+    {
+        self.lower()
+    }
+
+    /// Lower into a [`BinaryHeap`](BinaryHeap).
+    pub fn lower_binary_heap(self) -> BinaryHeap<A>
+    where
+        A: Ord,
+    {
+        self.lower()
+    }
+}
+
+impl<'a, F: IntoIterator, K, V> Coyoneda<'a, F, (K, V)> {
+    /// Lower into a [`HashMap`](HashMap). Later entries overwrite earlier
+    /// entries with the same key.
+    pub fn lower_hash_map(self) -> HashMap<K, V>
+    where
+        K: Hash + Eq,
+    {
+        self.lower()
+    }
+
+    /// Lower into a [`BTreeMap`](BTreeMap). Later entries overwrite earlier
+    /// entries with the same key.
+    pub fn lower_btree_map(self) -> BTreeMap<K, V>
+    where
+        K: Ord,
+    {
+        self.lower()
+    }
+}
+
+impl<'a, F> Coyoneda<'a, F, F::Item>
+where
+    F: IntoIterator,
+    F::Item: 'a,
+{
+    /// Lift a container into a `Coyoneda`. Maps are lifted as containers of
+    /// `(key, value)` pairs.
+    pub fn lift(value: F) -> Self {
+        Self::new(value, |item| item)
+    }
+}
+
+impl<'a, A: 'a> From<HashSet<A>> for Coyoneda<'a, HashSet<A>, A> {
+    fn from(set: HashSet<A>) -> Self {
+        Self::lift(set)
+    }
+}
+
+impl<'a, A: 'a> From<BTreeSet<A>> for Coyoneda<'a, BTreeSet<A>, A> {
+    fn from(set: BTreeSet<A>) -> Self {
+        Self::lift(set)
+    }
+}
+
+impl<'a, A: 'a> From<BinaryHeap<A>> for Coyoneda<'a, BinaryHeap<A>, A> {
+    fn from(heap: BinaryHeap<A>) -> Self {
+        Self::lift(heap)
+    }
+}
+
+impl<'a, K: 'a, V: 'a> From<HashMap<K, V>> for Coyoneda<'a, HashMap<K, V>, (K, V)> {
+    fn from(map: HashMap<K, V>) -> Self {
+        Self::lift(map)
+    }
+}
+
+impl<'a, K: 'a, V: 'a> From<BTreeMap<K, V>> for Coyoneda<'a, BTreeMap<K, V>, (K, V)> {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        Self::lift(map)
+    }
+}
+
+impl<'a, F: IntoIterator + Clone, A> Clone for Coyoneda<'a, F, A> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            f: self.f.clone(),
+            memo: OnceCell::new(),
+        }
+    }
+}
+
+impl<'a, F: IntoIterator + std::fmt::Debug, A> std::fmt::Debug for Coyoneda<'a, F, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Coyoneda").field(&self.value).finish()
+    }
+}
+
+impl<'a, F, A> Functor<'a, A> for Coyoneda<'a, F, A>
+where
+    F: IntoIterator + 'a,
+    A: 'a,
+{
+    type Target<T: 'a> = Coyoneda<'a, F, T>;
+
+    fn fmap<B, G>(self, g: G) -> Self::Target<B>
+    where
+        B: 'a,
+        G: Fn(A) -> B + 'a,
+    {
+        let f = self.f;
+        Coyoneda::new(self.value, move |item| g(f(item)))
+    }
+}
+
+impl<'a, F, A> Coyoneda<'a, F, A>
+where
+    F: IntoIterator + Clone,
+{
+    /// Run the accumulated function over a copy of the container and keep
+    /// the results, so that they can be borrowed for as long as `self` is.
+    ///
+    /// Panics if the function tries to fold over this same `Coyoneda` by
+    /// reference while the results are being computed.
+    fn values(&self) -> &[A] {
+        self.memo.get_or_init(|| {
+            let f = &self.f;
+            self.value.clone().into_iter().map(|item| f(item)).collect()
+        })
+    }
+}
+
+impl<'a, F, A> Foldable<'a, A> for Coyoneda<'a, F, A>
+where
+    F: IntoIterator + Clone,
+    A: 'a,
+{
+    fn foldr<B, G>(self, g: G, init: B) -> B
+    where
+        G: Fn(A, B) -> B,
+    {
+        self.lower_vec().into_iter().rfold(init, |a, b| g(b, a))
+    }
+
+    fn foldr_ref<B, G>(&'a self, g: G, init: B) -> B
+    where
+        G: Fn(&'a A, B) -> B + 'a,
+    {
+        self.values().iter().rfold(init, |a, b| g(b, a))
+    }
+
+    fn foldl<B, G>(self, g: G, init: B) -> B
+    where
+        G: Fn(B, A) -> B,
+    {
+        let f = self.f;
+        self.value
+            .into_iter()
+            .fold(init, |acc, item| g(acc, f(item)))
+    }
+
+    fn foldl_ref<B, G>(&'a self, g: G, init: B) -> B
+    where
+        G: Fn(B, &'a A) -> B + 'a,
+    {
+        self.values().iter().fold(init, g)
+    }
+
+    fn fold_map<G, M>(self, g: G) -> M
+    where
+        G: Fn(A) -> M,
+        M: Monoid,
+    {
+        self.foldl(|acc: M, item| acc.mappend(g(item)), M::default())
+    }
+
+    fn fold_map_ref<G, M>(&'a self, g: G) -> M
+    where
+        G: Fn(&'a A) -> M + 'a,
+        M: Monoid + 'a,
+    {
+        self.values()
+            .iter()
+            .fold(M::default(), |acc, item| acc.mappend(g(item)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        cell::Cell,
+        collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet},
+        rc::Rc,
+    };
+
+    use super::Coyoneda;
+    use crate::{Foldable, Functor};
+
+    #[test]
+    fn fuses_maps_into_one_pass() {
+        let calls = Rc::new(Cell::new(0));
+        let counter = calls.clone();
+        let coyo = Coyoneda::lift(vec![1, 2, 3])
+            .fmap(move |x| {
+                counter.set(counter.get() + 1);
+                x + 1
+            })
+            .fmap(|x| x * 2)
+            .fmap(|x| x.to_string());
+        assert_eq!(calls.get(), 0);
+        assert_eq!(coyo.lower_vec(), vec!["4", "6", "8"]);
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[test]
+    fn lowers_into_sets() {
+        let set = HashSet::from([-2, -1, 1, 2]);
+        let abs: HashSet<i32> = Coyoneda::from(set.clone()).fmap(i32::abs).lower_hash_set();
+        assert_eq!(abs, HashSet::from([1, 2]));
+
+        let strings = Coyoneda::from(BTreeSet::from([3, 1, 2]))
+            .fmap(|x| x * 10)
+            .lower_btree_set();
+        assert_eq!(strings, BTreeSet::from([10, 20, 30]));
+
+        let heap: BinaryHeap<i32> = Coyoneda::lift(set).fmap(|x| x * x).lower();
+        assert_eq!(heap.into_sorted_vec(), vec![1, 1, 4, 4]);
+    }
+
+    #[test]
+    fn lowers_into_maps() {
+        let map = BTreeMap::from([("a", 1), ("b", 2)]);
+        let map: HashMap<&str, String> = Coyoneda::from(map)
+            .fmap(|(k, v)| (k, v * 2))
+            .fmap(|(k, v)| (k, v.to_string()))
+            .lower_hash_map();
+        assert_eq!(
+            map,
+            HashMap::from([("a", "2".to_string()), ("b", "4".to_string())])
+        );
+
+        let swapped = Coyoneda::from(map).fmap(|(k, v)| (v, k)).lower_btree_map();
+        assert_eq!(swapped.get("4"), Some(&"b"));
+    }
+
+    #[test]
+    fn folds_mapped_values() {
+        let coyo = Coyoneda::lift(vec![1, 2, 3]).fmap(|x| x * 2);
+        assert_eq!(coyo.foldl_ref(|acc, x| acc - x, 0), -12);
+        assert_eq!(coyo.foldr_ref(|x, acc| acc - x, 0), -12);
+        assert_eq!(coyo.fold_map_ref(|x: &i32| x.to_string()), "246");
+        assert_eq!(coyo.clone().fold_map(|x| x.to_string()), "246");
+        assert_eq!(
+            coyo.foldr(|x, acc| format!("{}{}", acc, x), String::new()),
+            "642"
+        );
+    }
+}
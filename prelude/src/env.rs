@@ -0,0 +1,113 @@
+use crate::{Comonad, Extend, Functor};
+
+/// An `Env` comonad.
+///
+/// An `Env` pairs a value with an environment which it carries along, and
+/// which every computation run through [`extend`](Extend::extend) can
+/// [`ask`](Env::ask) for. It's the comonadic counterpart to
+/// [`Reader`](crate::Reader).
+///
+/// ```
+/// # use higher::{env::Env, Comonad, Extend};
+/// let reading = Env::new(1.5, 20.0);
+/// let scaled = reading.extend(|w: Env<f64, f64>| w.ask() * w.extract());
+/// assert_eq!(scaled.extract(), 30.0);
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Env<E, A> {
+    env: E,
+    value: A,
+}
+
+impl<E, A> Env<E, A> {
+    /// Construct an `Env` from an environment and a value.
+    pub fn new(env: E, value: A) -> Self {
+        Self { env, value }
+    }
+
+    /// Get the environment.
+    pub fn ask(&self) -> &E {
+        &self.env
+    }
+
+    /// Get the result of applying a function to the environment.
+    pub fn asks<B, F>(&self, f: F) -> B
+    where
+        F: FnOnce(&E) -> B,
+    {
+        f(&self.env)
+    }
+
+    /// Modify the environment using the given function.
+    pub fn local<F>(self, f: F) -> Self
+    where
+        F: FnOnce(E) -> E,
+    {
+        Self::new(f(self.env), self.value)
+    }
+
+    /// Get the environment and the value.
+    pub fn run_env(self) -> (E, A) {
+        (self.env, self.value)
+    }
+}
+
+impl<'a, E, A: 'a> Functor<'a, A> for Env<E, A> {
+    type Target<T: 'a> = Env<E, T>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        Env::new(self.env, f(self.value))
+    }
+}
+
+impl<'a, E, A: 'a> Extend<'a, A> for Env<E, A>
+where
+    E: Clone,
+{
+    fn extend<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(Self) -> B + 'a,
+    {
+        Env::new(self.env.clone(), f(self))
+    }
+}
+
+impl<'a, E, A: 'a> Comonad<'a, A> for Env<E, A>
+where
+    E: Clone,
+{
+    fn extract(self) -> A {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Env;
+    use crate::{Comonad, Extend, Functor};
+
+    #[test]
+    fn comonad_laws() {
+        let w = Env::new("env".to_string(), 5);
+        let f = |w: Env<String, usize>| w.ask().len() + w.extract();
+        let g = |w: Env<String, usize>| w.extract() * 2;
+        assert_eq!(w.clone().extend(|w| w.extract()), w);
+        assert_eq!(w.clone().extend(f).extract(), f(w.clone()));
+        assert_eq!(
+            w.clone().extend(f).extend(g),
+            w.extend(move |w| g(w.extend(f)))
+        );
+    }
+
+    #[test]
+    fn local_and_fmap() {
+        let w = Env::new(2, "hello").fmap(str::len).local(|env| env * 10);
+        assert_eq!(w.asks(|env| env + 1), 21);
+        assert_eq!(w.run_env(), (20, 5));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{run, Bind, Functor, Pure};
+use crate::{run, Bind, Functor, Pure, Reader, Semigroup, State, Writer};
 
 pub trait ApplicativeError<'a, A: 'a, E: 'a>: Functor<'a, A> + Pure<A> {
     /// Throw an error.
@@ -14,9 +14,9 @@ pub trait ApplicativeError<'a, A: 'a, E: 'a>: Functor<'a, A> + Pure<A> {
     /// [`ApplicativeError`](ApplicativeError).
     ///
     /// If there's no error, do nothing.
-    fn handle_error_with<F: 'a>(self, f: F) -> Self
+    fn handle_error_with<F>(self, f: F) -> Self
     where
-        F: Fn(E) -> Self;
+        F: Fn(E) -> Self + 'a;
 
     /// Handle an error.
     ///
@@ -25,10 +25,10 @@ pub trait ApplicativeError<'a, A: 'a, E: 'a>: Functor<'a, A> + Pure<A> {
     /// wrapped in a new [`ApplicativeError`](ApplicativeError).
     ///
     /// If there's no error, do nothing.
-    fn handle_error<F: 'a>(self, f: F) -> Self
+    fn handle_error<F>(self, f: F) -> Self
     where
         Self: Sized,
-        F: Fn(E) -> A,
+        F: Fn(E) -> A + 'a,
     {
         self.handle_error_with(move |e| Pure::pure(f(e)))
     }
@@ -67,11 +67,11 @@ pub trait ApplicativeError<'a, A: 'a, E: 'a>: Functor<'a, A> + Pure<A> {
         self.recover_with(move |error| Err(adapt(error)))
     }
 
-    fn redeem<B: 'a, FE: 'a, FA: 'a>(self, recover: FE, map: FA) -> Self::Target<B>
+    fn redeem<B: 'a, FE, FA>(self, recover: FE, map: FA) -> Self::Target<B>
     where
         Self: Sized,
-        FE: Fn(E) -> B,
-        FA: Fn(A) -> B,
+        FE: Fn(E) -> B + 'a,
+        FA: Fn(A) -> B + 'a,
 
         Self::Target<B>: ApplicativeError<'a, B, E>,
     {
@@ -107,11 +107,11 @@ pub trait MonadError<'a, A: 'a, E: 'a>: ApplicativeError<'a, A, E> + Bind<'a, A>
         }
     }
 
-    fn ensure_or<P: 'a, F: 'a>(self, error: F, predicate: P) -> Self
+    fn ensure_or<P, F>(self, error: F, predicate: P) -> Self
     where
         Self: Sized,
-        P: Fn(&A) -> bool,
-        F: Fn(&A) -> E,
+        P: Fn(&A) -> bool + 'a,
+        F: Fn(&A) -> E + 'a,
 
         Self: Bind<'a, A, Target<A> = Self>,
     {
@@ -125,22 +125,22 @@ pub trait MonadError<'a, A: 'a, E: 'a>: ApplicativeError<'a, A, E> + Bind<'a, A>
         }
     }
 
-    fn ensure<P: 'a>(self, error: E, predicate: P) -> Self
+    fn ensure<P>(self, error: E, predicate: P) -> Self
     where
         Self: Sized,
         E: Clone,
-        P: Fn(&A) -> bool,
+        P: Fn(&A) -> bool + 'a,
 
         Self: Bind<'a, A, Target<A> = Self>,
     {
         self.ensure_or(move |_| error.clone(), predicate)
     }
 
-    fn redeem_with<B: 'a, FA: 'a, FE: 'a>(self, recover: FE, bind: FA) -> Self::Target<B>
+    fn redeem_with<B: 'a, FA, FE>(self, recover: FE, bind: FA) -> Self::Target<B>
     where
         Self: Sized + Bind<'a, A, Target<A> = Self>,
-        FE: Fn(E) -> Self::Target<B>,
-        FA: Fn(A) -> Self::Target<B>,
+        FE: Fn(E) -> Self::Target<B> + 'a,
+        FA: Fn(A) -> Self::Target<B> + 'a,
 
         Self::Target<Result<A, E>>: Bind<'a, Result<A, E>, Target<B> = Self::Target<B>>
             + Pure<Result<A, E>>
@@ -158,6 +158,131 @@ impl<'a, A: 'a, E: 'a, M> MonadError<'a, A, E> for M where
 {
 }
 
+/// A `MonadState` is a monad which carries a state value of type `S`.
+///
+/// This lets you write code which reads and updates the state without
+/// naming a concrete monad: it works the same for a [`State`](State) as for
+/// any stack of [transformers](crate::transformers) with a
+/// [`StateT`](crate::transformers::StateT) somewhere inside it.
+pub trait MonadState<'a, A: 'a, S: 'a>: Bind<'a, A> {
+    /// Get the current state.
+    fn get() -> Self::Target<S>;
+
+    /// Replace the current state with the given value.
+    fn put(state: S) -> Self::Target<()>;
+
+    /// Update the current state using the given function.
+    fn modify<F>(f: F) -> Self::Target<()>
+    where
+        F: Fn(S) -> S + 'a;
+}
+
+/// A `MonadReader` is a monad which has access to a shared environment of
+/// type `R`.
+///
+/// This lets you write code which reads the environment without naming a
+/// concrete monad: it works the same for a [`Reader`](Reader) as for any
+/// stack of [transformers](crate::transformers) with a
+/// [`ReaderT`](crate::transformers::ReaderT) somewhere inside it.
+///
+/// ```
+/// # use higher::{run, ApplicativeError, Bind, MonadReader, transformers::ReaderT};
+/// fn port<'a, M>() -> M
+/// where
+///     M: MonadReader<'a, u16, Vec<(String, String)>> + ApplicativeError<'a, u16, String>,
+///     M::Target<Vec<(String, String)>>: Bind<'a, Vec<(String, String)>, Target<u16> = M>,
+/// {
+///     run! {
+///         config <= <u16> M::ask();
+///         match config.iter().find(|(key, _)| key == "port") {
+///             Some((_, value)) => value.parse().map_or_else(
+///                 |_| M::throw_error(format!("invalid port: {}", value)),
+///                 M::pure,
+///             ),
+///             None => M::throw_error("no port configured".to_string()),
+///         }
+///     }
+/// }
+///
+/// let config = vec![("port".to_string(), "8080".to_string())];
+/// let program: ReaderT<'_, _, Result<u16, String>> = port();
+/// assert_eq!(program.run_reader(config), Ok(8080));
+/// let program: ReaderT<'_, _, Result<u16, String>> = port();
+/// assert_eq!(program.run_reader(vec![]), Err("no port configured".to_string()));
+/// ```
+pub trait MonadReader<'a, A: 'a, R: 'a>: Bind<'a, A> {
+    /// Get the environment.
+    fn ask() -> Self::Target<R>;
+
+    /// Run the computation in an environment modified by the given function.
+    fn local<F>(self, f: F) -> Self
+    where
+        F: Fn(R) -> R + 'a;
+}
+
+/// A `MonadWriter` is a monad which accumulates output of type `W`.
+///
+/// This lets you write code which produces output without naming a concrete
+/// monad: it works the same for a [`Writer`](Writer) as for any stack of
+/// [transformers](crate::transformers) with a
+/// [`WriterT`](crate::transformers::WriterT) somewhere inside it.
+pub trait MonadWriter<'a, A: 'a, W: 'a>: Bind<'a, A> {
+    /// Add the given value to the output.
+    fn tell(output: W) -> Self::Target<()>;
+
+    /// Add the output accumulated by this computation to its result value.
+    fn listen(self) -> Self::Target<(A, W)>;
+}
+
+impl<'a, A: 'a, S: 'a> MonadState<'a, A, S> for State<'a, S, A>
+where
+    S: Clone,
+{
+    fn get() -> Self::Target<S> {
+        State::get()
+    }
+
+    fn put(state: S) -> Self::Target<()> {
+        State::put(state)
+    }
+
+    fn modify<F>(f: F) -> Self::Target<()>
+    where
+        F: Fn(S) -> S + 'a,
+    {
+        State::modify(f)
+    }
+}
+
+impl<'a, A: 'a, R: 'a> MonadReader<'a, A, R> for Reader<'a, R, A>
+where
+    R: Clone,
+{
+    fn ask() -> Self::Target<R> {
+        Reader::ask()
+    }
+
+    fn local<F>(self, f: F) -> Self
+    where
+        F: Fn(R) -> R + 'a,
+    {
+        Reader::local(self, f)
+    }
+}
+
+impl<'a, A: 'a, W: 'a> MonadWriter<'a, A, W> for Writer<W, A>
+where
+    W: Semigroup + Clone,
+{
+    fn tell(output: W) -> Self::Target<()> {
+        Writer::tell(output)
+    }
+
+    fn listen(self) -> Self::Target<(A, W)> {
+        Writer::listen(self)
+    }
+}
+
 impl<'a, A: 'a> ApplicativeError<'a, A, ()> for Option<A> {
     fn throw_error(_error: ()) -> Self::Target<A> {
         None
@@ -183,3 +308,83 @@ impl<'a, A: 'a, E: 'a> ApplicativeError<'a, A, E> for Result<A, E> {
         self.or_else(f)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        run,
+        transformers::{OptionT, ReaderT, ResultT, StateT},
+        Bind, Functor, MonadReader, MonadState, MonadWriter, Pure, Reader, State, Writer,
+    };
+
+    fn next<'a, M>() -> M
+    where
+        M: MonadState<'a, u32, u32> + Pure<u32>,
+        M::Target<u32>: Bind<'a, u32, Target<u32> = M>,
+        M::Target<()>: Bind<'a, (), Target<u32> = M>,
+    {
+        run! {
+            n <= <u32> M::get();
+            <u32> M::put(n + 1);
+            yield n
+        }
+    }
+
+    #[test]
+    fn monad_state() {
+        let state: State<'_, u32, u32> = next();
+        assert_eq!(state.run_state(5), (5, 6));
+        let state: StateT<'_, u32, Option<(u32, u32)>> = next();
+        assert_eq!(state.run_state(5), Some((5, 6)));
+        let state: OptionT<StateT<'_, u32, Option<(Option<u32>, u32)>>> = next();
+        assert_eq!(state.run_option().run_state(5), Some((Some(5), 6)));
+        let state: ReaderT<'_, (), StateT<'_, u32, Option<(u32, u32)>>> = next();
+        assert_eq!(state.run_reader(()).run_state(5), Some((5, 6)));
+    }
+
+    fn scaled<'a, M>(value: u32) -> M
+    where
+        M: MonadReader<'a, u32, u32>,
+    {
+        M::ask().fmap(move |factor| value * factor)
+    }
+
+    #[test]
+    fn monad_reader() {
+        let reader: Reader<'_, u32, u32> = scaled(5);
+        assert_eq!(reader.local(|factor| factor + 1).run_reader(2), 15);
+        let reader: ResultT<(), ReaderT<'_, u32, Option<Result<u32, ()>>>> = scaled(5);
+        assert_eq!(
+            reader.local(|factor| factor + 1).run_result().run_reader(2),
+            Some(Ok(15))
+        );
+    }
+
+    fn log<'a, M>(value: u32) -> M
+    where
+        M: MonadWriter<'a, u32, Vec<String>> + Pure<u32>,
+        M::Target<()>: Bind<'a, (), Target<u32> = M>,
+    {
+        run! {
+            <u32> M::tell(vec![format!("got {}", value)]);
+            yield value
+        }
+    }
+
+    #[test]
+    fn monad_writer() {
+        let writer: Writer<Vec<String>, u32> = log(5);
+        assert_eq!(
+            writer.listen().run_writer().0,
+            (5, vec!["got 5".to_string()])
+        );
+        let writer: OptionT<Writer<Vec<String>, Option<u32>>> = log(5);
+        assert_eq!(
+            writer.listen().run_option().run_writer(),
+            (
+                Some((5, vec!["got 5".to_string()])),
+                vec!["got 5".to_string()]
+            )
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use crate::Functor;
+
+/// `Extend` is the dual of [`Bind`](crate::Bind).
+///
+/// Where [`Bind`](crate::Bind) takes a function `Fn(A) -> W<B>` which
+/// produces a new context from a single value, `Extend` takes a function
+/// `Fn(W<A>) -> B` which computes a single value from a whole context, and
+/// applies it at every position in the context `W<A>`, producing a `W<B>`.
+///
+/// This is what you need to express computations where each value depends on
+/// its surroundings, like cellular automata or image convolution.
+///
+/// Instances should satisfy the law of associativity:
+/// * `w.extend(f).extend(g) == w.extend(|w| g(w.extend(f)))`
+pub trait Extend<'a, A: 'a>: Functor<'a, A> {
+    /// Apply the function `f` to the context at every position inside `W<A>`
+    /// to turn it into a `W<B>`.
+    fn extend<B: 'a, F>(self, f: F) -> Self::Target<B>
+    where
+        Self: Sized,
+        F: Fn(Self) -> B + 'a;
+
+    /// Turn a `W<A>` into a `W<W<A>>`, where each position holds the whole
+    /// context as seen from that position.
+    fn duplicate(self) -> Self::Target<Self>
+    where
+        Self: Sized + 'a,
+    {
+        self.extend(|w| w)
+    }
+}
+
+impl<'a, E, A: 'a> Extend<'a, A> for (E, A)
+where
+    E: Clone,
+{
+    fn extend<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(Self) -> B + 'a,
+    {
+        (self.0.clone(), f(self))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Comonad, Extend};
+
+    #[test]
+    fn extend_tuple() {
+        let w = ("env", 2);
+        assert_eq!(w.extend(|(e, a)| e.len() * a), ("env", 6));
+        assert_eq!(w.duplicate(), ("env", ("env", 2)));
+        assert_eq!(w.duplicate().extract(), w);
+    }
+}
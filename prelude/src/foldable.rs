@@ -1,6 +1,9 @@
 use std::{
-    collections::{LinkedList, VecDeque},
+    collections::{BTreeMap, HashMap, LinkedList, VecDeque},
     convert::identity,
+    rc::Rc,
+    sync::Arc,
+    task::Poll,
 };
 
 use crate::{
@@ -12,25 +15,25 @@ use crate::{
 };
 
 pub trait Foldable<'a, A: 'a> {
-    fn foldr<B: 'a, F: 'a>(self, f: F, init: B) -> B
+    fn foldr<B: 'a, F>(self, f: F, init: B) -> B
     where
-        F: Fn(A, B) -> B;
-    fn foldr_ref<B: 'a, F: 'a>(&'a self, f: F, init: B) -> B
+        F: Fn(A, B) -> B + 'a;
+    fn foldr_ref<B: 'a, F>(&'a self, f: F, init: B) -> B
     where
-        F: Fn(&'a A, B) -> B;
-    fn foldl<B: 'a, F: 'a>(self, f: F, init: B) -> B
+        F: Fn(&'a A, B) -> B + 'a;
+    fn foldl<B: 'a, F>(self, f: F, init: B) -> B
     where
-        F: Fn(B, A) -> B;
-    fn foldl_ref<B: 'a, F: 'a>(&'a self, f: F, init: B) -> B
+        F: Fn(B, A) -> B + 'a;
+    fn foldl_ref<B: 'a, F>(&'a self, f: F, init: B) -> B
     where
-        F: Fn(B, &'a A) -> B;
-    fn fold_map<F: 'a, M>(self, f: F) -> M
+        F: Fn(B, &'a A) -> B + 'a;
+    fn fold_map<F, M>(self, f: F) -> M
     where
-        F: Fn(A) -> M,
+        F: Fn(A) -> M + 'a,
         M: Monoid;
-    fn fold_map_ref<F: 'a, M>(&'a self, f: F) -> M
+    fn fold_map_ref<F, M>(&'a self, f: F) -> M
     where
-        F: Fn(&'a A) -> M,
+        F: Fn(&'a A) -> M + 'a,
         M: Monoid + 'a;
 
     fn fold(self) -> A
@@ -41,12 +44,12 @@ pub trait Foldable<'a, A: 'a> {
         self.fold_map(identity)
     }
 
-    fn traverse_unit<B: 'a, M: 'a, F: 'a>(self, f: F) -> M::Target<()>
+    fn traverse_unit<B, M, F>(self, f: F) -> M::Target<()>
     where
         Self: Sized,
-        B: Clone,
-        M: Applicative<'a, B>,
-        F: Fn(A) -> M,
+        B: Clone + 'a,
+        M: Applicative<'a, B> + 'a,
+        F: Fn(A) -> M + 'a,
 
         M::Target<()>:
             Applicative<'a, (), Target<ApplyFn<'a, B, ()>> = M::Target<ApplyFn<'a, B, ()>>>,
@@ -54,10 +57,10 @@ pub trait Foldable<'a, A: 'a> {
         self.foldr(move |x, y| f(x).apply_second(y), Pure::pure(()))
     }
 
-    fn sequence_unit<B: 'a>(self) -> A::Target<()>
+    fn sequence_unit<B>(self) -> A::Target<()>
     where
         Self: Sized,
-        B: Clone,
+        B: Clone + 'a,
         A: Applicative<'a, B>,
 
         A::Target<()>:
@@ -66,12 +69,12 @@ pub trait Foldable<'a, A: 'a> {
         self.traverse_unit(identity)
     }
 
-    fn fold_m<M: 'a, B: 'a, F: 'a>(&'a self, f: &'a F, init: B) -> M
+    fn fold_m<M, B, F>(&'a self, f: &'a F, init: B) -> M
     where
         Self: Sized,
-        B: Clone,
-        M: Pure<B> + Bind<'a, B, Target<B> = M>,
-        F: Fn(B, &'a A) -> M,
+        B: Clone + 'a,
+        M: Pure<B> + Bind<'a, B, Target<B> = M> + 'a,
+        F: Fn(B, &'a A) -> M + 'a,
     {
         self.foldl_ref(move |m, a| m.bind::<B, _>(move |b| f(b, a)), M::pure(init))
     }
@@ -100,29 +103,29 @@ pub trait Foldable<'a, A: 'a> {
         self.foldr(Alt::alt, Default::default())
     }
 
-    fn one_of_map<B: 'a, GB: 'a, F: 'a>(self, f: F) -> GB
+    fn one_of_map<B: 'a, GB, F>(self, f: F) -> GB
     where
         Self: Sized,
-        GB: Plus<'a, B>,
-        F: Fn(A) -> GB,
+        GB: Plus<'a, B> + 'a,
+        F: Fn(A) -> GB + 'a,
     {
         self.foldr(move |a, bs| f(a).alt(bs), Default::default())
     }
 
-    fn all<B: 'a, F: 'a>(&'a self, f: F) -> B
+    fn all<B, F>(&'a self, f: F) -> B
     where
         Self: Sized,
-        F: Fn(&'a A) -> B,
-        B: HeytingAlgebra,
+        F: Fn(&'a A) -> B + 'a,
+        B: HeytingAlgebra + 'a,
     {
         self.fold_map_ref(move |a| Conj(f(a))).unwrap()
     }
 
-    fn any<B: 'a, F: 'a>(&'a self, f: F) -> B
+    fn any<B, F>(&'a self, f: F) -> B
     where
         Self: Sized,
-        F: Fn(&'a A) -> B,
-        B: HeytingAlgebra,
+        F: Fn(&'a A) -> B + 'a,
+        B: HeytingAlgebra + 'a,
     {
         self.fold_map_ref(move |a| Disj(f(a))).unwrap()
     }
@@ -298,6 +301,148 @@ impl<'a, A: 'a, E> Foldable<'a, A> for Result<A, E> {
     }
 }
 
+impl<'a, A: 'a> Foldable<'a, A> for Poll<A> {
+    fn foldr<B, F>(self, f: F, init: B) -> B
+    where
+        B: 'a,
+        F: Fn(A, B) -> B + 'a,
+    {
+        match self {
+            Poll::Ready(value) => f(value, init),
+            Poll::Pending => init,
+        }
+    }
+
+    fn foldr_ref<B, F>(&'a self, f: F, init: B) -> B
+    where
+        B: 'a,
+        F: Fn(&'a A, B) -> B + 'a,
+    {
+        match self {
+            Poll::Ready(value) => f(value, init),
+            Poll::Pending => init,
+        }
+    }
+
+    fn foldl<B, F>(self, f: F, init: B) -> B
+    where
+        B: 'a,
+        F: Fn(B, A) -> B + 'a,
+    {
+        match self {
+            Poll::Ready(value) => f(init, value),
+            Poll::Pending => init,
+        }
+    }
+
+    fn foldl_ref<B, F>(&'a self, f: F, init: B) -> B
+    where
+        B: 'a,
+        F: Fn(B, &'a A) -> B + 'a,
+    {
+        match self {
+            Poll::Ready(value) => f(init, value),
+            Poll::Pending => init,
+        }
+    }
+
+    fn fold_map<F, M>(self, f: F) -> M
+    where
+        F: Fn(A) -> M + 'a,
+        M: Monoid,
+    {
+        match self {
+            Poll::Ready(value) => f(value),
+            Poll::Pending => M::default(),
+        }
+    }
+
+    fn fold_map_ref<F, M>(&'a self, f: F) -> M
+    where
+        F: Fn(&'a A) -> M + 'a,
+        M: Monoid,
+    {
+        match self {
+            Poll::Ready(value) => f(value),
+            Poll::Pending => M::default(),
+        }
+    }
+}
+
+/// Implement `Foldable` for a type holding exactly one `A`, given how to take
+/// the `A` out of it and how to borrow it.
+macro_rules! impl_foldable_for_single {
+    ([$($generics:tt)*] $type:ty, |$this:ident| $owned:expr, |$that:ident| $borrowed:expr) => {
+        impl<'a, $($generics)*> Foldable<'a, A> for $type {
+            fn foldr<B, F>(self, f: F, init: B) -> B
+            where
+                F: Fn(A, B) -> B,
+            {
+                let $this = self;
+                f($owned, init)
+            }
+
+            fn foldr_ref<B, F>(&'a self, f: F, init: B) -> B
+            where
+                F: Fn(&'a A, B) -> B,
+            {
+                let $that = self;
+                f($borrowed, init)
+            }
+
+            fn foldl<B, F>(self, f: F, init: B) -> B
+            where
+                F: Fn(B, A) -> B,
+            {
+                let $this = self;
+                f(init, $owned)
+            }
+
+            fn foldl_ref<B, F>(&'a self, f: F, init: B) -> B
+            where
+                F: Fn(B, &'a A) -> B,
+            {
+                let $that = self;
+                f(init, $borrowed)
+            }
+
+            fn fold_map<F, M>(self, f: F) -> M
+            where
+                F: Fn(A) -> M,
+                M: Monoid,
+            {
+                let $this = self;
+                f($owned)
+            }
+
+            fn fold_map_ref<F, M>(&'a self, f: F) -> M
+            where
+                F: Fn(&'a A) -> M,
+                M: Monoid,
+            {
+                let $that = self;
+                f($borrowed)
+            }
+        }
+    };
+}
+
+impl_foldable_for_single!([A: 'a] Box<A>, |this| *this, |this| &**this);
+impl_foldable_for_single!([W, A: 'a] (W, A), |this| this.1, |this| &this.1);
+
+// Folding a shared pointer by value clones the contents only if there are
+// other references to them.
+impl_foldable_for_single!(
+    [A: Clone + 'a] Rc<A>,
+    |this| Rc::try_unwrap(this).unwrap_or_else(|rc| (*rc).clone()),
+    |this| &**this
+);
+impl_foldable_for_single!(
+    [A: Clone + 'a] Arc<A>,
+    |this| Arc::try_unwrap(this).unwrap_or_else(|arc| (*arc).clone()),
+    |this| &**this
+);
+
 macro_rules! impl_foldable_from_iter {
     () => {
         fn foldl<B, F>(self, f: F, init: B) -> B
@@ -364,6 +509,61 @@ impl<'a, A: 'a> Foldable<'a, A> for LinkedList<A> {
     impl_foldable_from_iter!();
 }
 
+macro_rules! impl_foldable_for_map {
+    ($type:ident) => {
+        impl<'a, K, V: 'a> Foldable<'a, V> for $type<K, V> {
+            fn foldl<B, F>(self, f: F, init: B) -> B
+            where
+                F: Fn(B, V) -> B,
+            {
+                self.into_values().fold(init, f)
+            }
+
+            fn foldr<B, F>(self, f: F, init: B) -> B
+            where
+                F: Fn(V, B) -> B,
+            {
+                let values: Vec<V> = self.into_values().collect();
+                values.into_iter().rfold(init, |a, b| f(b, a))
+            }
+
+            fn fold_map<F, M>(self, f: F) -> M
+            where
+                F: Fn(V) -> M,
+                M: Monoid,
+            {
+                fold_map_default_l(f, self)
+            }
+
+            fn foldr_ref<B, F>(&'a self, f: F, init: B) -> B
+            where
+                F: Fn(&'a V, B) -> B + 'a,
+            {
+                let values: Vec<&'a V> = self.values().collect();
+                values.into_iter().rfold(init, |a, b| f(b, a))
+            }
+
+            fn foldl_ref<B, F>(&'a self, f: F, init: B) -> B
+            where
+                F: Fn(B, &'a V) -> B + 'a,
+            {
+                self.values().fold(init, f)
+            }
+
+            fn fold_map_ref<F, M>(&'a self, f: F) -> M
+            where
+                F: Fn(&'a V) -> M + 'a,
+                M: Monoid + 'a,
+            {
+                fold_map_default_l_ref(f, self)
+            }
+        }
+    };
+}
+
+impl_foldable_for_map!(HashMap);
+impl_foldable_for_map!(BTreeMap);
+
 #[cfg(test)]
 mod test {
     use crate::Foldable;
@@ -382,6 +582,31 @@ mod test {
         assert_eq!(b, -7);
     }
 
+    #[test]
+    fn fold_shared_pointers() {
+        let a = std::rc::Rc::new(vec![1, 2]);
+        let b = a.clone();
+        assert_eq!(
+            a.foldl(
+                |mut acc: Vec<i32>, x| {
+                    acc.extend(x);
+                    acc
+                },
+                vec![0]
+            ),
+            vec![0, 1, 2]
+        );
+        assert_eq!(b.fold_map_ref(|x: &Vec<i32>| x.len().to_string()), "2");
+        assert_eq!(std::sync::Arc::new(3).foldr(|x, acc| x - acc, 1), 2);
+    }
+
+    #[test]
+    fn fold_map_values() {
+        let a = std::collections::BTreeMap::from([(3, 1), (1, 2), (2, 3)]);
+        assert_eq!(a.fold_map_ref(|x: &i32| x.to_string()), "231");
+        assert_eq!(a.foldr(|x, acc| acc - x, 0), -6);
+    }
+
     #[test]
     fn foldmap_vec() {
         let a = vec![1, 2, 3, 4, 5];
@@ -0,0 +1,130 @@
+use std::convert::identity;
+
+use crate::{
+    semigroup::{Max, Min},
+    Foldable, Semigroup,
+};
+
+/// `Foldable1` is a [`Foldable`](Foldable) which is guaranteed to contain at
+/// least one value.
+///
+/// Because there's always a value to start from, folding a `Foldable1` only
+/// requires a [`Semigroup`](Semigroup) rather than a [`Monoid`](crate::Monoid),
+/// and [`foldr1`](Foldable1::foldr1) and [`foldl1`](Foldable1::foldl1) don't
+/// need an initial value.
+///
+/// ```
+/// # use higher::{Foldable1, semigroup::Max};
+/// let list = [Max(2), Max(31337), Max(5)];
+/// assert_eq!(list.fold1(), Max(31337));
+/// ```
+pub trait Foldable1<'a, A: 'a>: Foldable<'a, A> {
+    /// Map each value to a [`Semigroup`](Semigroup) and combine the results
+    /// from left to right.
+    fn fold_map1<S, F>(self, f: F) -> S
+    where
+        F: Fn(A) -> S + 'a,
+        S: Semigroup;
+
+    /// Fold from the right, using the last value as the initial accumulator.
+    fn foldr1<F>(self, f: F) -> A
+    where
+        F: Fn(A, A) -> A + 'a;
+
+    /// Fold from the left, using the first value as the initial accumulator.
+    fn foldl1<F>(self, f: F) -> A
+    where
+        F: Fn(A, A) -> A + 'a;
+
+    /// Combine all the values using their [`Semigroup`](Semigroup)
+    /// implementation.
+    fn fold1(self) -> A
+    where
+        Self: Sized,
+        A: Semigroup,
+    {
+        self.fold_map1(identity)
+    }
+
+    /// Find the largest value.
+    fn maximum(self) -> A
+    where
+        Self: Sized,
+        A: Ord,
+    {
+        self.fold_map1(Max).unwrap()
+    }
+
+    /// Find the smallest value.
+    fn minimum(self) -> A
+    where
+        Self: Sized,
+        A: Ord,
+    {
+        self.fold_map1(Min).unwrap()
+    }
+}
+
+struct AssertNonEmptyArray<const N: usize>;
+
+impl<const N: usize> AssertNonEmptyArray<N> {
+    const ASSERT: () = assert!(
+        N > 0,
+        "Foldable1 requires an array length greater than zero"
+    );
+}
+
+impl<'a, A: 'a, const N: usize> Foldable1<'a, A> for [A; N] {
+    fn fold_map1<S, F>(self, f: F) -> S
+    where
+        F: Fn(A) -> S + 'a,
+        S: Semigroup,
+    {
+        let () = AssertNonEmptyArray::<N>::ASSERT;
+        let mut iter = self.into_iter().map(f);
+        let first = iter.next().unwrap();
+        iter.fold(first, Semigroup::mappend)
+    }
+
+    fn foldr1<F>(self, f: F) -> A
+    where
+        F: Fn(A, A) -> A + 'a,
+    {
+        let () = AssertNonEmptyArray::<N>::ASSERT;
+        let mut iter = self.into_iter();
+        let last = iter.next_back().unwrap();
+        iter.rfold(last, |acc, a| f(a, acc))
+    }
+
+    fn foldl1<F>(self, f: F) -> A
+    where
+        F: Fn(A, A) -> A + 'a,
+    {
+        let () = AssertNonEmptyArray::<N>::ASSERT;
+        let mut iter = self.into_iter();
+        let first = iter.next().unwrap();
+        iter.fold(first, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        semigroup::{First, Last},
+        Foldable1,
+    };
+
+    #[test]
+    fn fold1_array() {
+        assert_eq!([First(1), First(2), First(3)].fold1(), First(1));
+        assert_eq!([Last(1), Last(2), Last(3)].fold1(), Last(3));
+        assert_eq!([3, 1, 2].maximum(), 3);
+        assert_eq!([3, 1, 2].minimum(), 1);
+    }
+
+    #[test]
+    fn foldr1_foldl1_array() {
+        assert_eq!([1, 2, 3, 4].foldl1(|acc, next| acc - next), -8);
+        assert_eq!([1, 2, 3, 4].foldr1(|next, acc| next - acc), -2);
+    }
+}
@@ -0,0 +1,430 @@
+use std::{
+    collections::{LinkedList, VecDeque},
+    rc::Rc,
+};
+
+use crate::{apply::ApplyFn, Apply, Bind, Functor, FunctorRef, Pure};
+
+/// A `FreeFunctor` is a [`Functor`](Functor) which can be used as the
+/// instruction set of a [`Free`](Free) monad.
+///
+/// A [`Free`](Free) monad needs to map over its instructions with any result
+/// type, but Rust has no way to say that mapping over `F::Target<T>` produces
+/// an `F::Target<U>` for an arbitrary functor `F`, so this trait provides a
+/// version of [`fmap`](Functor::fmap) which operates on the whole family of
+/// [`Target`](Functor::Target) types at once. For the same reason,
+/// [`Compose`](crate::compose::Compose) needs it to map over its outer
+/// functor, and every [`InnerMonad`](crate::transformers::InnerMonad) of a
+/// monad transformer must implement it.
+///
+/// You shouldn't normally have to implement this yourself:
+/// [`#[derive(Functor)]`](macro@crate::Functor) implements it for you.
+pub trait FreeFunctor<'a, A: 'a>: Functor<'a, A, Target<A> = Self> {
+    /// Map a function over a functor of any type in the family.
+    fn fmap_target<T: 'a, U: 'a, F>(fa: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> U + 'a;
+}
+
+/// The by-reference counterpart to [`FreeFunctor`](FreeFunctor), in the same
+/// way as [`FunctorRef`](FunctorRef) is to [`Functor`](Functor): a version of
+/// [`fmap_ref`](FunctorRef::fmap_ref) which operates on the whole family of
+/// [`Target`](Functor::Target) types at once.
+pub trait FreeFunctorRef<'a, A: 'a>: FreeFunctor<'a, A> + FunctorRef<'a, A> {
+    /// Map a function over a reference to a functor of any type in the
+    /// family.
+    fn fmap_ref_target<T: 'a, U: 'a, F>(fa: &Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(&T) -> U + 'a;
+}
+
+impl<'a, A: 'a> FreeFunctor<'a, A> for Option<A> {
+    fn fmap_target<T: 'a, U: 'a, F>(fa: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> U + 'a,
+    {
+        fa.map(f)
+    }
+}
+
+impl<'a, A: 'a> FreeFunctorRef<'a, A> for Option<A> {
+    fn fmap_ref_target<T: 'a, U: 'a, F>(fa: &Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(&T) -> U + 'a,
+    {
+        fa.as_ref().map(f)
+    }
+}
+
+impl<'a, A: 'a, E> FreeFunctor<'a, A> for Result<A, E> {
+    fn fmap_target<T: 'a, U: 'a, F>(fa: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> U + 'a,
+    {
+        fa.map(f)
+    }
+}
+
+macro_rules! impl_free_functor_for_list {
+    ($type:ident) => {
+        impl<'a, A: 'a> FreeFunctor<'a, A> for $type<A> {
+            fn fmap_target<T: 'a, U: 'a, F>(fa: Self::Target<T>, f: F) -> Self::Target<U>
+            where
+                F: Fn(T) -> U + 'a,
+            {
+                fa.into_iter().map(f).collect()
+            }
+        }
+
+        impl<'a, A: 'a> FreeFunctorRef<'a, A> for $type<A> {
+            fn fmap_ref_target<T: 'a, U: 'a, F>(fa: &Self::Target<T>, f: F) -> Self::Target<U>
+            where
+                F: Fn(&T) -> U + 'a,
+            {
+                fa.iter().map(f).collect()
+            }
+        }
+    };
+}
+
+impl_free_functor_for_list!(Vec);
+impl_free_functor_for_list!(VecDeque);
+impl_free_functor_for_list!(LinkedList);
+
+/// A `Free` monad.
+///
+/// A `Free` monad turns any [`Functor`](Functor) `F` into a monad, letting
+/// you describe a program as a data structure made of the instructions in `F`,
+/// which can then be interpreted into any other monad using
+/// [`fold_free`](Free::fold_free), or stepped through one instruction at a
+/// time using [`resume`](Free::resume). This way, you can run the same program
+/// against a test interpreter and a real one.
+///
+/// The type parameter `F` is the instruction functor applied to `()`, so that
+/// we can get at the rest of its types through [`Functor::Target`]. The
+/// instruction functor must implement [`FreeFunctor`](FreeFunctor), which
+/// [`#[derive(Functor)]`](macro@crate::Functor) will take care of.
+///
+/// Like [`State`](crate::State), a `Free` is a computation which can be run
+/// more than once, so [`Pure`](Pure) requires the result value to implement
+/// [`Clone`](Clone).
+///
+/// ```
+/// # use higher::{run, free::Free, writer::Writer, Functor};
+/// #[derive(Clone, Functor)]
+/// enum Console<A> {
+///     Say(String, A),
+/// }
+///
+/// fn say<'a>(message: &str) -> Free<'a, Console<()>, ()> {
+///     Free::lift_f(Console::Say(message.to_string(), ()))
+/// }
+///
+/// let program = run! {
+///     say("Hello");
+///     say("Joe");
+///     yield 5
+/// };
+///
+/// let output: Writer<Vec<String>, u32> = program.fold_free(|instruction| match instruction {
+///     Console::Say(message, next) => Writer::new(next, vec![message]),
+/// });
+/// assert_eq!(
+///     output.run_writer(),
+///     (5, vec!["Hello".to_string(), "Joe".to_string()])
+/// );
+/// ```
+pub struct Free<'a, F, A>
+where
+    F: Functor<'a, ()> + 'a,
+    A: 'a,
+{
+    run: Rc<dyn Fn() -> Result<A, F::Target<Free<'a, F, A>>> + 'a>,
+}
+
+impl<'a, F, A> Free<'a, F, A>
+where
+    F: FreeFunctor<'a, ()> + 'a,
+    A: 'a,
+{
+    fn new<R>(f: R) -> Self
+    where
+        R: Fn() -> Result<A, F::Target<Self>> + 'a,
+    {
+        Self { run: Rc::new(f) }
+    }
+
+    /// Lift a single instruction into a `Free` monad.
+    pub fn lift_f(instruction: F::Target<A>) -> Self
+    where
+        A: Clone,
+        F::Target<A>: Clone,
+    {
+        Self::new(move || {
+            Err(F::fmap_target::<A, Self, _>(
+                instruction.clone(),
+                Self::pure,
+            ))
+        })
+    }
+
+    /// Run the program up to its first instruction.
+    ///
+    /// If the program has finished, this returns [`Ok`](Result::Ok) with its
+    /// result. Otherwise, it returns [`Err`](Result::Err) with the next
+    /// instruction, which contains the rest of the program.
+    pub fn resume(&self) -> Result<A, F::Target<Self>> {
+        (self.run)()
+    }
+
+    /// Interpret the program into another monad `M`.
+    ///
+    /// The interpreter is a function which translates a single instruction
+    /// into a computation in `M`, which produces the rest of the program.
+    pub fn fold_free<M, I>(&self, interpret: I) -> M
+    where
+        M: Functor<'a, A> + Pure<A> + 'a,
+        M::Target<Self>: Bind<'a, Self, Target<A> = M>,
+        I: Fn(F::Target<Self>) -> M::Target<Self> + 'a,
+    {
+        self.fold_rc(Rc::new(interpret))
+    }
+
+    fn fold_rc<M>(&self, interpret: Rc<dyn Fn(F::Target<Self>) -> M::Target<Self> + 'a>) -> M
+    where
+        M: Functor<'a, A> + Pure<A> + 'a,
+        M::Target<Self>: Bind<'a, Self, Target<A> = M>,
+    {
+        match self.resume() {
+            Ok(value) => M::pure(value),
+            Err(instruction) => {
+                let step = interpret(instruction);
+                step.bind::<A, _>(move |next: Self| next.fold_rc(interpret.clone()))
+            }
+        }
+    }
+
+    fn fmap_rc<B: 'a>(self, f: Rc<dyn Fn(A) -> B + 'a>) -> Free<'a, F, B> {
+        Free::new(move || match self.resume() {
+            Ok(value) => Ok(f(value)),
+            Err(instruction) => {
+                let f = f.clone();
+                Err(F::fmap_target::<Self, Free<'a, F, B>, _>(
+                    instruction,
+                    move |next| next.fmap_rc(f.clone()),
+                ))
+            }
+        })
+    }
+
+    fn bind_rc<B: 'a>(self, f: Rc<dyn Fn(A) -> Free<'a, F, B> + 'a>) -> Free<'a, F, B> {
+        Free::new(move || match self.resume() {
+            Ok(value) => f(value).resume(),
+            Err(instruction) => {
+                let f = f.clone();
+                Err(F::fmap_target::<Self, Free<'a, F, B>, _>(
+                    instruction,
+                    move |next| next.bind_rc(f.clone()),
+                ))
+            }
+        })
+    }
+}
+
+impl<'a, F, A> Clone for Free<'a, F, A>
+where
+    F: Functor<'a, ()> + 'a,
+    A: 'a,
+{
+    fn clone(&self) -> Self {
+        Self {
+            run: self.run.clone(),
+        }
+    }
+}
+
+impl<'a, F, A> std::fmt::Debug for Free<'a, F, A>
+where
+    F: Functor<'a, ()> + 'a,
+    A: 'a,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Free<{}, {}>",
+            std::any::type_name::<F>(),
+            std::any::type_name::<A>()
+        )
+    }
+}
+
+impl<'a, F, A> Functor<'a, A> for Free<'a, F, A>
+where
+    F: FreeFunctor<'a, ()> + 'a,
+    A: 'a,
+{
+    type Target<T: 'a> = Free<'a, F, T>;
+
+    fn fmap<B, G>(self, f: G) -> Self::Target<B>
+    where
+        B: 'a,
+        G: Fn(A) -> B + 'a,
+    {
+        self.fmap_rc(Rc::new(f))
+    }
+}
+
+impl<'a, F, A> Pure<A> for Free<'a, F, A>
+where
+    F: FreeFunctor<'a, ()> + 'a,
+    A: Clone + 'a,
+{
+    fn pure(value: A) -> Self {
+        Self::new(move || Ok(value.clone()))
+    }
+}
+
+impl<'a, F, A> Apply<'a, A> for Free<'a, F, A>
+where
+    F: FreeFunctor<'a, ()> + 'a,
+    A: 'a,
+{
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        f.bind_rc(Rc::new(move |func: ApplyFn<'a, A, B>| {
+            self.clone().fmap(move |value| func.apply_fn(value))
+        }))
+    }
+}
+
+impl<'a, F, A> Bind<'a, A> for Free<'a, F, A>
+where
+    F: FreeFunctor<'a, ()> + 'a,
+    A: 'a,
+{
+    fn bind<B, G>(self, f: G) -> Self::Target<B>
+    where
+        B: 'a,
+        G: Fn(A) -> Self::Target<B> + 'a,
+    {
+        self.bind_rc(Rc::new(f))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use super::{Free, FreeFunctor};
+    use crate::{run, Functor, State};
+
+    #[derive(Clone)]
+    enum Teletype<'a, A> {
+        Print(String, A),
+        Read(Rc<dyn Fn(String) -> A + 'a>),
+    }
+
+    impl<'a, A: 'a> Functor<'a, A> for Teletype<'a, A> {
+        type Target<T: 'a> = Teletype<'a, T>;
+
+        fn fmap<B, F>(self, f: F) -> Self::Target<B>
+        where
+            B: 'a,
+            F: Fn(A) -> B + 'a,
+        {
+            match self {
+                Teletype::Print(line, next) => Teletype::Print(line, f(next)),
+                Teletype::Read(next) => Teletype::Read(Rc::new(move |line| f(next(line)))),
+            }
+        }
+    }
+
+    impl<'a, A: 'a> FreeFunctor<'a, A> for Teletype<'a, A> {
+        fn fmap_target<T: 'a, U: 'a, F>(fa: Self::Target<T>, f: F) -> Self::Target<U>
+        where
+            F: Fn(T) -> U + 'a,
+        {
+            fa.fmap(f)
+        }
+    }
+
+    type Program<'a, A> = Free<'a, Teletype<'a, ()>, A>;
+
+    fn print<'a>(line: &str) -> Program<'a, ()> {
+        Free::lift_f(Teletype::Print(line.to_string(), ()))
+    }
+
+    fn read<'a>() -> Program<'a, String> {
+        Free::lift_f(Teletype::Read(Rc::new(|line| line)))
+    }
+
+    fn echo<'a>() -> Program<'a, usize> {
+        run! {
+            line <= read();
+            print(&line);
+            yield line.len()
+        }
+    }
+
+    #[test]
+    fn resume_steps() {
+        let program = echo();
+        let next = match program.resume() {
+            Err(Teletype::Read(next)) => next("hello".to_string()),
+            _ => panic!("expected a Read instruction"),
+        };
+        let next = match next.resume() {
+            Err(Teletype::Print(line, next)) => {
+                assert_eq!(line, "hello");
+                next
+            }
+            _ => panic!("expected a Print instruction"),
+        };
+        assert_eq!(next.resume().ok(), Some(5));
+    }
+
+    #[test]
+    fn fold_into_state() {
+        let program = run! {
+            a <= echo();
+            b <= echo();
+            yield a + b
+        };
+        let result: State<'_, (Vec<String>, Vec<String>), usize> =
+            program.fold_free(|instruction| match instruction {
+                Teletype::Print(line, next) => {
+                    State::new(move |(input, mut output): (Vec<String>, Vec<String>)| {
+                        output.push(line.clone());
+                        (next.clone(), (input, output))
+                    })
+                }
+                Teletype::Read(next) => {
+                    State::new(move |(mut input, output): (Vec<String>, Vec<String>)| {
+                        let line = input.remove(0);
+                        (next(line), (input, output))
+                    })
+                }
+            });
+        let input = vec!["hello".to_string(), "Joe".to_string()];
+        assert_eq!(
+            result.run_state((input, vec![])),
+            (8, (vec![], vec!["hello".to_string(), "Joe".to_string()]))
+        );
+    }
+
+    #[test]
+    fn functor_and_apply() {
+        let program: Program<'_, usize> = echo().fmap(|len| len * 2);
+        let result: State<'_, Vec<String>, usize> =
+            program.fold_free(|instruction| match instruction {
+                Teletype::Print(_, next) => State::new(move |input| (next.clone(), input)),
+                Teletype::Read(next) => State::new(move |mut input: Vec<String>| {
+                    let line = input.remove(0);
+                    (next(line), input)
+                }),
+            });
+        assert_eq!(result.eval_state(vec!["four".to_string()]), 8);
+    }
+}
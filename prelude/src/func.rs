@@ -0,0 +1,133 @@
+use std::rc::Rc;
+
+use crate::profunctor::{Choice, Closed, Profunctor, Strong};
+
+/// A boxed function from `A` to `B`.
+///
+/// Plain Rust closures each have their own type, so they can't be a
+/// [`Profunctor`](Profunctor) themselves. A `Func` erases the closure type,
+/// which gives the function arrow a type to hang instances on: it's the
+/// simplest [`Profunctor`](Profunctor), and it's [`Strong`](Strong),
+/// [`Choice`](Choice) and [`Closed`](Closed) too.
+///
+/// ```
+/// # use higher::{func::Func, profunctor::{Profunctor, Strong}};
+/// let length = Func::new(|s: String| s.len());
+/// let shout = length.dimap(|s: &str| s.to_uppercase(), |n| n * 2);
+/// assert_eq!(shout.call("hello"), 10);
+/// let paired = shout.first::<bool>();
+/// assert_eq!(paired.call(("hi", true)), (4, true));
+/// ```
+pub struct Func<'a, A, B>(Rc<dyn Fn(A) -> B + 'a>);
+
+impl<'a, A, B> Func<'a, A, B> {
+    /// Wrap a function.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(A) -> B + 'a,
+    {
+        Self(Rc::new(f))
+    }
+
+    /// Call the function.
+    pub fn call(&self, value: A) -> B {
+        (self.0)(value)
+    }
+
+    /// Compose this function with another, which runs on its result.
+    pub fn and_then<C>(self, next: Func<'a, B, C>) -> Func<'a, A, C>
+    where
+        A: 'a,
+        B: 'a,
+        C: 'a,
+    {
+        Func::new(move |value| next.call(self.call(value)))
+    }
+}
+
+impl<'a, A, B, F> From<F> for Func<'a, A, B>
+where
+    F: Fn(A) -> B + 'a,
+{
+    fn from(f: F) -> Self {
+        Self::new(f)
+    }
+}
+
+impl<'a, A, B> Clone for Func<'a, A, B> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<'a, A, B> std::fmt::Debug for Func<'a, A, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Func<{}, {}>",
+            std::any::type_name::<A>(),
+            std::any::type_name::<B>()
+        )
+    }
+}
+
+impl<'a, A: 'a, B: 'a> Profunctor<'a, A, B> for Func<'a, A, B> {
+    type Target<T: 'a, U: 'a> = Func<'a, T, U>;
+
+    fn dimap<C: 'a, D: 'a, L, R>(self, left: L, right: R) -> Self::Target<C, D>
+    where
+        L: Fn(C) -> A + 'a,
+        R: Fn(B) -> D + 'a,
+    {
+        Func::new(move |value| right(self.call(left(value))))
+    }
+}
+
+impl<'a, A: 'a, B: 'a> Strong<'a, A, B> for Func<'a, A, B> {
+    fn first<C: Clone + 'a>(self) -> Self::Target<(A, C), (B, C)> {
+        Func::new(move |(a, c)| (self.call(a), c))
+    }
+
+    fn second<C: Clone + 'a>(self) -> Self::Target<(C, A), (C, B)> {
+        Func::new(move |(c, a)| (c, self.call(a)))
+    }
+}
+
+impl<'a, A: 'a, B: 'a> Choice<'a, A, B> for Func<'a, A, B> {
+    fn left<C: Clone + 'a>(self) -> Self::Target<Result<A, C>, Result<B, C>> {
+        Func::new(move |value: Result<A, C>| value.map(|a| self.call(a)))
+    }
+
+    fn right<C: Clone + 'a>(self) -> Self::Target<Result<C, A>, Result<C, B>> {
+        Func::new(move |value: Result<C, A>| value.map_err(|a| self.call(a)))
+    }
+}
+
+impl<'a, A: 'a, B: 'a> Closed<'a, A, B> for Func<'a, A, B> {
+    fn closed<X: Clone + 'a>(self) -> Self::Target<Func<'a, X, A>, Func<'a, X, B>> {
+        Func::new(move |g: Func<'a, X, A>| g.and_then(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Func;
+    use crate::profunctor::{Choice, Closed, Profunctor, Strong};
+
+    #[test]
+    fn profunctor_instances() {
+        let double = Func::new(|x: i32| x * 2);
+        assert_eq!(
+            double.clone().lcmap(|s: &str| s.len() as i32).call("abc"),
+            6
+        );
+        assert_eq!(double.clone().second::<char>().call(('x', 4)), ('x', 8));
+        assert_eq!(double.clone().left::<&str>().call(Ok(3)), Ok(6));
+        assert_eq!(double.clone().left().call(Err("no")), Err("no"));
+        assert_eq!(double.clone().right::<()>().call(Err(5)), Err(10));
+
+        let closed = double.closed::<String>();
+        let composed = closed.call(Func::new(|s: String| s.len() as i32));
+        assert_eq!(composed.call("four".to_string()), 8);
+    }
+}
@@ -1,8 +1,10 @@
 use std::{
     cell::RefCell,
-    collections::{LinkedList, VecDeque},
+    collections::{BTreeMap, HashMap, LinkedList, VecDeque},
+    hash::Hash,
     mem::MaybeUninit,
     rc::Rc,
+    task::Poll,
 };
 
 use crate::repeat;
@@ -25,9 +27,9 @@ pub trait Functor<'a, A: 'a> {
 
     /// Map a functor of `A` to a functor of `B` using a function from `A`
     /// to `B`.
-    fn fmap<B: 'a, F: 'a>(self, f: F) -> Self::Target<B>
+    fn fmap<B: 'a, F>(self, f: F) -> Self::Target<B>
     where
-        F: Fn(A) -> B;
+        F: Fn(A) -> B + 'a;
 
     /// Map the functor to the provided constant value.
     fn fconst<B>(self, b: B) -> Self::Target<B>
@@ -86,9 +88,9 @@ pub trait Functor<'a, A: 'a> {
 pub trait FunctorRef<'a, A: 'a>: Functor<'a, A> {
     /// Map a functor of `A` to a functor of `B` using a function from `&A` to
     /// `B`.
-    fn fmap_ref<B: 'a, F: 'a>(&self, f: F) -> Self::Target<B>
+    fn fmap_ref<B: 'a, F>(&self, f: F) -> Self::Target<B>
     where
-        F: Fn(&A) -> B;
+        F: Fn(&A) -> B + 'a;
 
     /// Given a type `A` implementing [`Clone`](Clone), create a new identical
     /// `FunctorRef<A>` by cloning the values inside `self`.
@@ -145,6 +147,93 @@ impl<'a, A: 'a, E> Functor<'a, A> for Result<A, E> {
     }
 }
 
+impl<'a, E, A: 'a> Functor<'a, A> for (E, A) {
+    type Target<T: 'a> = (E, T);
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B,
+    {
+        (self.0, f(self.1))
+    }
+}
+
+impl<'a, E: Clone, A: 'a> FunctorRef<'a, A> for (E, A) {
+    fn fmap_ref<B: 'a, F>(&self, f: F) -> Self::Target<B>
+    where
+        F: Fn(&A) -> B,
+    {
+        (self.0.clone(), f(&self.1))
+    }
+
+    fn fclone(&self) -> Self
+    where
+        A: Clone,
+    {
+        self.clone()
+    }
+}
+
+impl<'a, A: 'a> Functor<'a, A> for Box<A> {
+    type Target<T: 'a> = Box<T>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B,
+    {
+        Box::new(f(*self))
+    }
+}
+
+impl<'a, A: 'a> FunctorRef<'a, A> for Box<A> {
+    fn fmap_ref<B: 'a, F>(&self, f: F) -> Self::Target<B>
+    where
+        F: Fn(&A) -> B,
+    {
+        Box::new(f(self))
+    }
+
+    fn fclone(&self) -> Self
+    where
+        A: Clone,
+    {
+        self.clone()
+    }
+}
+
+impl<'a, A: 'a> Functor<'a, A> for Poll<A> {
+    type Target<T: 'a> = Poll<T>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B,
+    {
+        self.map(f)
+    }
+}
+
+impl<'a, A: 'a> FunctorRef<'a, A> for Poll<A> {
+    fn fmap_ref<B: 'a, F>(&self, f: F) -> Self::Target<B>
+    where
+        F: Fn(&A) -> B,
+    {
+        match self {
+            Poll::Ready(value) => Poll::Ready(f(value)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn fclone(&self) -> Self
+    where
+        A: Clone,
+    {
+        self.clone()
+    }
+}
+
 impl<'a, A: 'a, const N: usize> Functor<'a, A> for [A; N] {
     type Target<T: 'a> = [T; N];
 
@@ -228,6 +317,43 @@ impl_functor_for_collection!(Vec);
 impl_functor_for_collection!(VecDeque);
 impl_functor_for_collection!(LinkedList);
 
+macro_rules! impl_functor_for_map {
+    ($type:ident, $($key_bound:tt)*) => {
+        impl<'a, K: $($key_bound)*, V: 'a> Functor<'a, V> for $type<K, V> {
+            type Target<T: 'a> = $type<K, T>;
+
+            fn fmap<B, F>(self, f: F) -> Self::Target<B>
+            where
+                B: 'a,
+                F: Fn(V) -> B,
+            {
+                self.into_iter().map(|(key, value)| (key, f(value))).collect()
+            }
+        }
+
+        impl<'a, K: $($key_bound)* + Clone, V: 'a> FunctorRef<'a, V> for $type<K, V> {
+            fn fmap_ref<B: 'a, F>(&self, f: F) -> Self::Target<B>
+            where
+                F: Fn(&V) -> B,
+            {
+                self.iter()
+                    .map(|(key, value)| (key.clone(), f(value)))
+                    .collect()
+            }
+
+            fn fclone(&self) -> Self
+            where
+                V: Clone,
+            {
+                self.clone()
+            }
+        }
+    };
+}
+
+impl_functor_for_map!(HashMap, Hash + Eq);
+impl_functor_for_map!(BTreeMap, Ord);
+
 #[cfg(test)]
 mod test {
     use crate::Functor;
@@ -249,7 +375,7 @@ mod test {
     #[test]
     fn array_exofunctor() {
         let a: [u64; 5] = [1, 2, 3, 4, 5];
-        let b = a.fmap(|x| ((x * 2) as u16));
+        let b = a.fmap(|x| (x * 2) as u16);
         assert_eq!(b, [2, 4, 6, 8, 10]);
     }
 
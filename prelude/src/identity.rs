@@ -0,0 +1,198 @@
+use crate::{
+    apply::ApplyFn,
+    free::{FreeFunctor, FreeFunctorRef},
+    Applicative, Apply, Bind, Comonad, Extend, Foldable, Functor, FunctorRef, Monoid, Pure,
+    Traversable,
+};
+
+/// The `Identity` functor.
+///
+/// This is a plain value with no extra context at all, which is useful
+/// whenever generic code needs a [`Functor`](Functor) or a
+/// [`Monad`](crate::Monad) but you don't want any effects.
+///
+/// ```
+/// # use higher::{identity::Identity, Comonad, Functor};
+/// let value = Identity(20).fmap(|x| x * 2 + 2);
+/// assert_eq!(value.extract(), 42);
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Identity<A>(pub A);
+
+impl<A> Identity<A> {
+    /// Get the wrapped value.
+    pub fn run_identity(self) -> A {
+        self.0
+    }
+}
+
+impl<'a, A: 'a> Functor<'a, A> for Identity<A> {
+    type Target<T: 'a> = Identity<T>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        Identity(f(self.0))
+    }
+}
+
+impl<'a, A: 'a> FunctorRef<'a, A> for Identity<A> {
+    fn fmap_ref<B, F>(&self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(&A) -> B + 'a,
+    {
+        Identity(f(&self.0))
+    }
+}
+
+impl<'a, A: 'a> FreeFunctor<'a, A> for Identity<A> {
+    fn fmap_target<T: 'a, U: 'a, F>(fa: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> U + 'a,
+    {
+        fa.fmap(f)
+    }
+}
+
+impl<'a, A: 'a> FreeFunctorRef<'a, A> for Identity<A> {
+    fn fmap_ref_target<T: 'a, U: 'a, F>(fa: &Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(&T) -> U + 'a,
+    {
+        Identity(f(&fa.0))
+    }
+}
+
+impl<A> Pure<A> for Identity<A> {
+    fn pure(value: A) -> Self {
+        Identity(value)
+    }
+}
+
+impl<'a, A: 'a> Apply<'a, A> for Identity<A> {
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        Identity(f.0.apply_fn(self.0))
+    }
+}
+
+impl<'a, A: 'a> Bind<'a, A> for Identity<A> {
+    fn bind<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> Self::Target<B> + 'a,
+    {
+        f(self.0)
+    }
+}
+
+impl<'a, A: 'a> Foldable<'a, A> for Identity<A> {
+    fn foldr<B, F>(self, f: F, init: B) -> B
+    where
+        B: 'a,
+        F: Fn(A, B) -> B + 'a,
+    {
+        f(self.0, init)
+    }
+
+    fn foldr_ref<B, F>(&'a self, f: F, init: B) -> B
+    where
+        B: 'a,
+        F: Fn(&'a A, B) -> B + 'a,
+    {
+        f(&self.0, init)
+    }
+
+    fn foldl<B, F>(self, f: F, init: B) -> B
+    where
+        B: 'a,
+        F: Fn(B, A) -> B + 'a,
+    {
+        f(init, self.0)
+    }
+
+    fn foldl_ref<B, F>(&'a self, f: F, init: B) -> B
+    where
+        B: 'a,
+        F: Fn(B, &'a A) -> B + 'a,
+    {
+        f(init, &self.0)
+    }
+
+    fn fold_map<F, M>(self, f: F) -> M
+    where
+        F: Fn(A) -> M + 'a,
+        M: Monoid,
+    {
+        f(self.0)
+    }
+
+    fn fold_map_ref<F, M>(&'a self, f: F) -> M
+    where
+        F: Fn(&'a A) -> M + 'a,
+        M: Monoid,
+    {
+        f(&self.0)
+    }
+}
+
+impl<'a, A: 'a> Traversable<'a, A> for Identity<A> {
+    fn traverse<B: 'a, M, F>(self, f: F) -> M::Target<Self::Target<B>>
+    where
+        M: Applicative<'a, B>,
+        M::Target<Self::Target<B>>: Applicative<'a, Self::Target<B>>,
+        F: Fn(A) -> M + 'a,
+    {
+        f(self.0).fmap(Identity)
+    }
+}
+
+impl<'a, A: 'a> Extend<'a, A> for Identity<A> {
+    fn extend<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(Self) -> B + 'a,
+    {
+        Identity(f(self))
+    }
+}
+
+impl<'a, A: 'a> Comonad<'a, A> for Identity<A> {
+    fn extract(self) -> A {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Identity;
+    use crate::{run, Comonad, Extend, Traversable};
+
+    #[test]
+    fn comonad_laws() {
+        let w = Identity(5);
+        let f = |w: Identity<i32>| w.0 * 2;
+        let g = |w: Identity<i32>| w.0 + 1;
+        assert_eq!(w.extend(|w| w.extract()), w);
+        assert_eq!(w.extend(f).extract(), f(w));
+        assert_eq!(w.extend(f).extend(g), w.extend(|w| g(w.extend(f))));
+        assert_eq!(w.duplicate(), Identity(w));
+    }
+
+    #[test]
+    fn monad_and_traverse() {
+        let value = run! {
+            x <= Identity(20);
+            y <= Identity(x * 2);
+            yield y + 2
+        };
+        assert_eq!(value, Identity(42));
+        assert_eq!(Identity(5).traverse(|x| Some(x + 1)), Some(Identity(6)));
+        assert_eq!(Identity(Some(5)).sequence::<i32>(), Some(Identity(5)));
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{
+    free::FreeFunctor,
+    profunctor::{Choice, Profunctor, Strong},
+    star::Star,
+    Bind, Functor, Pure,
+};
+
+/// A monadic function from `A` into a [`Bind`](Bind) `M` of `B`.
+///
+/// A `Kleisli` is a [`Star`](Star) whose functor is also a monad, which
+/// means `Kleisli`s can be chained together with
+/// [`and_then`](Kleisli::and_then), just like plain functions can.
+///
+/// ```
+/// # use higher::{kleisli::Kleisli, profunctor::Profunctor};
+/// let parse = Kleisli::new(|s: &str| s.parse::<u32>().ok());
+/// let half = Kleisli::new(|x: u32| if x % 2 == 0 { Some(x / 2) } else { None });
+/// let parse_half = parse.and_then(half).rmap(|x| x + 1);
+/// assert_eq!(parse_half.run("8"), Some(5));
+/// assert_eq!(parse_half.run("7"), None);
+/// ```
+pub struct Kleisli<'a, M, A, B>(Star<'a, M, A, B>);
+
+impl<'a, M, A, B> Kleisli<'a, M, A, B> {
+    /// Wrap a monadic function.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(A) -> M + 'a,
+    {
+        Self(Star::new(f))
+    }
+
+    /// Run the function.
+    pub fn run(&self, value: A) -> M {
+        self.0.run(value)
+    }
+
+    /// Get the underlying [`Star`](Star).
+    pub fn into_star(self) -> Star<'a, M, A, B> {
+        self.0
+    }
+}
+
+impl<'a, M, A, B> From<Star<'a, M, A, B>> for Kleisli<'a, M, A, B> {
+    fn from(star: Star<'a, M, A, B>) -> Self {
+        Self(star)
+    }
+}
+
+impl<'a, M, A, B> Kleisli<'a, M, A, B>
+where
+    M: Bind<'a, B> + 'a,
+    A: 'a,
+    B: 'a,
+{
+    /// Compose this function with another, which is bound to its result.
+    pub fn and_then<C: 'a>(
+        self,
+        next: Kleisli<'a, M::Target<C>, B, C>,
+    ) -> Kleisli<'a, M::Target<C>, A, C> {
+        Kleisli::new(move |value| {
+            let next = next.clone();
+            self.run(value).bind(move |b| next.run(b))
+        })
+    }
+}
+
+impl<'a, M, A, B> Clone for Kleisli<'a, M, A, B> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<'a, M, A, B> std::fmt::Debug for Kleisli<'a, M, A, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Kleisli<{}, {}>",
+            std::any::type_name::<A>(),
+            std::any::type_name::<M>()
+        )
+    }
+}
+
+impl<'a, M, A, B> Profunctor<'a, A, B> for Kleisli<'a, M, A, B>
+where
+    M: Functor<'a, B> + 'a,
+    A: 'a,
+    B: 'a,
+{
+    type Target<T: 'a, U: 'a> = Kleisli<'a, M::Target<U>, T, U>;
+
+    fn dimap<C: 'a, D: 'a, L, R>(self, left: L, right: R) -> Self::Target<C, D>
+    where
+        L: Fn(C) -> A + 'a,
+        R: Fn(B) -> D + 'a,
+    {
+        Kleisli(self.0.dimap(left, right))
+    }
+}
+
+impl<'a, M, A, B> Strong<'a, A, B> for Kleisli<'a, M, A, B>
+where
+    M: Functor<'a, B> + 'a,
+    A: 'a,
+    B: 'a,
+{
+    fn first<C: Clone + 'a>(self) -> Self::Target<(A, C), (B, C)> {
+        Kleisli(self.0.first())
+    }
+
+    fn second<C: Clone + 'a>(self) -> Self::Target<(C, A), (C, B)> {
+        Kleisli(self.0.second())
+    }
+}
+
+impl<'a, M, A, B> Choice<'a, A, B> for Kleisli<'a, M, A, B>
+where
+    M: FreeFunctor<'a, B> + 'a,
+    M::Target<()>: Pure<()>,
+    A: 'a,
+    B: 'a,
+{
+    fn left<C: Clone + 'a>(self) -> Self::Target<Result<A, C>, Result<B, C>> {
+        Kleisli(self.0.left())
+    }
+
+    fn right<C: Clone + 'a>(self) -> Self::Target<Result<C, A>, Result<C, B>> {
+        Kleisli(self.0.right())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Kleisli;
+    use crate::profunctor::{Choice, Profunctor, Strong};
+
+    #[test]
+    fn chains_and_lifts() {
+        let spread = Kleisli::new(|x: i32| vec![x, x + 1]);
+        let twice = spread.clone().and_then(spread.clone());
+        assert_eq!(twice.run(0), vec![0, 1, 1, 2]);
+        assert_eq!(
+            spread.clone().second::<bool>().run((true, 3)),
+            vec![(true, 3), (true, 4)]
+        );
+        assert_eq!(spread.clone().left::<()>().run(Err(())), vec![Err(())]);
+
+        let tens = Kleisli::from(spread.into_star().rmap(|x| x * 10));
+        assert_eq!(tens.run(1), vec![10, 20]);
+    }
+}
@@ -0,0 +1,450 @@
+use std::fmt::Debug;
+
+use crate::{
+    algebras::{BooleanAlgebra, HeytingAlgebra},
+    rings::{EuclideanRing, Ring, Semiring},
+    Monoid, Semigroup,
+};
+
+use super::{law, Arbitrary, Equivalent, Rng, CASES};
+
+/// Check the [`Semigroup`](Semigroup) law for `A`:
+///
+/// * Associativity: `a.mappend(b).mappend(c) == a.mappend(b.mappend(c))`
+pub fn check_semigroup<A>(rng: &mut Rng)
+where
+    A: Semigroup + Arbitrary + Clone + Equivalent + Debug,
+{
+    for _ in 0..CASES {
+        let (a, b, c): (A, A, A) = rng.arbitrary();
+        law!(
+            "Semigroup associativity",
+            a.clone().mappend(b.clone()).mappend(c.clone()),
+            a.clone().mappend(b.clone().mappend(c.clone())),
+            a,
+            b,
+            c
+        );
+    }
+}
+
+/// Check the [`Monoid`](Monoid) laws for `A`, in addition to the
+/// [`Semigroup`](Semigroup) law checked by
+/// [`check_semigroup`](check_semigroup):
+///
+/// * Left identity: `A::default().mappend(a) == a`
+/// * Right identity: `a.mappend(A::default()) == a`
+pub fn check_monoid<A>(rng: &mut Rng)
+where
+    A: Monoid + Arbitrary + Clone + Equivalent + Debug,
+{
+    check_semigroup::<A>(rng);
+    for _ in 0..CASES {
+        let a: A = rng.arbitrary();
+        law!(
+            "Monoid left identity",
+            A::default().mappend(a.clone()),
+            a.clone(),
+            a
+        );
+        law!(
+            "Monoid right identity",
+            a.clone().mappend(A::default()),
+            a.clone(),
+            a
+        );
+    }
+}
+
+/// Check the [`Semiring`](Semiring) laws for `A`:
+///
+/// * Addition is associative and commutative, with `ZERO` as its identity.
+/// * Multiplication is associative, with `ONE` as its identity.
+/// * Multiplication distributes over addition, on both sides.
+/// * Annihilation: `ZERO.mul(a) == a.mul(ZERO) == ZERO`
+pub fn check_semiring<A>(rng: &mut Rng)
+where
+    A: Semiring + Arbitrary + Clone + Equivalent + Debug,
+{
+    for _ in 0..CASES {
+        let (a, b, c): (A, A, A) = rng.arbitrary();
+        law!(
+            "Semiring additive associativity",
+            a.clone().add(b.clone()).add(c.clone()),
+            a.clone().add(b.clone().add(c.clone())),
+            a,
+            b,
+            c
+        );
+        law!(
+            "Semiring additive identity",
+            A::ZERO.add(a.clone()),
+            a.clone().add(A::ZERO),
+            a
+        );
+        law!(
+            "Semiring additive identity",
+            A::ZERO.add(a.clone()),
+            a.clone(),
+            a
+        );
+        law!(
+            "Semiring additive commutativity",
+            a.clone().add(b.clone()),
+            b.clone().add(a.clone()),
+            a,
+            b
+        );
+        law!(
+            "Semiring multiplicative associativity",
+            a.clone().mul(b.clone()).mul(c.clone()),
+            a.clone().mul(b.clone().mul(c.clone())),
+            a,
+            b,
+            c
+        );
+        law!(
+            "Semiring multiplicative identity",
+            A::ONE.mul(a.clone()),
+            a.clone().mul(A::ONE),
+            a
+        );
+        law!(
+            "Semiring multiplicative identity",
+            A::ONE.mul(a.clone()),
+            a.clone(),
+            a
+        );
+        law!(
+            "Semiring left distributivity",
+            a.clone().mul(b.clone().add(c.clone())),
+            a.clone().mul(b.clone()).add(a.clone().mul(c.clone())),
+            a,
+            b,
+            c
+        );
+        law!(
+            "Semiring right distributivity",
+            a.clone().add(b.clone()).mul(c.clone()),
+            a.clone().mul(c.clone()).add(b.clone().mul(c.clone())),
+            a,
+            b,
+            c
+        );
+        law!("Semiring annihilation", A::ZERO.mul(a.clone()), A::ZERO, a);
+        law!("Semiring annihilation", a.clone().mul(A::ZERO), A::ZERO, a);
+    }
+}
+
+/// Check the [`Ring`](Ring) laws for `A`, in addition to the
+/// [`Semiring`](Semiring) laws checked by [`check_semiring`](check_semiring):
+///
+/// * Additive inverse: `a.sub(a) == ZERO`
+/// * Subtraction undoes addition: `a.add(b).sub(b) == a`
+pub fn check_ring<A>(rng: &mut Rng)
+where
+    A: Ring + Arbitrary + Clone + Equivalent + Debug,
+{
+    check_semiring::<A>(rng);
+    for _ in 0..CASES {
+        let (a, b): (A, A) = rng.arbitrary();
+        law!(
+            "Ring additive inverse",
+            a.clone().sub(a.clone()),
+            A::ZERO,
+            a
+        );
+        law!(
+            "Ring subtraction",
+            a.clone().add(b.clone()).sub(b.clone()),
+            a.clone(),
+            a,
+            b
+        );
+    }
+}
+
+/// Check the [`EuclideanRing`](EuclideanRing) laws for `A`, in addition to
+/// the [`Ring`](Ring) laws checked by [`check_ring`](check_ring):
+///
+/// * Commutative multiplication: `a.mul(b) == b.mul(a)`
+/// * Integral domain: `ONE != ZERO`, and if `a` and `b` are both non-zero, so
+///   is `a.mul(b)`.
+/// * Remainder: if `b` is non-zero, `a == a.div(b).mul(b).add(a.modulo(b))`
+/// * Submultiplicative: if `a` and `b` are both non-zero,
+///   `a.degree() <= a.mul(b).degree()`
+pub fn check_euclidean_ring<A>(rng: &mut Rng)
+where
+    A: EuclideanRing + Arbitrary + Clone + Equivalent + Debug,
+{
+    check_ring::<A>(rng);
+    assert!(
+        !A::ONE.equivalent(&A::ZERO),
+        "EuclideanRing integral domain law failed: ONE == ZERO"
+    );
+    for _ in 0..CASES {
+        let (a, b): (A, A) = rng.arbitrary();
+        law!(
+            "EuclideanRing commutative multiplication",
+            a.clone().mul(b.clone()),
+            b.clone().mul(a.clone()),
+            a,
+            b
+        );
+        if b.equivalent(&A::ZERO) {
+            continue;
+        }
+        law!(
+            "EuclideanRing remainder",
+            a.clone()
+                .div(b.clone())
+                .mul(b.clone())
+                .add(a.clone().modulo(b.clone())),
+            a.clone(),
+            a,
+            b
+        );
+        if a.equivalent(&A::ZERO) {
+            continue;
+        }
+        law!(
+            "EuclideanRing integral domain",
+            a.clone().mul(b.clone()).equivalent(&A::ZERO),
+            false,
+            a,
+            b
+        );
+        law!(
+            "EuclideanRing submultiplicative",
+            a.clone().degree() <= a.clone().mul(b.clone()).degree(),
+            true,
+            a,
+            b
+        );
+    }
+}
+
+/// Check the [`HeytingAlgebra`](HeytingAlgebra) laws for `A`:
+///
+/// * Conjunction and disjunction are associative, commutative and
+///   idempotent, with `TRUE` and `FALSE` as their respective identities.
+/// * Absorption: `a.disj(a.conj(b)) == a` and `a.conj(a.disj(b)) == a`
+/// * Implication:
+///   * `a.implies(a) == TRUE`
+///   * `a.conj(a.implies(b)) == a.conj(b)`
+///   * `b.conj(a.implies(b)) == b`
+///   * `a.implies(b.conj(c)) == a.implies(b).conj(a.implies(c))`
+/// * Complement: `a.not() == a.implies(FALSE)`
+pub fn check_heyting_algebra<A>(rng: &mut Rng)
+where
+    A: HeytingAlgebra + Arbitrary + Clone + Equivalent + Debug,
+{
+    for _ in 0..CASES {
+        let (a, b, c): (A, A, A) = rng.arbitrary();
+        law!(
+            "HeytingAlgebra conjunction associativity",
+            a.clone().conj(b.clone()).conj(c.clone()),
+            a.clone().conj(b.clone().conj(c.clone())),
+            a,
+            b,
+            c
+        );
+        law!(
+            "HeytingAlgebra disjunction associativity",
+            a.clone().disj(b.clone()).disj(c.clone()),
+            a.clone().disj(b.clone().disj(c.clone())),
+            a,
+            b,
+            c
+        );
+        law!(
+            "HeytingAlgebra conjunction commutativity",
+            a.clone().conj(b.clone()),
+            b.clone().conj(a.clone()),
+            a,
+            b
+        );
+        law!(
+            "HeytingAlgebra disjunction commutativity",
+            a.clone().disj(b.clone()),
+            b.clone().disj(a.clone()),
+            a,
+            b
+        );
+        law!(
+            "HeytingAlgebra conjunction idempotence",
+            a.clone().conj(a.clone()),
+            a.clone(),
+            a
+        );
+        law!(
+            "HeytingAlgebra disjunction idempotence",
+            a.clone().disj(a.clone()),
+            a.clone(),
+            a
+        );
+        law!(
+            "HeytingAlgebra conjunction identity",
+            a.clone().conj(A::TRUE),
+            a.clone(),
+            a
+        );
+        law!(
+            "HeytingAlgebra disjunction identity",
+            a.clone().disj(A::FALSE),
+            a.clone(),
+            a
+        );
+        law!(
+            "HeytingAlgebra absorption",
+            a.clone().disj(a.clone().conj(b.clone())),
+            a.clone(),
+            a,
+            b
+        );
+        law!(
+            "HeytingAlgebra absorption",
+            a.clone().conj(a.clone().disj(b.clone())),
+            a.clone(),
+            a,
+            b
+        );
+        law!(
+            "HeytingAlgebra implication",
+            a.clone().implies(a.clone()),
+            A::TRUE,
+            a
+        );
+        law!(
+            "HeytingAlgebra implication",
+            a.clone().conj(a.clone().implies(b.clone())),
+            a.clone().conj(b.clone()),
+            a,
+            b
+        );
+        law!(
+            "HeytingAlgebra implication",
+            b.clone().conj(a.clone().implies(b.clone())),
+            b.clone(),
+            a,
+            b
+        );
+        law!(
+            "HeytingAlgebra implication",
+            a.clone().implies(b.clone().conj(c.clone())),
+            a.clone()
+                .implies(b.clone())
+                .conj(a.clone().implies(c.clone())),
+            a,
+            b,
+            c
+        );
+        law!(
+            "HeytingAlgebra complement",
+            a.clone().not(),
+            a.clone().implies(A::FALSE),
+            a
+        );
+    }
+}
+
+/// Check the [`BooleanAlgebra`](BooleanAlgebra) law for `A`, in addition to
+/// the [`HeytingAlgebra`](HeytingAlgebra) laws checked by
+/// [`check_heyting_algebra`](check_heyting_algebra):
+///
+/// * Excluded middle: `a.disj(a.not()) == TRUE`
+pub fn check_boolean_algebra<A>(rng: &mut Rng)
+where
+    A: BooleanAlgebra + Arbitrary + Clone + Equivalent + Debug,
+{
+    check_heyting_algebra::<A>(rng);
+    for _ in 0..CASES {
+        let a: A = rng.arbitrary();
+        law!(
+            "BooleanAlgebra excluded middle",
+            a.clone().disj(a.clone().not()),
+            A::TRUE,
+            a
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, HashMap};
+
+    use super::{
+        check_boolean_algebra, check_euclidean_ring, check_monoid, check_ring, check_semigroup,
+        check_semiring,
+    };
+    use crate::{
+        laws::Rng,
+        monoid::{Additive, Conj, Disj, Dual, Multiplicative},
+        semigroup::{First, Last, Max, Min},
+    };
+
+    #[test]
+    fn semigroups() {
+        let mut rng = Rng::default();
+        check_semigroup::<First<i32>>(&mut rng);
+        check_semigroup::<Last<i32>>(&mut rng);
+        check_semigroup::<Max<i32>>(&mut rng);
+        check_semigroup::<Min<String>>(&mut rng);
+    }
+
+    #[test]
+    fn monoids() {
+        let mut rng = Rng::default();
+        check_monoid::<()>(&mut rng);
+        check_monoid::<String>(&mut rng);
+        check_monoid::<Vec<i32>>(&mut rng);
+        check_monoid::<Additive<i32>>(&mut rng);
+        check_monoid::<Multiplicative<u8>>(&mut rng);
+        check_monoid::<Conj<bool>>(&mut rng);
+        check_monoid::<Disj<bool>>(&mut rng);
+        check_monoid::<Dual<String>>(&mut rng);
+        check_monoid::<HashMap<bool, String>>(&mut rng);
+        check_monoid::<BTreeMap<bool, Vec<i32>>>(&mut rng);
+    }
+
+    #[test]
+    fn semirings() {
+        let mut rng = Rng::default();
+        check_semiring::<Conj<bool>>(&mut rng);
+        check_semiring::<Disj<bool>>(&mut rng);
+    }
+
+    #[test]
+    fn rings() {
+        let mut rng = Rng::default();
+        check_ring::<()>(&mut rng);
+        check_ring::<f32>(&mut rng);
+        check_ring::<f64>(&mut rng);
+    }
+
+    #[test]
+    fn euclidean_rings() {
+        let mut rng = Rng::default();
+        check_euclidean_ring::<i8>(&mut rng);
+        check_euclidean_ring::<i16>(&mut rng);
+        check_euclidean_ring::<i32>(&mut rng);
+        check_euclidean_ring::<i64>(&mut rng);
+        check_euclidean_ring::<i128>(&mut rng);
+        check_euclidean_ring::<isize>(&mut rng);
+        check_euclidean_ring::<u8>(&mut rng);
+        check_euclidean_ring::<u16>(&mut rng);
+        check_euclidean_ring::<u32>(&mut rng);
+        check_euclidean_ring::<u64>(&mut rng);
+        check_euclidean_ring::<u128>(&mut rng);
+        check_euclidean_ring::<usize>(&mut rng);
+        // Floating point division can't satisfy the remainder law exactly,
+        // so the float instances are only checked as rings above.
+    }
+
+    #[test]
+    fn boolean_algebras() {
+        let mut rng = Rng::default();
+        check_boolean_algebra::<bool>(&mut rng);
+        check_boolean_algebra::<()>(&mut rng);
+    }
+}
@@ -0,0 +1,259 @@
+use std::{fmt::Debug, hash::Hash};
+
+use crate::{apply::ApplyFn, Applicative, Apply, Bind, Functor, Pure};
+
+use super::{law, Arbitrary, Equivalent, Rng, CASES};
+
+/// Check the [`Functor`](Functor) laws for `F`, a functor of `A`:
+///
+/// * Identity: `fa.fmap(|x| x) == fa`
+/// * Composition: `fa.fmap(f).fmap(g) == fa.fmap(|x| g(f(x)))`
+pub fn check_functor<'a, F, A>(rng: &mut Rng)
+where
+    F: Functor<'a, A, Target<A> = F> + Arbitrary + Clone + Equivalent + Debug + 'a,
+    A: Arbitrary + Hash + 'a,
+{
+    for _ in 0..CASES {
+        let fa: F = rng.arbitrary();
+        law!("Functor identity", fa.clone().fmap(|x| x), fa.clone(), fa);
+
+        let f = rng.function::<A, A>();
+        let g = rng.function::<A, A>();
+        let (f2, g2) = (f.clone(), g.clone());
+        law!(
+            "Functor composition",
+            fa.clone().fmap(f).fmap(g),
+            fa.clone().fmap(move |x| g2(f2(x))),
+            fa
+        );
+    }
+}
+
+/// Generate a random functor of functions from `A` to `A`.
+fn arbitrary_functions<'a, F, A>(rng: &mut Rng) -> F::Target<ApplyFn<'a, A, A>>
+where
+    F: Functor<'a, A> + Arbitrary,
+    A: Hash + Arbitrary + Clone + 'a,
+{
+    let f = rng.function::<(A, A), A>();
+    rng.arbitrary::<F>().fmap(move |x: A| {
+        let f = f.clone();
+        ApplyFn::from(move |y| f((x.clone(), y)))
+    })
+}
+
+/// Check the [`Apply`](Apply) law for `F`, a functor of `A`, where `FF` is
+/// the functor of functions `F<ApplyFn<A, A>>`:
+///
+/// * Composition: `w.apply(v.apply(u.fmap(compose))) == w.apply(v).apply(u)`
+pub fn check_apply<'a, F, A, FF>(rng: &mut Rng)
+where
+    F: Apply<'a, A, Target<ApplyFn<'a, A, A>> = FF> + Arbitrary + Clone + Equivalent + Debug + 'a,
+    F: Functor<'a, A, Target<A> = F>,
+    FF: Apply<'a, ApplyFn<'a, A, A>, Target<A> = F> + Clone + Debug + 'a,
+    FF: Functor<'a, ApplyFn<'a, A, A>, Target<ApplyFn<'a, A, A>> = FF>,
+    A: Arbitrary + Hash + Clone + 'a,
+{
+    for _ in 0..CASES {
+        let u: FF = arbitrary_functions::<F, A>(rng);
+        let v: FF = arbitrary_functions::<F, A>(rng);
+        let w: F = rng.arbitrary();
+        let compose = |f: ApplyFn<'a, A, A>| {
+            ApplyFn::from(move |g: ApplyFn<'a, A, A>| {
+                let f = f.clone();
+                ApplyFn::from(move |x| f.apply_fn(g.apply_fn(x)))
+            })
+        };
+        law!(
+            "Apply composition",
+            w.clone().apply(v.clone().apply(u.clone().fmap(compose))),
+            w.clone().apply(v.clone()).apply(u.clone()),
+            u,
+            v,
+            w
+        );
+    }
+}
+
+/// Check the [`Applicative`](Applicative) laws for `F`, a functor of `A`,
+/// where `FF` is the functor of functions `F<ApplyFn<A, A>>`, in addition to
+/// the [`Apply`](Apply) law checked by [`check_apply`](check_apply):
+///
+/// * Identity: `v.apply(pure(id)) == v`
+/// * Homomorphism: `pure(x).apply(pure(f)) == pure(f(x))`
+/// * Interchange: `pure(y).apply(u) == u.apply(pure(|f| f(y)))`
+pub fn check_applicative<'a, F, A, FF>(rng: &mut Rng)
+where
+    F: Applicative<'a, A, Target<ApplyFn<'a, A, A>> = FF>
+        + Arbitrary
+        + Clone
+        + Equivalent
+        + Debug
+        + 'a,
+    F: Functor<'a, A, Target<A> = F>,
+    FF: Applicative<'a, ApplyFn<'a, A, A>, Target<A> = F> + Clone + Debug + 'a,
+    FF: Functor<'a, ApplyFn<'a, A, A>, Target<ApplyFn<'a, A, A>> = FF>,
+    FF::Target<ApplyFn<'a, ApplyFn<'a, A, A>, A>>: Pure<ApplyFn<'a, ApplyFn<'a, A, A>, A>>,
+    A: Arbitrary + Hash + Clone + Debug + 'a,
+{
+    check_apply::<F, A, FF>(rng);
+    for _ in 0..CASES {
+        let v: F = rng.arbitrary();
+        law!(
+            "Applicative identity",
+            v.clone().apply(FF::pure(ApplyFn::from(|x| x))),
+            v.clone(),
+            v
+        );
+
+        let x: A = rng.arbitrary();
+        let f = rng.function::<A, A>();
+        let f2 = f.clone();
+        law!(
+            "Applicative homomorphism",
+            F::pure(x.clone()).apply(FF::pure(ApplyFn::from(f))),
+            F::pure(f2(x.clone())),
+            x
+        );
+
+        let u: FF = arbitrary_functions::<F, A>(rng);
+        let y: A = rng.arbitrary();
+        let y2 = y.clone();
+        law!(
+            "Applicative interchange",
+            F::pure(y.clone()).apply(u.clone()),
+            u.clone()
+                .apply(Pure::pure(ApplyFn::from(move |f: ApplyFn<'a, A, A>| {
+                    f.apply_fn(y2.clone())
+                }))),
+            u,
+            y
+        );
+    }
+}
+
+/// Check the [`Monad`](crate::Monad) laws for `M`, a monad of `A`:
+///
+/// * Left identity: `pure(x).bind(f) == f(x)`
+/// * Right identity: `m.bind(pure) == m`
+/// * Associativity: `m.bind(f).bind(g) == m.bind(|x| f(x).bind(g))`
+pub fn check_monad<'a, M, A>(rng: &mut Rng)
+where
+    M: Bind<'a, A, Target<A> = M> + Pure<A> + Arbitrary + Clone + Equivalent + Debug + 'a,
+    A: Arbitrary + Hash + Clone + Debug + 'a,
+{
+    for _ in 0..CASES {
+        let x: A = rng.arbitrary();
+        let f = rng.function::<A, M>();
+        let g = rng.function::<A, M>();
+        law!(
+            "Monad left identity",
+            M::pure(x.clone()).bind::<A, _>(f.clone()),
+            f(x.clone()),
+            x
+        );
+
+        let m: M = rng.arbitrary();
+        law!(
+            "Monad right identity",
+            m.clone().bind::<A, _>(M::pure),
+            m.clone(),
+            m
+        );
+
+        let (f2, g2) = (f.clone(), g.clone());
+        law!(
+            "Monad associativity",
+            m.clone().bind::<A, _>(f).bind::<A, _>(g),
+            m.clone()
+                .bind::<A, _>(move |x| f2(x).bind::<A, _>(g2.clone())),
+            m
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::{BTreeMap, HashMap, LinkedList, VecDeque},
+        task::Poll,
+    };
+
+    use super::{check_applicative, check_functor, check_monad};
+    use crate::{
+        compose::Compose,
+        constant::Const,
+        cont::Cont,
+        env::Env,
+        identity::Identity,
+        laws::Rng,
+        transformers::{ContT, OptionT, ReaderT, ResultT, StateT, WriterT},
+        Gen, NonEmpty, Reader, State, Validation, Writer,
+    };
+
+    macro_rules! check_monad_laws {
+        ($name:ident, $type:ty) => {
+            #[test]
+            fn $name() {
+                let mut rng = Rng::default();
+                check_functor::<$type, i32>(&mut rng);
+                check_applicative::<$type, i32, _>(&mut rng);
+                check_monad::<$type, i32>(&mut rng);
+            }
+        };
+    }
+
+    check_monad_laws!(identity, Identity<i32>);
+    check_monad_laws!(option, Option<i32>);
+    check_monad_laws!(result, Result<i32, String>);
+    check_monad_laws!(poll, Poll<i32>);
+    check_monad_laws!(boxed, Box<i32>);
+    check_monad_laws!(tuple, (String, i32));
+    check_monad_laws!(vec, Vec<i32>);
+    check_monad_laws!(vec_deque, VecDeque<i32>);
+    check_monad_laws!(linked_list, LinkedList<i32>);
+    check_monad_laws!(non_empty, NonEmpty<i32>);
+    check_monad_laws!(state, State<'static, i32, i32>);
+    check_monad_laws!(reader, Reader<'static, i32, i32>);
+    check_monad_laws!(writer, Writer<String, i32>);
+    check_monad_laws!(cont, Cont<'static, String, i32>);
+    check_monad_laws!(gen, Gen<'static, i32>);
+    check_monad_laws!(option_t, OptionT<Vec<Option<i32>>>);
+    check_monad_laws!(result_t, ResultT<String, Vec<Result<i32, String>>>);
+    check_monad_laws!(writer_t, WriterT<String, Option<(i32, String)>>);
+    check_monad_laws!(state_t, StateT<'static, i32, Vec<(i32, i32)>>);
+    check_monad_laws!(reader_t, ReaderT<'static, i32, Option<i32>>);
+    check_monad_laws!(cont_t, ContT<'static, String, Vec<String>, i32>);
+
+    #[test]
+    fn validation() {
+        let mut rng = Rng::default();
+        check_functor::<Validation<String, i32>, i32>(&mut rng);
+        check_applicative::<Validation<String, i32>, i32, _>(&mut rng);
+    }
+
+    #[test]
+    fn constant() {
+        let mut rng = Rng::default();
+        check_functor::<Const<String, i32>, i32>(&mut rng);
+        check_applicative::<Const<String, i32>, i32, _>(&mut rng);
+    }
+
+    #[test]
+    fn compose() {
+        type OptionVec = Compose<Option<Vec<i32>>, Vec<i32>, i32>;
+        let mut rng = Rng::default();
+        check_functor::<OptionVec, i32>(&mut rng);
+        check_applicative::<OptionVec, i32, _>(&mut rng);
+    }
+
+    #[test]
+    fn functors() {
+        let mut rng = Rng::default();
+        check_functor::<[i32; 3], i32>(&mut rng);
+        check_functor::<(String, i32), i32>(&mut rng);
+        check_functor::<Env<String, i32>, i32>(&mut rng);
+        check_functor::<HashMap<bool, i32>, i32>(&mut rng);
+        check_functor::<BTreeMap<u8, i32>, i32>(&mut rng);
+    }
+}
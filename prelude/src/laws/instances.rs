@@ -0,0 +1,443 @@
+use std::{
+    collections::{BTreeMap, HashMap, LinkedList, VecDeque},
+    hash::Hash,
+    task::Poll,
+};
+
+use crate::{
+    compose::Compose,
+    constant::Const,
+    cont::Cont,
+    env::Env,
+    identity::Identity,
+    monoid::{Additive, Conj, Disj, Dual, Multiplicative},
+    property::{Tree, MAX_SIZE},
+    semigroup::{First, Last, Max, Min},
+    transformers::{ContT, OptionT, ReaderT, ResultT, StateT, WriterT},
+    Gen, NonEmpty, Reader, State, Validation, Writer,
+};
+
+use super::{equivalent_on, Arbitrary, Equivalent, Rng};
+
+/// The largest number of elements in a generated collection.
+const MAX_LENGTH: u64 = 3;
+
+impl Arbitrary for () {
+    fn arbitrary(_rng: &mut Rng) -> Self {}
+}
+
+impl Arbitrary for bool {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        rng.bool()
+    }
+}
+
+// Integers are kept small enough that multiplying three of them can't
+// overflow, so the checks can't fail on an overflow rather than a broken law.
+macro_rules! impl_arbitrary_int {
+    ($type:ty, $low:expr, $high:expr) => {
+        impl Arbitrary for $type {
+            fn arbitrary(rng: &mut Rng) -> Self {
+                rng.range($low, $high) as $type
+            }
+        }
+    };
+}
+
+impl_arbitrary_int!(i8, -5, 5);
+impl_arbitrary_int!(i16, -30, 30);
+impl_arbitrary_int!(i32, -1000, 1000);
+impl_arbitrary_int!(i64, -100_000, 100_000);
+impl_arbitrary_int!(i128, -100_000, 100_000);
+impl_arbitrary_int!(isize, -1000, 1000);
+impl_arbitrary_int!(u8, 0, 6);
+impl_arbitrary_int!(u16, 0, 40);
+impl_arbitrary_int!(u32, 0, 1600);
+impl_arbitrary_int!(u64, 0, 100_000);
+impl_arbitrary_int!(u128, 0, 100_000);
+impl_arbitrary_int!(usize, 0, 1600);
+
+// Floats are generated as small multiples of a quarter, which keeps addition
+// and multiplication exact.
+macro_rules! impl_arbitrary_float {
+    ($type:ty) => {
+        impl Arbitrary for $type {
+            fn arbitrary(rng: &mut Rng) -> Self {
+                rng.range(-40, 40) as $type / 4.0
+            }
+        }
+    };
+}
+
+impl_arbitrary_float!(f32);
+impl_arbitrary_float!(f64);
+
+impl Arbitrary for char {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        (b'a' + rng.below(26) as u8) as char
+    }
+}
+
+impl Arbitrary for String {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        let length = rng.below(MAX_LENGTH + 2);
+        (0..length).map(|_| rng.arbitrary::<char>()).collect()
+    }
+}
+
+macro_rules! impl_arbitrary_for_collection {
+    ($type:ident) => {
+        impl<A: Arbitrary> Arbitrary for $type<A> {
+            fn arbitrary(rng: &mut Rng) -> Self {
+                let length = rng.below(MAX_LENGTH + 1);
+                (0..length).map(|_| rng.arbitrary()).collect()
+            }
+        }
+    };
+}
+
+impl_arbitrary_for_collection!(Vec);
+impl_arbitrary_for_collection!(VecDeque);
+impl_arbitrary_for_collection!(LinkedList);
+
+impl<K: Arbitrary + Hash + Eq, V: Arbitrary> Arbitrary for HashMap<K, V> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        let length = rng.below(MAX_LENGTH + 1);
+        (0..length).map(|_| rng.arbitrary()).collect()
+    }
+}
+
+impl<K: Arbitrary + Ord, V: Arbitrary> Arbitrary for BTreeMap<K, V> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        let length = rng.below(MAX_LENGTH + 1);
+        (0..length).map(|_| rng.arbitrary()).collect()
+    }
+}
+
+impl<A: Arbitrary> Arbitrary for NonEmpty<A> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        let head = rng.arbitrary();
+        let length = rng.below(MAX_LENGTH);
+        NonEmpty::new(head, (0..length).map(|_| rng.arbitrary()).collect())
+    }
+}
+
+impl<A: Arbitrary, const N: usize> Arbitrary for [A; N] {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        std::array::from_fn(|_| rng.arbitrary())
+    }
+}
+
+impl<A: Arbitrary> Arbitrary for Option<A> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        if rng.below(4) == 0 {
+            None
+        } else {
+            Some(rng.arbitrary())
+        }
+    }
+}
+
+impl<A: Arbitrary> Arbitrary for Poll<A> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        if rng.below(4) == 0 {
+            Poll::Pending
+        } else {
+            Poll::Ready(rng.arbitrary())
+        }
+    }
+}
+
+impl<A: Arbitrary> Arbitrary for Box<A> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        Box::new(rng.arbitrary())
+    }
+}
+
+impl<A: Arbitrary, E: Arbitrary> Arbitrary for Result<A, E> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        if rng.below(4) == 0 {
+            Err(rng.arbitrary())
+        } else {
+            Ok(rng.arbitrary())
+        }
+    }
+}
+
+impl<E: Arbitrary, A: Arbitrary> Arbitrary for Validation<E, A> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        if rng.below(4) == 0 {
+            Validation::Failure(rng.arbitrary())
+        } else {
+            Validation::Success(rng.arbitrary())
+        }
+    }
+}
+
+impl<A: Arbitrary, B: Arbitrary> Arbitrary for (A, B) {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        (rng.arbitrary(), rng.arbitrary())
+    }
+}
+
+impl<A: Arbitrary, B: Arbitrary, C: Arbitrary> Arbitrary for (A, B, C) {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        (rng.arbitrary(), rng.arbitrary(), rng.arbitrary())
+    }
+}
+
+macro_rules! impl_arbitrary_for_newtype {
+    ($type:ident) => {
+        impl<A: Arbitrary> Arbitrary for $type<A> {
+            fn arbitrary(rng: &mut Rng) -> Self {
+                $type(rng.arbitrary())
+            }
+        }
+    };
+}
+
+impl_arbitrary_for_newtype!(Identity);
+impl_arbitrary_for_newtype!(Additive);
+impl_arbitrary_for_newtype!(Multiplicative);
+impl_arbitrary_for_newtype!(Conj);
+impl_arbitrary_for_newtype!(Disj);
+impl_arbitrary_for_newtype!(Dual);
+impl_arbitrary_for_newtype!(First);
+impl_arbitrary_for_newtype!(Last);
+impl_arbitrary_for_newtype!(Max);
+impl_arbitrary_for_newtype!(Min);
+
+impl<C: Arbitrary, A> Arbitrary for Const<C, A> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        Const::new(rng.arbitrary())
+    }
+}
+
+impl<F: Arbitrary, G, A> Arbitrary for Compose<F, G, A> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        Compose::new(rng.arbitrary())
+    }
+}
+
+impl<W: Arbitrary, A: Arbitrary> Arbitrary for Writer<W, A> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        Writer::new(rng.arbitrary(), rng.arbitrary())
+    }
+}
+
+impl<E: Arbitrary, A: Arbitrary> Arbitrary for Env<E, A> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        Env::new(rng.arbitrary(), rng.arbitrary())
+    }
+}
+
+impl<'a, S, A> Arbitrary for State<'a, S, A>
+where
+    S: Arbitrary + Hash + 'a,
+    A: Arbitrary + 'a,
+{
+    fn arbitrary(rng: &mut Rng) -> Self {
+        State::new(rng.function())
+    }
+}
+
+impl<'a, S, A> Equivalent for State<'a, S, A>
+where
+    S: Arbitrary + Clone,
+    (A, S): Equivalent,
+{
+    fn equivalent(&self, other: &Self) -> bool {
+        equivalent_on(
+            |rng| rng.arbitrary::<S>(),
+            |state| self.run_state(state),
+            |state| other.run_state(state),
+        )
+    }
+}
+
+impl<'a, R, A> Arbitrary for Reader<'a, R, A>
+where
+    R: Hash + 'a,
+    A: Arbitrary + 'a,
+{
+    fn arbitrary(rng: &mut Rng) -> Self {
+        Reader::new(rng.function())
+    }
+}
+
+impl<'a, R, A> Equivalent for Reader<'a, R, A>
+where
+    R: Arbitrary + Clone,
+    A: Equivalent,
+{
+    fn equivalent(&self, other: &Self) -> bool {
+        equivalent_on(
+            |rng| rng.arbitrary::<R>(),
+            |env| self.run_reader(env),
+            |env| other.run_reader(env),
+        )
+    }
+}
+
+impl<'a, R, A> Arbitrary for Cont<'a, R, A>
+where
+    R: Arbitrary + Clone + 'a,
+    A: Arbitrary + Clone + 'a,
+{
+    fn arbitrary(rng: &mut Rng) -> Self {
+        if rng.below(4) == 0 {
+            // Skip the continuation entirely.
+            let result: R = rng.arbitrary();
+            Cont::new(move |_| result.clone())
+        } else {
+            let value: A = rng.arbitrary();
+            Cont::new(move |k| k(value.clone()))
+        }
+    }
+}
+
+impl<'a, R, A> Equivalent for Cont<'a, R, A>
+where
+    R: Arbitrary + Equivalent + 'a,
+    A: Hash + 'a,
+{
+    fn equivalent(&self, other: &Self) -> bool {
+        equivalent_on(
+            |rng| rng.function::<A, R>(),
+            |k| self.run_cont(k),
+            |k| other.run_cont(k),
+        )
+    }
+}
+
+impl<M: Arbitrary> Arbitrary for OptionT<M> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        OptionT::new(rng.arbitrary())
+    }
+}
+
+impl<E, M: Arbitrary> Arbitrary for ResultT<E, M> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        ResultT::new(rng.arbitrary())
+    }
+}
+
+impl<W, M: Arbitrary> Arbitrary for WriterT<W, M> {
+    fn arbitrary(rng: &mut Rng) -> Self {
+        WriterT::new(rng.arbitrary())
+    }
+}
+
+impl<'a, S, M> Arbitrary for StateT<'a, S, M>
+where
+    S: Hash + 'a,
+    M: Arbitrary + 'a,
+{
+    fn arbitrary(rng: &mut Rng) -> Self {
+        StateT::new(rng.function())
+    }
+}
+
+impl<'a, S, M> Equivalent for StateT<'a, S, M>
+where
+    S: Arbitrary + Clone,
+    M: Equivalent,
+{
+    fn equivalent(&self, other: &Self) -> bool {
+        equivalent_on(
+            |rng| rng.arbitrary::<S>(),
+            |state| self.run_state(state),
+            |state| other.run_state(state),
+        )
+    }
+}
+
+impl<'a, R, M> Arbitrary for ReaderT<'a, R, M>
+where
+    R: Hash + 'a,
+    M: Arbitrary + 'a,
+{
+    fn arbitrary(rng: &mut Rng) -> Self {
+        ReaderT::new(rng.function())
+    }
+}
+
+impl<'a, R, M> Equivalent for ReaderT<'a, R, M>
+where
+    R: Arbitrary + Clone,
+    M: Equivalent,
+{
+    fn equivalent(&self, other: &Self) -> bool {
+        equivalent_on(
+            |rng| rng.arbitrary::<R>(),
+            |env| self.run_reader(env),
+            |env| other.run_reader(env),
+        )
+    }
+}
+
+impl<'a, R, M, A> Arbitrary for ContT<'a, R, M, A>
+where
+    R: 'a,
+    M: Arbitrary + Clone + 'a,
+    A: Arbitrary + Clone + 'a,
+{
+    fn arbitrary(rng: &mut Rng) -> Self {
+        if rng.below(4) == 0 {
+            // Skip the continuation entirely.
+            let result: M = rng.arbitrary();
+            ContT::new(move |_| result.clone())
+        } else {
+            let value: A = rng.arbitrary();
+            ContT::new(move |k| k(value.clone()))
+        }
+    }
+}
+
+impl<'a, R, M, A> Equivalent for ContT<'a, R, M, A>
+where
+    R: 'a,
+    M: Arbitrary + Equivalent + 'a,
+    A: Hash + 'a,
+{
+    fn equivalent(&self, other: &Self) -> bool {
+        equivalent_on(
+            |rng| rng.function::<A, M>(),
+            |k| self.run_cont(k),
+            |k| other.run_cont(k),
+        )
+    }
+}
+
+impl<'a, A> Arbitrary for Gen<'a, A>
+where
+    A: Arbitrary + Clone + 'a,
+{
+    fn arbitrary(rng: &mut Rng) -> Self {
+        let f = rng.function::<(u64, usize), (A, Vec<A>)>();
+        Gen::from_tree(move |rng, size| {
+            let (value, shrinks) = f((rng.next_u64(), size));
+            Tree::new(value, move || {
+                shrinks.iter().cloned().map(Tree::leaf).collect()
+            })
+        })
+    }
+}
+
+impl<'a, A> Equivalent for Gen<'a, A>
+where
+    A: PartialEq + Clone + 'a,
+{
+    fn equivalent(&self, other: &Self) -> bool {
+        let shallow = |gen: &Self, (seed, size): (u64, usize)| {
+            let tree = gen.generate(&mut Rng::new(seed), size);
+            let shrinks: Vec<A> = tree.children().into_iter().map(Tree::into_value).collect();
+            (tree.into_value(), shrinks)
+        };
+        equivalent_on(
+            |rng| (rng.next_u64(), rng.below(MAX_SIZE as u64 + 1) as usize),
+            |input| shallow(self, input),
+            |input| shallow(other, input),
+        )
+    }
+}
@@ -0,0 +1,135 @@
+//! Property checks for type class laws.
+//!
+//! Every type class in this crate comes with a set of laws which its
+//! instances are expected to follow, but the compiler can't check them for
+//! you. This module provides reusable checks which generate random inputs
+//! using a seeded [`Rng`](Rng), so that results are reproducible, and panic
+//! with the law and the inputs involved if an instance breaks a law.
+//!
+//! This module is only available with the `laws` feature enabled.
+//!
+//! ```
+//! # use higher::laws::{self, Rng};
+//! let mut rng = Rng::new(1337);
+//! laws::check_functor::<Option<i32>, i32>(&mut rng);
+//! laws::check_monad::<Vec<i32>, i32>(&mut rng);
+//! laws::check_monoid::<String>(&mut rng);
+//! laws::check_euclidean_ring::<i64>(&mut rng);
+//! ```
+//!
+//! To check your own types, implement [`Arbitrary`](Arbitrary) for them, so
+//! the checks know how to generate values, and make sure they either
+//! implement [`PartialEq`](PartialEq) or [`Equivalent`](Equivalent), so the
+//! checks know how to compare them.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+mod instances;
+
+mod functor;
+pub use self::functor::{check_applicative, check_apply, check_functor, check_monad};
+
+mod algebra;
+pub use self::algebra::{
+    check_boolean_algebra, check_euclidean_ring, check_heyting_algebra, check_monoid, check_ring,
+    check_semigroup, check_semiring,
+};
+
+/// The number of random cases each law is checked against.
+pub const CASES: usize = 100;
+
+pub use crate::property::{Rng, DEFAULT_SEED};
+
+impl Rng {
+    /// Generate a random value of any type implementing
+    /// [`Arbitrary`](Arbitrary).
+    pub fn arbitrary<A: Arbitrary>(&mut self) -> A {
+        A::arbitrary(self)
+    }
+
+    /// Generate a random function.
+    ///
+    /// The function is pure: it always returns the same output for the same
+    /// input, which it computes by seeding a fresh generator with a hash of
+    /// the input.
+    pub fn function<A, B>(&mut self) -> impl Fn(A) -> B + Clone
+    where
+        A: Hash,
+        B: Arbitrary,
+    {
+        let seed = self.next_u64();
+        move |input: A| {
+            let mut hasher = DefaultHasher::new();
+            input.hash(&mut hasher);
+            B::arbitrary(&mut Rng::new(seed ^ hasher.finish()))
+        }
+    }
+}
+
+/// A type which can generate random values of itself.
+pub trait Arbitrary: Sized {
+    /// Generate a random value.
+    fn arbitrary(rng: &mut Rng) -> Self;
+}
+
+/// A notion of equality used by the law checks.
+///
+/// This is implemented for everything which implements
+/// [`PartialEq`](PartialEq). Types which can't implement
+/// [`PartialEq`](PartialEq) because they wrap functions, like
+/// [`State`](crate::State), implement it by running both sides on the same
+/// set of random inputs and comparing the results.
+pub trait Equivalent {
+    /// Test whether two values are equivalent.
+    fn equivalent(&self, other: &Self) -> bool;
+}
+
+impl<A> Equivalent for A
+where
+    A: PartialEq,
+{
+    fn equivalent(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+/// The number of random inputs [`Equivalent`](Equivalent) implementations
+/// compare functions on.
+const SAMPLES: usize = 10;
+
+/// Compare two functions on a fixed set of random inputs, generated by
+/// `sample`.
+fn equivalent_on<I, O, S, L, R>(mut sample: S, left: L, right: R) -> bool
+where
+    I: Clone,
+    O: Equivalent,
+    S: FnMut(&mut Rng) -> I,
+    L: Fn(I) -> O,
+    R: Fn(I) -> O,
+{
+    let mut rng = Rng::new(DEFAULT_SEED);
+    (0..SAMPLES).all(|_| {
+        let input = sample(&mut rng);
+        left(input.clone()).equivalent(&right(input))
+    })
+}
+
+macro_rules! law {
+    ($name:expr, $left:expr, $right:expr, $($input:ident),*) => {{
+        let left = $left;
+        let right = $right;
+        if !$crate::laws::Equivalent::equivalent(&left, &right) {
+            panic!(
+                "{} law failed:\n  left: {:?}\n right: {:?}\n{}",
+                $name,
+                left,
+                right,
+                [$(format!("{:>6}: {:?}", stringify!($input), $input)),*].join("\n")
+            );
+        }
+    }};
+}
+pub(crate) use law;
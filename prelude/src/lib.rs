@@ -4,7 +4,10 @@
 #![allow(rustdoc::bare_urls)]
 #![doc = include_str!("../README.md")]
 
-pub use higher_derive::{Bifunctor, BifunctorRef, Functor, FunctorRef};
+pub use higher_derive::{
+    Bifunctor, BifunctorRef, Contravariant, Foldable, Functor, FunctorRef, Lenses, Monoid,
+    Prisms, Profunctor, Semigroup, Traversable,
+};
 
 pub mod semigroup;
 #[doc(inline)]
@@ -28,7 +31,19 @@ pub use crate::bifunctor::{Bifunctor, BifunctorRef};
 
 pub mod profunctor;
 #[doc(inline)]
-pub use crate::profunctor::Profunctor;
+pub use crate::profunctor::{Choice, Closed, Profunctor, Strong};
+
+pub mod func;
+#[doc(inline)]
+pub use crate::func::Func;
+
+pub mod star;
+#[doc(inline)]
+pub use crate::star::{Costar, Star};
+
+pub mod kleisli;
+#[doc(inline)]
+pub use crate::kleisli::Kleisli;
 
 pub mod pure;
 #[doc(inline)]
@@ -50,17 +65,29 @@ pub mod monad;
 #[doc(inline)]
 pub use crate::monad::Monad;
 
+pub mod extend;
+#[doc(inline)]
+pub use crate::extend::Extend;
+
+pub mod comonad;
+#[doc(inline)]
+pub use crate::comonad::Comonad;
+
 pub mod foldable;
 #[doc(inline)]
 pub use crate::foldable::Foldable;
 
+pub mod foldable1;
+#[doc(inline)]
+pub use crate::foldable1::Foldable1;
+
 pub mod traversable;
 #[doc(inline)]
 pub use crate::traversable::Traversable;
 
 pub mod error;
 #[doc(inline)]
-pub use crate::error::{ApplicativeError, MonadError};
+pub use crate::error::{ApplicativeError, MonadError, MonadReader, MonadState, MonadWriter};
 
 pub mod alt;
 #[doc(inline)]
@@ -78,9 +105,83 @@ pub mod monadplus;
 #[doc(inline)]
 pub use crate::monadplus::MonadPlus;
 
+pub mod validation;
+#[doc(inline)]
+pub use crate::validation::Validation;
+
+pub mod non_empty;
+#[doc(inline)]
+pub use crate::non_empty::NonEmpty;
+
+pub mod state;
+#[doc(inline)]
+pub use crate::state::State;
+
+pub mod reader;
+#[doc(inline)]
+pub use crate::reader::Reader;
+
+pub mod writer;
+#[doc(inline)]
+pub use crate::writer::Writer;
+
+pub mod free;
+#[doc(inline)]
+pub use crate::free::Free;
+
+pub mod trampoline;
+#[doc(inline)]
+pub use crate::trampoline::Trampoline;
+
+pub mod cont;
+#[doc(inline)]
+pub use crate::cont::Cont;
+
+pub mod identity;
+#[doc(inline)]
+pub use crate::identity::Identity;
+
+pub mod constant;
+#[doc(inline)]
+pub use crate::constant::Const;
+
+pub mod compose;
+#[doc(inline)]
+pub use crate::compose::Compose;
+
+pub mod coyoneda;
+#[doc(inline)]
+pub use crate::coyoneda::Coyoneda;
+
+pub mod constrained;
+#[doc(inline)]
+pub use crate::constrained::{CBifunctor, CBind, CFoldable, CFunctor};
+
+pub mod store;
+#[doc(inline)]
+pub use crate::store::Store;
+
+pub mod env;
+#[doc(inline)]
+pub use crate::env::Env;
+
+pub mod traced;
+#[doc(inline)]
+pub use crate::traced::Traced;
+
+pub mod property;
+#[doc(inline)]
+pub use crate::property::Gen;
+
+pub mod transformers;
+
 pub mod algebras;
+pub mod optics;
 pub mod rings;
 
+#[cfg(any(test, feature = "laws"))]
+pub mod laws;
+
 /// Monadic do notation.
 ///
 /// This macro provides some syntactic sugar to make monads easier to read and
@@ -195,7 +296,6 @@ pub mod rings;
 /// }
 /// # , Some(NoCopy(5_u32)));
 /// ```
-
 #[macro_export]
 macro_rules! run {
     //matching against a token tree, because a pattern cannot be followed by "<=".
@@ -0,0 +1,368 @@
+use std::{
+    iter::{self, Chain, Once},
+    slice, vec,
+};
+
+use crate::{
+    apply::{ap, lift2, ApplyFn},
+    foldable::{fold_map_default_l, fold_map_default_l_ref},
+    free::FreeFunctor,
+    Applicative, Apply, Bind, Foldable, Foldable1, Functor, FunctorRef, Monoid, Pure, Semigroup,
+    Traversable,
+};
+
+/// A list which is guaranteed to contain at least one value.
+///
+/// Because it can never be empty, `NonEmpty` implements
+/// [`Foldable1`](Foldable1), letting you fold it using only a
+/// [`Semigroup`](Semigroup), and it can always give you a
+/// [`first`](NonEmpty::first) and a [`last`](NonEmpty::last) value without an
+/// [`Option`](Option).
+///
+/// ```
+/// # use higher::{non_empty::NonEmpty, semigroup::Max, Foldable1, Functor};
+/// let list = NonEmpty::new(1, vec![5, 3]);
+/// assert_eq!(list.fmap(Max).fold1(), Max(5));
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct NonEmpty<A> {
+    pub head: A,
+    pub tail: Vec<A>,
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl<A> NonEmpty<A> {
+    /// Construct a `NonEmpty` from its first value and a list of the rest.
+    pub fn new(head: A, tail: Vec<A>) -> Self {
+        Self { head, tail }
+    }
+
+    /// Construct a `NonEmpty` containing a single value.
+    pub fn singleton(head: A) -> Self {
+        Self::new(head, Vec::new())
+    }
+
+    /// Construct a `NonEmpty` from a [`Vec`](Vec), returning
+    /// [`None`](Option::None) if the [`Vec`](Vec) is empty.
+    pub fn from_vec(vec: Vec<A>) -> Option<Self> {
+        let mut values = vec.into_iter();
+        let head = values.next()?;
+        Some(Self::new(head, values.collect()))
+    }
+
+    /// Get the number of values in the list. This is never zero.
+    pub fn len(&self) -> usize {
+        self.tail.len() + 1
+    }
+
+    /// Get a reference to the first value in the list.
+    pub fn first(&self) -> &A {
+        &self.head
+    }
+
+    /// Get a reference to the last value in the list.
+    pub fn last(&self) -> &A {
+        self.tail.last().unwrap_or(&self.head)
+    }
+
+    /// Add a value to the end of the list.
+    pub fn push(&mut self, value: A) {
+        self.tail.push(value)
+    }
+
+    /// Iterate over references to the values in the list.
+    pub fn iter(&self) -> Chain<Once<&A>, slice::Iter<'_, A>> {
+        iter::once(&self.head).chain(self.tail.iter())
+    }
+
+    /// Convert the list into a [`Vec`](Vec).
+    pub fn into_vec(self) -> Vec<A> {
+        self.into()
+    }
+}
+
+impl<A> From<NonEmpty<A>> for Vec<A> {
+    fn from(list: NonEmpty<A>) -> Self {
+        let mut out = Vec::with_capacity(list.len());
+        out.push(list.head);
+        out.extend(list.tail);
+        out
+    }
+}
+
+impl<A> IntoIterator for NonEmpty<A> {
+    type Item = A;
+    type IntoIter = Chain<Once<A>, vec::IntoIter<A>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        iter::once(self.head).chain(self.tail)
+    }
+}
+
+impl<'b, A> IntoIterator for &'b NonEmpty<A> {
+    type Item = &'b A;
+    type IntoIter = Chain<Once<&'b A>, slice::Iter<'b, A>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<A> Extend<A> for NonEmpty<A> {
+    fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+        self.tail.extend(iter)
+    }
+}
+
+impl<A> Semigroup for NonEmpty<A> {
+    fn mappend(mut self, other: Self) -> Self {
+        self.extend(other);
+        self
+    }
+}
+
+impl<'a, A: 'a> Functor<'a, A> for NonEmpty<A> {
+    type Target<T: 'a> = NonEmpty<T>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B,
+    {
+        NonEmpty::new(f(self.head), self.tail.into_iter().map(f).collect())
+    }
+}
+
+impl<'a, A: 'a> FreeFunctor<'a, A> for NonEmpty<A> {
+    fn fmap_target<T: 'a, U: 'a, F>(fa: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> U + 'a,
+    {
+        fa.fmap(f)
+    }
+}
+
+impl<'a, A: 'a> FunctorRef<'a, A> for NonEmpty<A> {
+    fn fmap_ref<B: 'a, F>(&self, f: F) -> Self::Target<B>
+    where
+        F: Fn(&A) -> B,
+    {
+        NonEmpty::new(f(&self.head), self.tail.iter().map(f).collect())
+    }
+
+    fn fclone(&self) -> Self
+    where
+        A: Clone,
+    {
+        self.clone()
+    }
+}
+
+impl<A> Pure<A> for NonEmpty<A> {
+    fn pure(value: A) -> Self {
+        Self::singleton(value)
+    }
+}
+
+impl<'a, A: 'a> Apply<'a, A> for NonEmpty<A>
+where
+    A: Clone,
+{
+    fn apply<B: 'a>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B> {
+        ap(f, self)
+    }
+}
+
+impl<'a, A: 'a> Bind<'a, A> for NonEmpty<A> {
+    fn bind<B: 'a, F>(self, f: F) -> Self::Target<B>
+    where
+        F: Fn(A) -> Self::Target<B> + 'a,
+    {
+        let mut out = f(self.head);
+        for value in self.tail {
+            out.extend(f(value));
+        }
+        out
+    }
+}
+
+impl<'a, A: 'a> Foldable<'a, A> for NonEmpty<A> {
+    fn foldr<B, F>(self, f: F, init: B) -> B
+    where
+        F: Fn(A, B) -> B,
+    {
+        self.into_iter().rfold(init, |a, b| f(b, a))
+    }
+
+    fn foldr_ref<B, F>(&'a self, f: F, init: B) -> B
+    where
+        F: Fn(&'a A, B) -> B + 'a,
+    {
+        self.iter().rfold(init, |a, b| f(b, a))
+    }
+
+    fn foldl<B, F>(self, f: F, init: B) -> B
+    where
+        F: Fn(B, A) -> B,
+    {
+        self.into_iter().fold(init, f)
+    }
+
+    fn foldl_ref<B, F>(&'a self, f: F, init: B) -> B
+    where
+        F: Fn(B, &'a A) -> B + 'a,
+    {
+        self.iter().fold(init, f)
+    }
+
+    fn fold_map<F, M>(self, f: F) -> M
+    where
+        F: Fn(A) -> M,
+        M: Monoid,
+    {
+        fold_map_default_l(f, self)
+    }
+
+    fn fold_map_ref<F, M>(&'a self, f: F) -> M
+    where
+        F: Fn(&'a A) -> M + 'a,
+        M: Monoid + 'a,
+    {
+        fold_map_default_l_ref(f, self)
+    }
+}
+
+impl<'a, A: 'a> Foldable1<'a, A> for NonEmpty<A> {
+    fn fold_map1<S, F>(self, f: F) -> S
+    where
+        F: Fn(A) -> S + 'a,
+        S: Semigroup,
+    {
+        let head = f(self.head);
+        self.tail
+            .into_iter()
+            .fold(head, |acc, value| acc.mappend(f(value)))
+    }
+
+    fn foldr1<F>(mut self, f: F) -> A
+    where
+        F: Fn(A, A) -> A + 'a,
+    {
+        match self.tail.pop() {
+            None => self.head,
+            Some(last) => {
+                let acc = self
+                    .tail
+                    .into_iter()
+                    .rfold(last, |acc, value| f(value, acc));
+                f(self.head, acc)
+            }
+        }
+    }
+
+    fn foldl1<F>(self, f: F) -> A
+    where
+        F: Fn(A, A) -> A + 'a,
+    {
+        self.tail.into_iter().fold(self.head, f)
+    }
+}
+
+impl<'a, A: 'a> Traversable<'a, A> for NonEmpty<A> {
+    fn traverse<B, M, F>(self, f: F) -> M::Target<Self::Target<B>>
+    where
+        B: Clone + 'a,
+        M: Applicative<'a, B> + 'a,
+        F: Fn(A) -> M + 'a,
+
+        M::Target<Self::Target<B>>: Applicative<'a, Self::Target<B>, Target<Self::Target<B>> = M::Target<Self::Target<B>>>
+            + Applicative<
+                'a,
+                Self::Target<B>,
+                Target<ApplyFn<'a, B, Self::Target<B>>> = M::Target<
+                    ApplyFn<'a, B, Self::Target<B>>,
+                >,
+            >,
+    {
+        fn snoc<A>(mut list: NonEmpty<A>, value: A) -> NonEmpty<A> {
+            list.push(value);
+            list
+        }
+
+        let head = f(self.head).fmap(NonEmpty::singleton);
+        self.tail
+            .into_iter()
+            .fold(head, move |acc, value| lift2(&snoc, acc, f(value)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NonEmpty;
+    use crate::{
+        apply::{Apply, ApplyFn},
+        semigroup::{First, Last},
+        Bind, Foldable, Foldable1, Functor, Semigroup, Traversable,
+    };
+
+    #[test]
+    fn construct() {
+        assert_eq!(NonEmpty::<i32>::from_vec(vec![]), None);
+        let list = NonEmpty::from_vec(vec![1, 2, 3]).unwrap();
+        assert_eq!(list, NonEmpty::new(1, vec![2, 3]));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.first(), &1);
+        assert_eq!(list.last(), &3);
+        assert_eq!(list.into_vec(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn functor_and_bind() {
+        let list = NonEmpty::new(1, vec![2, 3]);
+        assert_eq!(list.clone().fmap(|x| x * 2), NonEmpty::new(2, vec![4, 6]));
+        assert_eq!(
+            list.bind(|x| NonEmpty::new(x, vec![x * 10])),
+            NonEmpty::new(1, vec![10, 2, 20, 3, 30])
+        );
+    }
+
+    #[test]
+    fn apply() {
+        let list = NonEmpty::new(1, vec![2]);
+        let f = NonEmpty::new(
+            ApplyFn::from(|x: i32| x + 1),
+            vec![ApplyFn::from(|x: i32| x * 10)],
+        );
+        assert_eq!(list.apply(f), NonEmpty::new(2, vec![3, 10, 20]));
+    }
+
+    #[test]
+    fn fold() {
+        let list = NonEmpty::new(1, vec![2, 3, 4]);
+        assert_eq!(list.clone().foldl1(|acc, next| acc - next), -8);
+        assert_eq!(list.clone().foldr1(|next, acc| next - acc), -2);
+        assert_eq!(list.clone().foldl(|acc, next| acc + next, 0), 10);
+        assert_eq!(list.clone().fmap(First).fold1(), First(1));
+        assert_eq!(list.clone().fmap(Last).fold1(), Last(4));
+        assert_eq!(list.clone().maximum(), 4);
+        assert_eq!(NonEmpty::singleton(5).foldr1(|a, b| a - b), 5);
+    }
+
+    #[test]
+    fn semigroup() {
+        assert_eq!(
+            NonEmpty::new(1, vec![2]).mappend(NonEmpty::new(3, vec![4])),
+            NonEmpty::new(1, vec![2, 3, 4])
+        );
+    }
+
+    #[test]
+    fn traverse() {
+        let list = NonEmpty::new(1, vec![2, 3]);
+        assert_eq!(
+            list.clone().traverse(|x| Some(x * 2)),
+            Some(NonEmpty::new(2, vec![4, 6]))
+        );
+        assert_eq!(list.traverse(|x| if x == 2 { None } else { Some(x) }), None);
+    }
+}
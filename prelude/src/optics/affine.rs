@@ -0,0 +1,122 @@
+use std::rc::Rc;
+
+use super::{Iso, Lens, Prism, Then};
+
+/// An affine traversal focuses on an `A` which may or may not be inside an
+/// `S`.
+///
+/// It's what you get when you compose a [`Lens`](Lens) with a
+/// [`Prism`](Prism): like a prism, it might not find anything, and like a
+/// lens, it can't build an `S` out of an `A` alone.
+///
+/// ```
+/// # use higher::optics::{Lens, Prism, Then};
+/// let first_some = Lens::<(Option<i32>, char), _>::first().then(Prism::some());
+/// assert_eq!(first_some.preview(&(Some(1), 'a')), Some(1));
+/// assert_eq!(first_some.preview(&(None, 'a')), None);
+/// assert_eq!(first_some.set((Some(1), 'a'), 2), (Some(2), 'a'));
+/// assert_eq!(first_some.set((None, 'a'), 2), (None, 'a'));
+/// ```
+pub struct AffineTraversal<'a, S, A> {
+    pub(super) preview: Rc<dyn Fn(&S) -> Option<A> + 'a>,
+    pub(super) set: Rc<dyn Fn(S, A) -> S + 'a>,
+}
+
+impl<'a, S, A> AffineTraversal<'a, S, A> {
+    /// Construct an `AffineTraversal` from a partial getter and a setter.
+    /// The setter should leave the `S` unchanged if it has no `A` in it.
+    pub fn new<P, U>(preview: P, set: U) -> Self
+    where
+        P: Fn(&S) -> Option<A> + 'a,
+        U: Fn(S, A) -> S + 'a,
+    {
+        Self {
+            preview: Rc::new(preview),
+            set: Rc::new(set),
+        }
+    }
+
+    /// Get the `A` inside an `S`, if there is one.
+    pub fn preview(&self, s: &S) -> Option<A> {
+        (self.preview)(s)
+    }
+
+    /// Replace the `A` inside an `S`, if there is one.
+    pub fn set(&self, s: S, a: A) -> S {
+        (self.set)(s, a)
+    }
+
+    /// Update the `A` inside an `S` by applying a function to it, if there
+    /// is one.
+    pub fn over<F>(&self, s: S, f: F) -> S
+    where
+        F: FnOnce(A) -> A,
+    {
+        match self.preview(&s) {
+            Some(a) => self.set(s, f(a)),
+            None => s,
+        }
+    }
+}
+
+impl<'a, S, A> Clone for AffineTraversal<'a, S, A> {
+    fn clone(&self) -> Self {
+        Self {
+            preview: self.preview.clone(),
+            set: self.set.clone(),
+        }
+    }
+}
+
+impl<'a, S, A> std::fmt::Debug for AffineTraversal<'a, S, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "AffineTraversal<{}, {}>",
+            std::any::type_name::<S>(),
+            std::any::type_name::<A>()
+        )
+    }
+}
+
+impl<'a, S: 'a, A: 'a> From<Lens<'a, S, A>> for AffineTraversal<'a, S, A> {
+    fn from(lens: Lens<'a, S, A>) -> Self {
+        let view = lens.view;
+        AffineTraversal {
+            preview: Rc::new(move |s| Some(view(s))),
+            set: lens.set,
+        }
+    }
+}
+
+impl<'a, S: 'a, A: 'a> From<Prism<'a, S, A>> for AffineTraversal<'a, S, A> {
+    fn from(prism: Prism<'a, S, A>) -> Self {
+        let set = prism.clone();
+        AffineTraversal {
+            preview: prism.preview,
+            set: Rc::new(move |s, a| set.set(s, a)),
+        }
+    }
+}
+
+impl<'a, S: 'a, A: 'a> From<Iso<'a, S, A>> for AffineTraversal<'a, S, A> {
+    fn from(iso: Iso<'a, S, A>) -> Self {
+        Lens::from(iso).into()
+    }
+}
+
+impl<'a, S: 'a, A: 'a, B: 'a> Then<AffineTraversal<'a, A, B>> for AffineTraversal<'a, S, A> {
+    type Output = AffineTraversal<'a, S, B>;
+
+    fn then(self, other: AffineTraversal<'a, A, B>) -> Self::Output {
+        let outer = self.clone();
+        let inner = other.clone();
+        AffineTraversal::new(
+            move |s: &S| self.preview(s).and_then(|a| other.preview(&a)),
+            move |s, b| match outer.preview(&s) {
+                Some(a) => outer.set(s, inner.set(a, b)),
+                None => s,
+            },
+        )
+    }
+}
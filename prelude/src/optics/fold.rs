@@ -0,0 +1,117 @@
+use std::rc::Rc;
+
+use crate::Monoid;
+
+use super::{AffineTraversal, Iso, Lens, Prism, Then, Traversal};
+
+/// A fold focuses on any number of `A`s inside an `S`, but can only look at
+/// them, not update them.
+///
+/// ```
+/// # use higher::optics::{Fold, Lens, Then, Traversal};
+/// let lengths = Traversal::<Vec<String>, String>::traversed()
+///     .then(Fold::new(|s: &String| vec![s.len()]));
+/// let words = vec!["one".to_string(), "three".to_string()];
+/// assert_eq!(lengths.to_list_of(&words), vec![3, 5]);
+/// assert_eq!(lengths.fold_map_of(&words, |n| n.to_string()), "35");
+/// ```
+pub struct Fold<'a, S, A> {
+    pub(super) fold: Rc<dyn Fn(&S) -> Vec<A> + 'a>,
+}
+
+impl<'a, S, A> Fold<'a, S, A> {
+    /// Construct a `Fold` from a function listing the `A`s inside an `S`.
+    pub fn new<F>(fold: F) -> Self
+    where
+        F: Fn(&S) -> Vec<A> + 'a,
+    {
+        Self {
+            fold: Rc::new(fold),
+        }
+    }
+
+    /// Get all of the `A`s inside an `S`.
+    pub fn to_list_of(&self, s: &S) -> Vec<A> {
+        (self.fold)(s)
+    }
+
+    /// Get the first `A` inside an `S`, if there is one.
+    pub fn preview(&self, s: &S) -> Option<A> {
+        self.to_list_of(s).into_iter().next()
+    }
+
+    /// Map every `A` inside an `S` into a [`Monoid`](Monoid) and combine the
+    /// results.
+    pub fn fold_map_of<M, F>(&self, s: &S, f: F) -> M
+    where
+        M: Monoid,
+        F: Fn(A) -> M,
+    {
+        self.to_list_of(s)
+            .into_iter()
+            .fold(M::default(), |acc, a| acc.mappend(f(a)))
+    }
+}
+
+impl<'a, S, A> Clone for Fold<'a, S, A> {
+    fn clone(&self) -> Self {
+        Self {
+            fold: self.fold.clone(),
+        }
+    }
+}
+
+impl<'a, S, A> std::fmt::Debug for Fold<'a, S, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Fold<{}, {}>",
+            std::any::type_name::<S>(),
+            std::any::type_name::<A>()
+        )
+    }
+}
+
+impl<'a, S: Clone + 'a, A: 'a> From<Traversal<'a, S, A>> for Fold<'a, S, A> {
+    fn from(traversal: Traversal<'a, S, A>) -> Self {
+        Fold::new(move |s: &S| traversal.to_list_of(s.clone()))
+    }
+}
+
+impl<'a, S: 'a, A: 'a> From<AffineTraversal<'a, S, A>> for Fold<'a, S, A> {
+    fn from(affine: AffineTraversal<'a, S, A>) -> Self {
+        let preview = affine.preview;
+        Fold::new(move |s| preview(s).into_iter().collect())
+    }
+}
+
+impl<'a, S: 'a, A: 'a> From<Lens<'a, S, A>> for Fold<'a, S, A> {
+    fn from(lens: Lens<'a, S, A>) -> Self {
+        AffineTraversal::from(lens).into()
+    }
+}
+
+impl<'a, S: 'a, A: 'a> From<Prism<'a, S, A>> for Fold<'a, S, A> {
+    fn from(prism: Prism<'a, S, A>) -> Self {
+        AffineTraversal::from(prism).into()
+    }
+}
+
+impl<'a, S: 'a, A: 'a> From<Iso<'a, S, A>> for Fold<'a, S, A> {
+    fn from(iso: Iso<'a, S, A>) -> Self {
+        AffineTraversal::from(iso).into()
+    }
+}
+
+impl<'a, S: 'a, A: 'a, B: 'a> Then<Fold<'a, A, B>> for Fold<'a, S, A> {
+    type Output = Fold<'a, S, B>;
+
+    fn then(self, other: Fold<'a, A, B>) -> Self::Output {
+        Fold::new(move |s: &S| {
+            self.to_list_of(s)
+                .iter()
+                .flat_map(|a| other.to_list_of(a))
+                .collect()
+        })
+    }
+}
@@ -0,0 +1,120 @@
+use std::rc::Rc;
+
+use crate::Profunctor;
+
+use super::Then;
+
+/// An isomorphism between `S` and `A`.
+///
+/// An `Iso` says that `S` and `A` are two ways of looking at the same thing:
+/// you can [`view`](Iso::view) any `S` as an `A`, and
+/// [`review`](Iso::review) any `A` back into an `S`, without losing anything
+/// along the way.
+///
+/// ```
+/// # use higher::optics::Iso;
+/// let chars = Iso::new(
+///     |s: &String| s.chars().collect::<Vec<_>>(),
+///     |chars: Vec<char>| chars.into_iter().collect::<String>(),
+/// );
+/// assert_eq!(chars.view(&"abc".to_string()), vec!['a', 'b', 'c']);
+/// assert_eq!(chars.over("abc".to_string(), |mut cs| { cs.reverse(); cs }), "cba");
+/// ```
+pub struct Iso<'a, S, A> {
+    pub(super) view: Rc<dyn Fn(&S) -> A + 'a>,
+    pub(super) review: Rc<dyn Fn(A) -> S + 'a>,
+}
+
+impl<'a, S, A> Iso<'a, S, A> {
+    /// Construct an `Iso` from a pair of functions, which should be each
+    /// other's inverse.
+    pub fn new<V, R>(view: V, review: R) -> Self
+    where
+        V: Fn(&S) -> A + 'a,
+        R: Fn(A) -> S + 'a,
+    {
+        Self {
+            view: Rc::new(view),
+            review: Rc::new(review),
+        }
+    }
+
+    /// View an `S` as an `A`.
+    pub fn view(&self, s: &S) -> A {
+        (self.view)(s)
+    }
+
+    /// Turn an `A` back into an `S`.
+    pub fn review(&self, a: A) -> S {
+        (self.review)(a)
+    }
+
+    /// Replace an `S` with the `S` corresponding to an `A`.
+    pub fn set(&self, _s: S, a: A) -> S {
+        self.review(a)
+    }
+
+    /// Update an `S` by applying a function to its `A`.
+    pub fn over<F>(&self, s: S, f: F) -> S
+    where
+        F: FnOnce(A) -> A,
+    {
+        self.review(f(self.view(&s)))
+    }
+}
+
+impl<'a, S: 'a, A: 'a> Iso<'a, S, A> {
+    /// Turn the `Iso` around, so that it goes from `A` to `S` instead.
+    pub fn reverse(self) -> Iso<'a, A, S>
+    where
+        A: Clone,
+    {
+        let view = self.view;
+        let review = self.review;
+        Iso::new(move |a: &A| review(a.clone()), move |s| view(&s))
+    }
+
+    /// Apply the `Iso` to a [`Profunctor`](Profunctor) over `A`, turning
+    /// it into a profunctor over `S`.
+    pub fn transform<P>(&self, p: P) -> P::Target<S, S>
+    where
+        P: Profunctor<'a, A, A>,
+    {
+        let view = self.view.clone();
+        let review = self.review.clone();
+        p.dimap(move |s: S| view(&s), move |a| review(a))
+    }
+}
+
+impl<'a, S, A> Clone for Iso<'a, S, A> {
+    fn clone(&self) -> Self {
+        Self {
+            view: self.view.clone(),
+            review: self.review.clone(),
+        }
+    }
+}
+
+impl<'a, S, A> std::fmt::Debug for Iso<'a, S, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Iso<{}, {}>",
+            std::any::type_name::<S>(),
+            std::any::type_name::<A>()
+        )
+    }
+}
+
+impl<'a, S: 'a, A: 'a, B: 'a> Then<Iso<'a, A, B>> for Iso<'a, S, A> {
+    type Output = Iso<'a, S, B>;
+
+    fn then(self, other: Iso<'a, A, B>) -> Self::Output {
+        let outer = self.clone();
+        let inner = other.clone();
+        Iso::new(
+            move |s: &S| other.view(&self.view(s)),
+            move |b| outer.review(inner.review(b)),
+        )
+    }
+}
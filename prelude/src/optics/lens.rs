@@ -0,0 +1,150 @@
+use std::rc::Rc;
+
+use crate::{Functor, Profunctor, Strong};
+
+use super::{Iso, Then};
+
+/// A lens focuses on exactly one `A` inside an `S`.
+///
+/// A `Lens` is a getter and a setter bundled together: it can
+/// [`view`](Lens::view) the `A` inside an `S`, and [`set`](Lens::set) it to
+/// a new value, giving you back an updated `S`.
+///
+/// ```
+/// # use higher::optics::Lens;
+/// #[derive(Debug, PartialEq)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let x = Lens::new(|p: &Point| p.x, |p, x| Point { x, ..p });
+/// let point = Point { x: 1, y: 2 };
+/// assert_eq!(x.view(&point), 1);
+/// assert_eq!(x.over(point, |x| x + 10), Point { x: 11, y: 2 });
+/// ```
+pub struct Lens<'a, S, A> {
+    pub(super) view: Rc<dyn Fn(&S) -> A + 'a>,
+    pub(super) set: Rc<dyn Fn(S, A) -> S + 'a>,
+}
+
+impl<'a, S, A> Lens<'a, S, A> {
+    /// Construct a `Lens` from a getter and a setter.
+    pub fn new<V, U>(view: V, set: U) -> Self
+    where
+        V: Fn(&S) -> A + 'a,
+        U: Fn(S, A) -> S + 'a,
+    {
+        Self {
+            view: Rc::new(view),
+            set: Rc::new(set),
+        }
+    }
+
+    /// Get the `A` inside an `S`.
+    pub fn view(&self, s: &S) -> A {
+        (self.view)(s)
+    }
+
+    /// Replace the `A` inside an `S`.
+    pub fn set(&self, s: S, a: A) -> S {
+        (self.set)(s, a)
+    }
+
+    /// Update the `A` inside an `S` by applying a function to it.
+    pub fn over<F>(&self, s: S, f: F) -> S
+    where
+        F: FnOnce(A) -> A,
+    {
+        let a = self.view(&s);
+        self.set(s, f(a))
+    }
+}
+
+impl<'a, S: 'a, A: 'a> Lens<'a, S, A> {
+    /// Apply an effectful function to the `A` inside an `S`, and put the
+    /// result back into the `S` inside the [`Functor`](Functor).
+    pub fn traverse_of<M, F>(&self, s: S, f: F) -> M::Target<S>
+    where
+        S: Clone,
+        M: Functor<'a, A>,
+        F: FnOnce(A) -> M,
+    {
+        let set = self.set.clone();
+        f(self.view(&s)).fmap(move |a| set(s.clone(), a))
+    }
+
+    /// Apply the `Lens` to a [`Strong`](Strong) profunctor over `A`, turning
+    /// it into a profunctor over `S`.
+    pub fn transform<P>(&self, p: P) -> P::Target<S, S>
+    where
+        S: Clone,
+        P: Strong<'a, A, A>,
+        P::Target<(A, S), (A, S)>: Profunctor<'a, (A, S), (A, S), Target<S, S> = P::Target<S, S>>,
+    {
+        let view = self.view.clone();
+        let set = self.set.clone();
+        p.first::<S>()
+            .dimap(move |s: S| (view(&s), s), move |(a, s)| set(s, a))
+    }
+}
+
+impl<'a, A: Clone + 'a, B: 'a> Lens<'a, (A, B), A> {
+    /// A `Lens` focusing on the first element of a pair.
+    pub fn first() -> Self {
+        Lens::new(|pair: &(A, B)| pair.0.clone(), |(_, b), a| (a, b))
+    }
+}
+
+impl<'a, A: 'a, B: Clone + 'a> Lens<'a, (A, B), B> {
+    /// A `Lens` focusing on the second element of a pair.
+    pub fn second() -> Self {
+        Lens::new(|pair: &(A, B)| pair.1.clone(), |(a, _), b| (a, b))
+    }
+}
+
+impl<'a, S, A> Clone for Lens<'a, S, A> {
+    fn clone(&self) -> Self {
+        Self {
+            view: self.view.clone(),
+            set: self.set.clone(),
+        }
+    }
+}
+
+impl<'a, S, A> std::fmt::Debug for Lens<'a, S, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Lens<{}, {}>",
+            std::any::type_name::<S>(),
+            std::any::type_name::<A>()
+        )
+    }
+}
+
+impl<'a, S: 'a, A: 'a> From<Iso<'a, S, A>> for Lens<'a, S, A> {
+    fn from(iso: Iso<'a, S, A>) -> Self {
+        let review = iso.review;
+        Lens {
+            view: iso.view,
+            set: Rc::new(move |_, a| review(a)),
+        }
+    }
+}
+
+impl<'a, S: 'a, A: 'a, B: 'a> Then<Lens<'a, A, B>> for Lens<'a, S, A> {
+    type Output = Lens<'a, S, B>;
+
+    fn then(self, other: Lens<'a, A, B>) -> Self::Output {
+        let outer = self.clone();
+        let inner = other.clone();
+        Lens::new(
+            move |s: &S| other.view(&self.view(s)),
+            move |s, b| {
+                let a = outer.view(&s);
+                outer.set(s, inner.set(a, b))
+            },
+        )
+    }
+}
@@ -0,0 +1,249 @@
+//! Optics for looking into and updating parts of larger structures.
+//!
+//! An optic focuses on some part `A` of a structure `S`. The different
+//! kinds of optic differ in how many `A`s they can find inside an `S`, and
+//! whether they can rebuild an `S` from them:
+//!
+//! | Optic                                | Focuses on   | Can update | Can build an `S` |
+//! | ------------------------------------ | ------------ | ---------- | ---------------- |
+//! | [`Iso`](Iso)                         | exactly one  | yes        | yes              |
+//! | [`Lens`](Lens)                       | exactly one  | yes        | no               |
+//! | [`Prism`](Prism)                     | zero or one  | yes        | yes              |
+//! | [`AffineTraversal`](AffineTraversal) | zero or one  | yes        | no               |
+//! | [`Traversal`](Traversal)             | zero or more | yes        | no               |
+//! | [`Fold`](Fold)                       | zero or more | no         | no               |
+//!
+//! Any two optics can be composed using [`Then::then`](Then::then), which
+//! focuses the second optic on whatever the first optic found. The result
+//! is the most capable kind of optic both of them can be turned into: a
+//! [`Lens`](Lens) followed by a [`Prism`](Prism) gives you an
+//! [`AffineTraversal`](AffineTraversal), for instance.
+//!
+//! ```
+//! # use higher::optics::{Lens, Prism, Then, Traversal};
+//! #[derive(Clone, Debug, PartialEq)]
+//! struct Player {
+//!     name: String,
+//!     scores: Vec<Option<u32>>,
+//! }
+//!
+//! let scores = Lens::new(
+//!     |player: &Player| player.scores.clone(),
+//!     |player, scores| Player { scores, ..player },
+//! );
+//! let all_scores = scores.then(Traversal::traversed()).then(Prism::some());
+//!
+//! let player = Player {
+//!     name: "Alice".to_string(),
+//!     scores: vec![Some(1), None, Some(3)],
+//! };
+//! assert_eq!(all_scores.to_list_of(player.clone()), vec![1, 3]);
+//! assert_eq!(
+//!     all_scores.over(player, |score| score * 10).scores,
+//!     vec![Some(10), None, Some(30)]
+//! );
+//! ```
+//!
+//! The optics here are concrete: a [`Lens`](Lens) is simply a getter and a
+//! setter, a [`Prism`](Prism) a matcher and a constructor, and so on, rather
+//! than a function polymorphic over functors or profunctors as in the van
+//! Laarhoven and profunctor encodings. Composition is provided by
+//! [`Then`](Then) instead of plain function composition, and every optic
+//! has just the two type parameters `S` and `A`, so an update can't change
+//! the type of the focus, and with it the type of the structure, the way a
+//! `Lens s t a b` can in Haskell.
+//!
+//! [`Iso`](Iso)s, [`Lens`](Lens)es and [`Prism`](Prism)s can still be applied
+//! to [`Profunctor`](crate::Profunctor)s using their `transform` methods,
+//! turning a [`Strong`](crate::Strong) or [`Choice`](crate::Choice)
+//! profunctor over `A` into one over `S`, just like their profunctor
+//! encoded counterparts would.
+
+mod affine;
+mod fold;
+mod iso;
+mod lens;
+mod prism;
+mod traversal;
+
+#[doc(inline)]
+pub use self::affine::AffineTraversal;
+#[doc(inline)]
+pub use self::fold::Fold;
+#[doc(inline)]
+pub use self::iso::Iso;
+#[doc(inline)]
+pub use self::lens::Lens;
+#[doc(inline)]
+pub use self::prism::Prism;
+#[doc(inline)]
+pub use self::traversal::Traversal;
+
+/// Composition of optics.
+///
+/// `first.then(second)` is an optic which focuses on whatever `second`
+/// focuses on inside whatever `first` focuses on. Optics of the same kind
+/// compose into that same kind; otherwise, both are converted into the
+/// least capable of the two, or into an
+/// [`AffineTraversal`](AffineTraversal) when composing a
+/// [`Lens`](Lens) with a [`Prism`](Prism).
+pub trait Then<O> {
+    /// The kind of optic the composition produces.
+    type Output;
+
+    /// Compose this optic with another, which focuses inside it.
+    fn then(self, other: O) -> Self::Output;
+}
+
+macro_rules! impl_then {
+    ($($left:ident, $right:ident => $output:ident;)*) => {
+        $(
+            impl<'a, S, A, B> Then<$right<'a, A, B>> for $left<'a, S, A>
+            where
+                S: Clone + 'a,
+                A: Clone + 'a,
+                B: Clone + 'a,
+            {
+                type Output = $output<'a, S, B>;
+
+                fn then(self, other: $right<'a, A, B>) -> Self::Output {
+                    $output::from(self).then($output::from(other))
+                }
+            }
+        )*
+    };
+}
+
+impl_then! {
+    Iso, Lens => Lens;
+    Iso, Prism => Prism;
+    Iso, AffineTraversal => AffineTraversal;
+    Iso, Traversal => Traversal;
+    Iso, Fold => Fold;
+
+    Lens, Iso => Lens;
+    Lens, Prism => AffineTraversal;
+    Lens, AffineTraversal => AffineTraversal;
+    Lens, Traversal => Traversal;
+    Lens, Fold => Fold;
+
+    Prism, Iso => Prism;
+    Prism, Lens => AffineTraversal;
+    Prism, AffineTraversal => AffineTraversal;
+    Prism, Traversal => Traversal;
+    Prism, Fold => Fold;
+
+    AffineTraversal, Iso => AffineTraversal;
+    AffineTraversal, Lens => AffineTraversal;
+    AffineTraversal, Prism => AffineTraversal;
+    AffineTraversal, Traversal => Traversal;
+    AffineTraversal, Fold => Fold;
+
+    Traversal, Iso => Traversal;
+    Traversal, Lens => Traversal;
+    Traversal, Prism => Traversal;
+    Traversal, AffineTraversal => Traversal;
+    Traversal, Fold => Fold;
+
+    Fold, Iso => Fold;
+    Fold, Lens => Fold;
+    Fold, Prism => Fold;
+    Fold, AffineTraversal => Fold;
+    Fold, Traversal => Fold;
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AffineTraversal, Fold, Iso, Lens, Prism, Then, Traversal};
+    use crate::{func::Func, star::Star};
+
+    #[test]
+    fn composes_lenses_and_isos() {
+        let negated = Iso::new(|x: &i32| -x, |x: i32| -x);
+        let lens = Lens::<((i32, char), bool), _>::first()
+            .then(Lens::first())
+            .then(negated);
+        let value = ((1, 'a'), true);
+        assert_eq!(lens.view(&value), -1);
+        assert_eq!(lens.set(value, 5), ((-5, 'a'), true));
+        assert_eq!(
+            lens.traverse_of(value, |x| if x < 0 { Some(x * 2) } else { None }),
+            Some(((2, 'a'), true))
+        );
+        assert_eq!(lens.traverse_of(value, |x| vec![x, 0]).len(), 2);
+    }
+
+    #[test]
+    fn composes_prisms_into_affine_traversals() {
+        let prism = Prism::<Result<Option<i32>, ()>, _>::ok().then(Prism::some());
+        assert_eq!(prism.review(1), Ok(Some(1)));
+        assert_eq!(prism.preview(&Ok(None)), None);
+
+        let affine: AffineTraversal<'_, (i32, (Result<i32, String>, u8)), String> =
+            Lens::second().then(Lens::first()).then(Prism::err());
+        assert_eq!(
+            affine.preview(&(5, (Err("no".to_string()), 1))).as_deref(),
+            Some("no")
+        );
+        assert_eq!(
+            affine.set((5, (Ok(1), 1)), "x".to_string()),
+            (5, (Ok(1), 1))
+        );
+    }
+
+    #[test]
+    fn traverses_any_traversable() {
+        let nested = Traversal::<Vec<Vec<i32>>, Vec<i32>>::traversed().then(Traversal::traversed());
+        let value = vec![vec![1, 2], vec![], vec![3]];
+        assert_eq!(nested.to_list_of(value.clone()), vec![1, 2, 3]);
+        assert_eq!(
+            nested.over(value.clone(), |x| x * 10),
+            vec![vec![10, 20], vec![], vec![30]]
+        );
+        assert_eq!(
+            nested.traverse_of(value, |x| if x > 0 { Ok(x) } else { Err(x) }),
+            Ok(vec![vec![1, 2], vec![], vec![3]])
+        );
+
+        let option = Traversal::<Option<i32>, i32>::traversed();
+        assert_eq!(option.set(Some(1), 2), Some(2));
+        assert_eq!(option.set(None, 2), None);
+        let result = Traversal::<Result<i32, ()>, i32>::traversed();
+        assert_eq!(result.to_list_of(Ok(3)), vec![3]);
+    }
+
+    #[test]
+    fn folds_see_through_everything() {
+        let fold: Fold<'_, Vec<(i32, char)>, char> = Traversal::traversed()
+            .then(Lens::second())
+            .then(Fold::new(|c: &char| vec![*c, *c]));
+        assert_eq!(
+            fold.to_list_of(&vec![(1, 'a'), (2, 'b')]),
+            vec!['a', 'a', 'b', 'b']
+        );
+        assert_eq!(fold.preview(&vec![]), None);
+    }
+
+    #[test]
+    fn transforms_profunctors() {
+        let lens = Lens::<(i32, char), _>::first();
+        assert_eq!(
+            lens.transform(Func::new(|x| x + 1)).call((1, 'a')),
+            (2, 'a')
+        );
+        let halve = Star::new(|x: i32| if x % 2 == 0 { Some(x / 2) } else { None });
+        assert_eq!(lens.transform(halve.clone()).run((4, 'a')), Some((2, 'a')));
+        assert_eq!(lens.transform(halve.clone()).run((3, 'a')), None);
+
+        let prism = Prism::<Option<i32>, _>::some();
+        assert_eq!(prism.transform(Func::new(|x| x * 3)).call(Some(2)), Some(6));
+        assert_eq!(prism.transform(halve).run(None), Some(None));
+
+        let iso = Iso::new(|s: &String| s.len(), |n: usize| "x".repeat(n));
+        assert_eq!(
+            iso.transform(Func::new(|n| n + 1)).call("ab".to_string()),
+            "xxx"
+        );
+        assert_eq!(iso.reverse().view(&2), "xx");
+    }
+}
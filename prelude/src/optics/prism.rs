@@ -0,0 +1,159 @@
+use std::rc::Rc;
+
+use crate::{Choice, Profunctor};
+
+use super::{Iso, Then};
+
+/// A prism focuses on an `A` which may or may not be inside an `S`, and
+/// which can always be turned into an `S` on its own.
+///
+/// A `Prism` usually picks out one variant of an enum: it can
+/// [`preview`](Prism::preview) an `S` to see if it's that variant, and
+/// [`review`](Prism::review) an `A` to construct it.
+///
+/// ```
+/// # use higher::optics::Prism;
+/// let ok = Prism::<Result<i32, String>, i32>::ok();
+/// assert_eq!(ok.preview(&Ok(5)), Some(5));
+/// assert_eq!(ok.preview(&Err("nope".to_string())), None);
+/// assert_eq!(ok.review(5), Ok(5));
+/// assert_eq!(ok.over(Ok(5), |x| x * 2), Ok(10));
+/// ```
+pub struct Prism<'a, S, A> {
+    pub(super) preview: Rc<dyn Fn(&S) -> Option<A> + 'a>,
+    pub(super) review: Rc<dyn Fn(A) -> S + 'a>,
+}
+
+impl<'a, S, A> Prism<'a, S, A> {
+    /// Construct a `Prism` from a partial getter and a constructor.
+    pub fn new<P, R>(preview: P, review: R) -> Self
+    where
+        P: Fn(&S) -> Option<A> + 'a,
+        R: Fn(A) -> S + 'a,
+    {
+        Self {
+            preview: Rc::new(preview),
+            review: Rc::new(review),
+        }
+    }
+
+    /// Get the `A` inside an `S`, if there is one.
+    pub fn preview(&self, s: &S) -> Option<A> {
+        (self.preview)(s)
+    }
+
+    /// Construct an `S` from an `A`.
+    pub fn review(&self, a: A) -> S {
+        (self.review)(a)
+    }
+
+    /// Replace the `A` inside an `S`, if there is one. Otherwise, the `S` is
+    /// returned unchanged.
+    pub fn set(&self, s: S, a: A) -> S {
+        match self.preview(&s) {
+            Some(_) => self.review(a),
+            None => s,
+        }
+    }
+
+    /// Update the `A` inside an `S` by applying a function to it, if there
+    /// is one.
+    pub fn over<F>(&self, s: S, f: F) -> S
+    where
+        F: FnOnce(A) -> A,
+    {
+        match self.preview(&s) {
+            Some(a) => self.review(f(a)),
+            None => s,
+        }
+    }
+}
+
+impl<'a, S: 'a, A: 'a> Prism<'a, S, A> {
+    /// Apply the `Prism` to a [`Choice`](Choice) profunctor over `A`,
+    /// turning it into a profunctor over `S`.
+    pub fn transform<P>(&self, p: P) -> P::Target<S, S>
+    where
+        S: Clone,
+        P: Choice<'a, A, A>,
+        P::Target<Result<A, S>, Result<A, S>>:
+            Profunctor<'a, Result<A, S>, Result<A, S>, Target<S, S> = P::Target<S, S>>,
+    {
+        let preview = self.preview.clone();
+        let review = self.review.clone();
+        p.left::<S>().dimap(
+            move |s: S| match preview(&s) {
+                Some(a) => Ok(a),
+                None => Err(s),
+            },
+            move |result| match result {
+                Ok(a) => review(a),
+                Err(s) => s,
+            },
+        )
+    }
+}
+
+impl<'a, A: Clone + 'a> Prism<'a, Option<A>, A> {
+    /// A `Prism` focusing on the contents of a [`Some`](Option::Some).
+    pub fn some() -> Self {
+        Prism::new(Option::clone, Some)
+    }
+}
+
+impl<'a, A: Clone + 'a, E: 'a> Prism<'a, Result<A, E>, A> {
+    /// A `Prism` focusing on the contents of an [`Ok`](Result::Ok).
+    pub fn ok() -> Self {
+        Prism::new(|result: &Result<A, E>| result.as_ref().ok().cloned(), Ok)
+    }
+}
+
+impl<'a, A: 'a, E: Clone + 'a> Prism<'a, Result<A, E>, E> {
+    /// A `Prism` focusing on the contents of an [`Err`](Result::Err).
+    pub fn err() -> Self {
+        Prism::new(|result: &Result<A, E>| result.as_ref().err().cloned(), Err)
+    }
+}
+
+impl<'a, S, A> Clone for Prism<'a, S, A> {
+    fn clone(&self) -> Self {
+        Self {
+            preview: self.preview.clone(),
+            review: self.review.clone(),
+        }
+    }
+}
+
+impl<'a, S, A> std::fmt::Debug for Prism<'a, S, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Prism<{}, {}>",
+            std::any::type_name::<S>(),
+            std::any::type_name::<A>()
+        )
+    }
+}
+
+impl<'a, S: 'a, A: 'a> From<Iso<'a, S, A>> for Prism<'a, S, A> {
+    fn from(iso: Iso<'a, S, A>) -> Self {
+        let view = iso.view;
+        Prism {
+            preview: Rc::new(move |s| Some(view(s))),
+            review: iso.review,
+        }
+    }
+}
+
+impl<'a, S: 'a, A: 'a, B: 'a> Then<Prism<'a, A, B>> for Prism<'a, S, A> {
+    type Output = Prism<'a, S, B>;
+
+    fn then(self, other: Prism<'a, A, B>) -> Self::Output {
+        let outer = self.clone();
+        let inner = other.clone();
+        Prism::new(
+            move |s: &S| self.preview(s).and_then(|a| other.preview(&a)),
+            move |b| outer.review(inner.review(b)),
+        )
+    }
+}
@@ -0,0 +1,200 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{apply::ApplyFn, Applicative, Functor, Traversable};
+
+use super::{AffineTraversal, Iso, Lens, Prism, Then};
+
+/// A function which puts a list of parts back into the structure they came
+/// from.
+type Rebuild<'a, S, A> = Rc<dyn Fn(Vec<A>) -> S + 'a>;
+
+/// A traversal focuses on any number of `A`s inside an `S`.
+///
+/// A `Traversal` splits an `S` into the `A`s inside it, along with a way to
+/// put the `S` back together from a list of `A`s, which lets you
+/// [`over`](Traversal::over) all of the `A`s at once, or
+/// [`traverse_of`](Traversal::traverse_of) them with an
+/// [`Applicative`](Applicative) effect.
+///
+/// Any [`Traversable`](Traversable) comes with a `Traversal` over its
+/// elements, which you get from [`traversed`](Traversal::traversed).
+///
+/// ```
+/// # use higher::optics::Traversal;
+/// let each = Traversal::<Vec<i32>, i32>::traversed();
+/// assert_eq!(each.over(vec![1, 2, 3], |x| x * 2), vec![2, 4, 6]);
+/// assert_eq!(
+///     each.traverse_of(vec![1, 2, 3], |x| if x > 0 { Some(x) } else { None }),
+///     Some(vec![1, 2, 3])
+/// );
+/// assert_eq!(each.traverse_of(vec![1, -2, 3], |x| if x > 0 { Some(x) } else { None }), None);
+/// ```
+pub struct Traversal<'a, S, A> {
+    pub(super) parts: Rc<dyn Fn(S) -> (Vec<A>, Rebuild<'a, S, A>) + 'a>,
+}
+
+impl<'a, S, A> Traversal<'a, S, A> {
+    /// Construct a `Traversal` from a function which splits an `S` into its
+    /// `A`s and a function to rebuild it from them. The rebuilding function
+    /// will always be given as many `A`s as it handed out.
+    pub fn new<P, R>(parts: P) -> Self
+    where
+        P: Fn(S) -> (Vec<A>, R) + 'a,
+        R: Fn(Vec<A>) -> S + 'a,
+    {
+        Self {
+            parts: Rc::new(move |s| {
+                let (elements, rebuild) = parts(s);
+                (elements, Rc::new(rebuild) as Rebuild<'a, S, A>)
+            }),
+        }
+    }
+
+    /// Get all of the `A`s inside an `S`.
+    pub fn to_list_of(&self, s: S) -> Vec<A> {
+        (self.parts)(s).0
+    }
+
+    /// Update every `A` inside an `S` by applying a function to it.
+    pub fn over<F>(&self, s: S, f: F) -> S
+    where
+        F: Fn(A) -> A,
+    {
+        let (elements, rebuild) = (self.parts)(s);
+        rebuild(elements.into_iter().map(f).collect())
+    }
+
+    /// Replace every `A` inside an `S`.
+    pub fn set(&self, s: S, a: A) -> S
+    where
+        A: Clone,
+    {
+        self.over(s, |_| a.clone())
+    }
+}
+
+impl<'a, S: 'a, A: 'a> Traversal<'a, S, A> {
+    /// Apply an effectful function to every `A` inside an `S`, and put the
+    /// results back into the `S` inside the [`Applicative`](Applicative).
+    pub fn traverse_of<M, F>(&self, s: S, f: F) -> M::Target<S>
+    where
+        A: Clone,
+        M: Applicative<'a, A> + 'a,
+        F: Fn(A) -> M + 'a,
+        M::Target<Vec<A>>: Applicative<'a, Vec<A>, Target<Vec<A>> = M::Target<Vec<A>>>
+            + Applicative<
+                'a,
+                Vec<A>,
+                Target<ApplyFn<'a, A, Vec<A>>> = M::Target<ApplyFn<'a, A, Vec<A>>>,
+            > + Functor<'a, Vec<A>, Target<S> = M::Target<S>>,
+    {
+        let (elements, rebuild) = (self.parts)(s);
+        elements.traverse(f).fmap(move |elements| rebuild(elements))
+    }
+}
+
+impl<'a, T, A> Traversal<'a, T, A>
+where
+    T: Traversable<'a, A> + 'a,
+    T::Target<()>: Clone,
+    A: Clone + 'a,
+{
+    /// A `Traversal` focusing on every element of a
+    /// [`Traversable`](Traversable).
+    pub fn traversed() -> Self {
+        Traversal::new(|t: T| {
+            // Traversing with a writer pulls out the elements and leaves the
+            // shape of the structure behind, with `()` in place of each one.
+            let (elements, shape) = t.traverse(|a| (vec![a], ()));
+            let rebuild = move |elements: Vec<A>| {
+                let elements = RefCell::new(elements.into_iter());
+                shape.clone().fmap(move |()| {
+                    elements
+                        .borrow_mut()
+                        .next()
+                        .expect("Traversal rebuilt from too few elements")
+                })
+            };
+            (elements, rebuild)
+        })
+    }
+}
+
+impl<'a, S, A> Clone for Traversal<'a, S, A> {
+    fn clone(&self) -> Self {
+        Self {
+            parts: self.parts.clone(),
+        }
+    }
+}
+
+impl<'a, S, A> std::fmt::Debug for Traversal<'a, S, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Traversal<{}, {}>",
+            std::any::type_name::<S>(),
+            std::any::type_name::<A>()
+        )
+    }
+}
+
+impl<'a, S: Clone + 'a, A: 'a> From<AffineTraversal<'a, S, A>> for Traversal<'a, S, A> {
+    fn from(affine: AffineTraversal<'a, S, A>) -> Self {
+        Traversal::new(move |s: S| {
+            let affine = affine.clone();
+            let elements = affine.preview(&s).into_iter().collect();
+            let rebuild = move |mut elements: Vec<A>| match elements.pop() {
+                Some(a) => affine.set(s.clone(), a),
+                None => s.clone(),
+            };
+            (elements, rebuild)
+        })
+    }
+}
+
+impl<'a, S: Clone + 'a, A: 'a> From<Lens<'a, S, A>> for Traversal<'a, S, A> {
+    fn from(lens: Lens<'a, S, A>) -> Self {
+        AffineTraversal::from(lens).into()
+    }
+}
+
+impl<'a, S: Clone + 'a, A: 'a> From<Prism<'a, S, A>> for Traversal<'a, S, A> {
+    fn from(prism: Prism<'a, S, A>) -> Self {
+        AffineTraversal::from(prism).into()
+    }
+}
+
+impl<'a, S: Clone + 'a, A: 'a> From<Iso<'a, S, A>> for Traversal<'a, S, A> {
+    fn from(iso: Iso<'a, S, A>) -> Self {
+        AffineTraversal::from(iso).into()
+    }
+}
+
+impl<'a, S: 'a, A: 'a, B: 'a> Then<Traversal<'a, A, B>> for Traversal<'a, S, A> {
+    type Output = Traversal<'a, S, B>;
+
+    fn then(self, other: Traversal<'a, A, B>) -> Self::Output {
+        Traversal::new(move |s| {
+            let (outer, rebuild_outer) = (self.parts)(s);
+            let mut elements = Vec::new();
+            let mut rebuilds = Vec::new();
+            for a in outer {
+                let (inner, rebuild_inner) = (other.parts)(a);
+                rebuilds.push((inner.len(), rebuild_inner));
+                elements.extend(inner);
+            }
+            let rebuild = move |elements: Vec<B>| {
+                let mut elements = elements.into_iter();
+                let outer = rebuilds
+                    .iter()
+                    .map(|(count, rebuild_inner)| {
+                        rebuild_inner(elements.by_ref().take(*count).collect())
+                    })
+                    .collect();
+                rebuild_outer(outer)
+            };
+            (elements, rebuild)
+        })
+    }
+}
@@ -1,31 +1,70 @@
 use std::convert::identity;
 
+use crate::func::Func;
+
 /// A `Profunctor` is just a `Bifunctor` that is contravariant over its first
 /// argument and covariant over its second argument. What's the problem?
 pub trait Profunctor<'a, B: 'a, C: 'a> {
     type Target<T: 'a, U: 'a>: Profunctor<'a, T, U, Target<B, C> = Self>;
 
     /// Map a function over both arguments of the profunctor.
-    fn dimap<A: 'a, D: 'a, L: 'a, R: 'a>(self, left: L, right: R) -> Self::Target<A, D>
+    fn dimap<A: 'a, D: 'a, L, R>(self, left: L, right: R) -> Self::Target<A, D>
     where
-        L: Fn(A) -> B,
-        R: Fn(C) -> D;
+        L: Fn(A) -> B + 'a,
+        R: Fn(C) -> D + 'a;
 
     /// Map a function over the contravariant first argument only.
-    fn lcmap<A: 'a, L: 'a>(self, left: L) -> Self::Target<A, C>
+    fn lcmap<A: 'a, L>(self, left: L) -> Self::Target<A, C>
     where
         Self: Sized,
-        L: Fn(A) -> B,
+        L: Fn(A) -> B + 'a,
     {
         self.dimap(left, identity)
     }
 
     /// Map a function over the covariant second argument only.
-    fn rmap<D: 'a, R: 'a>(self, right: R) -> Self::Target<B, D>
+    fn rmap<D: 'a, R>(self, right: R) -> Self::Target<B, D>
     where
         Self: Sized,
-        R: Fn(C) -> D,
+        R: Fn(C) -> D + 'a,
     {
         self.dimap(identity, right)
     }
 }
+
+/// A `Strong` profunctor can carry an extra value alongside its input and
+/// output, untouched.
+///
+/// This is what a lens needs from a profunctor: to focus on one half of a
+/// pair, it works on the half it cares about and passes the other through.
+pub trait Strong<'a, A: 'a, B: 'a>: Profunctor<'a, A, B> {
+    /// Lift the profunctor to work on the first element of a pair.
+    fn first<C: Clone + 'a>(self) -> Self::Target<(A, C), (B, C)>;
+
+    /// Lift the profunctor to work on the second element of a pair.
+    fn second<C: Clone + 'a>(self) -> Self::Target<(C, A), (C, B)>;
+}
+
+/// A `Choice` profunctor can pass a value around itself when it's not the
+/// one it works on.
+///
+/// This is what a prism needs from a profunctor. The two alternatives are
+/// represented by [`Result`](Result): [`left`](Choice::left) works on the
+/// [`Ok`](Result::Ok) side and [`right`](Choice::right) on the
+/// [`Err`](Result::Err) side.
+pub trait Choice<'a, A: 'a, B: 'a>: Profunctor<'a, A, B> {
+    /// Lift the profunctor to work on the [`Ok`](Result::Ok) side of a
+    /// [`Result`](Result), passing [`Err`](Result::Err)s through.
+    fn left<C: Clone + 'a>(self) -> Self::Target<Result<A, C>, Result<B, C>>;
+
+    /// Lift the profunctor to work on the [`Err`](Result::Err) side of a
+    /// [`Result`](Result), passing [`Ok`](Result::Ok)s through.
+    fn right<C: Clone + 'a>(self) -> Self::Target<Result<C, A>, Result<C, B>>;
+}
+
+/// A `Closed` profunctor can work on the results of functions.
+pub trait Closed<'a, A: 'a, B: 'a>: Profunctor<'a, A, B> {
+    /// Lift the profunctor to work on functions into its input, by
+    /// post-composing it onto them.
+    fn closed<X: Clone + 'a>(self) -> Self::Target<Func<'a, X, A>, Func<'a, X, B>>;
+}
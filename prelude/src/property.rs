@@ -0,0 +1,642 @@
+//! Property based testing with integrated shrinking.
+//!
+//! A [`Gen`](Gen) is a random generator of values, which is a
+//! [`Monad`](crate::Monad), so you can build generators for your own types
+//! out of simpler ones using [`run!`](crate::run). Every generated value
+//! comes with a [`Tree`](Tree) of smaller versions of itself, and when
+//! [`check`](check) finds a value for which a property doesn't hold, it
+//! walks that tree to find the smallest value which still fails.
+//!
+//! ```
+//! # use higher::{property::{check, choose, vec_of}, run, Gen};
+//! let pairs: Gen<'_, (i32, Vec<i32>)> = run! {
+//!     x <= choose(0, 100);
+//!     xs <= vec_of(choose(0, x));
+//!     yield (x, xs)
+//! };
+//! assert!(check(&pairs, |(x, xs)| xs.iter().all(|y| *y <= x)).is_ok());
+//!
+//! let failure = check(&pairs, |(_, xs)| xs.len() < 5).unwrap_err();
+//! assert_eq!(failure.value, (0, vec![0, 0, 0, 0, 0]));
+//! ```
+
+use std::{fmt::Display, rc::Rc};
+
+use crate::{apply::ApplyFn, Alt, Apply, Bind, Functor, Pure};
+
+/// The seed used by [`Rng::default()`](Default::default).
+pub const DEFAULT_SEED: u64 = 0x5eed_5eed_5eed_5eed;
+
+/// The number of tests [`check`](check) runs.
+pub const TESTS: usize = 100;
+
+/// The size [`check`](check) grows its generators up to.
+pub const MAX_SIZE: usize = 100;
+
+/// The largest number of shrinking steps [`check`](check) will take.
+const MAX_SHRINKS: usize = 1000;
+
+/// A small, fast, seedable pseudo-random number generator.
+///
+/// This is an implementation of SplitMix64, which is not cryptographically
+/// secure in any way, but is more than good enough for generating test cases.
+/// The same seed always produces the same sequence of numbers.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Construct a generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Generate the next random `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Generate a random number from `0` up to, but not including, `bound`.
+    ///
+    /// Panics if `bound` is zero.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "Rng::below called with a bound of zero");
+        self.next_u64() % bound
+    }
+
+    /// Generate a random number in the inclusive range from `low` to `high`.
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low <= high, "Rng::range called with an empty range");
+        low + self.below((high - low) as u64 + 1) as i64
+    }
+
+    /// Generate a random `bool`.
+    pub fn bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    fn range_i128(&mut self, low: i128, high: i128) -> i128 {
+        let span = (high - low) as u128 + 1;
+        let offset = if span > u64::MAX as u128 {
+            self.next_u64()
+        } else {
+            self.below(span as u64)
+        };
+        low + offset as i128
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEED)
+    }
+}
+
+/// A rose tree of a value and ever smaller versions of it.
+///
+/// The children of a tree are the first steps towards a smaller value, in
+/// order of preference, and are only computed when asked for.
+pub struct Tree<'a, A> {
+    value: A,
+    children: Rc<dyn Fn() -> Vec<Tree<'a, A>> + 'a>,
+}
+
+impl<'a, A: 'a> Tree<'a, A> {
+    /// Construct a tree from a value and a function computing its children.
+    pub fn new<F>(value: A, children: F) -> Self
+    where
+        F: Fn() -> Vec<Tree<'a, A>> + 'a,
+    {
+        Self {
+            value,
+            children: Rc::new(children),
+        }
+    }
+
+    /// Construct a tree of a value which can't be shrunk.
+    pub fn leaf(value: A) -> Self {
+        Self::new(value, Vec::new)
+    }
+
+    /// Construct a tree from a value and a function which, given a value,
+    /// returns its immediate shrinks.
+    pub fn unfold<F>(value: A, shrink: F) -> Self
+    where
+        A: Clone,
+        F: Fn(&A) -> Vec<A> + 'a,
+    {
+        unfold_tree(value, Rc::new(shrink))
+    }
+
+    /// Get the value at the root of the tree.
+    pub fn value(&self) -> &A {
+        &self.value
+    }
+
+    /// Get the value at the root of the tree, discarding the rest.
+    pub fn into_value(self) -> A {
+        self.value
+    }
+
+    /// Compute the immediate children of the tree.
+    pub fn children(&self) -> Vec<Tree<'a, A>> {
+        (self.children)()
+    }
+}
+
+fn unfold_tree<'a, A, F>(value: A, shrink: Rc<F>) -> Tree<'a, A>
+where
+    A: Clone + 'a,
+    F: Fn(&A) -> Vec<A> + 'a,
+{
+    let candidates = shrink(&value);
+    Tree::new(value, move || {
+        candidates
+            .iter()
+            .cloned()
+            .map(|candidate| unfold_tree(candidate, shrink.clone()))
+            .collect()
+    })
+}
+
+fn map_tree<'a, A, B, F>(tree: Tree<'a, A>, f: Rc<F>) -> Tree<'a, B>
+where
+    A: 'a,
+    B: 'a,
+    F: Fn(A) -> B + 'a,
+{
+    let children = tree.children;
+    Tree::new(f(tree.value), move || {
+        children()
+            .into_iter()
+            .map(|child| map_tree(child, f.clone()))
+            .collect()
+    })
+}
+
+/// Bind every node of a tree to a generator. The root runs the generator
+/// from `rng`, and every shrink reruns it from a copy of the same state, so
+/// that shrinking the left hand side regenerates the right hand side from the
+/// same random numbers.
+fn bind_tree<'a, A, B, F>(tree: Tree<'a, A>, f: Rc<F>, rng: &mut Rng, size: usize) -> Tree<'a, B>
+where
+    A: 'a,
+    B: 'a,
+    F: Fn(A) -> Gen<'a, B> + 'a,
+{
+    let snapshot = rng.clone();
+    let right = f(tree.value).generate(rng, size);
+    let (left_children, right_children) = (tree.children, right.children);
+    Tree::new(right.value, move || {
+        let mut children: Vec<_> = left_children()
+            .into_iter()
+            .map(|child| bind_tree(child, f.clone(), &mut snapshot.clone(), size))
+            .collect();
+        children.extend(right_children());
+        children
+    })
+}
+
+impl<'a, A: Clone> Clone for Tree<'a, A> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            children: self.children.clone(),
+        }
+    }
+}
+
+impl<'a, A: std::fmt::Debug> std::fmt::Debug for Tree<'a, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tree")
+            .field("value", &self.value)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, A: 'a> Functor<'a, A> for Tree<'a, A> {
+    type Target<T: 'a> = Tree<'a, T>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        map_tree(self, Rc::new(f))
+    }
+}
+
+/// A random generator of values of type `A`, with integrated shrinking.
+///
+/// A generator is a function from a [`Rng`](Rng) and a size to a
+/// [`Tree`](Tree) of values. The size is a hint of how large the generated
+/// value should be, such as the length of a [`Vec`](Vec), which
+/// [`check`](check) starts at zero and grows as it runs more tests.
+///
+/// [`Bind`](Bind) passes the generator state along from one generator to the
+/// next, and shrinks the values from the first generator before the values
+/// from the second.
+pub struct Gen<'a, A> {
+    run: Rc<dyn Fn(&mut Rng, usize) -> Tree<'a, A> + 'a>,
+}
+
+impl<'a, A: 'a> Gen<'a, A> {
+    /// Construct a generator from a function which generates values that
+    /// can't be shrunk.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&mut Rng, usize) -> A + 'a,
+    {
+        Self::from_tree(move |rng, size| Tree::leaf(f(rng, size)))
+    }
+
+    /// Construct a generator from a function which generates a tree of
+    /// values.
+    pub fn from_tree<F>(f: F) -> Self
+    where
+        F: Fn(&mut Rng, usize) -> Tree<'a, A> + 'a,
+    {
+        Self { run: Rc::new(f) }
+    }
+
+    /// Run the generator, producing a value along with its shrinks.
+    pub fn generate(&self, rng: &mut Rng, size: usize) -> Tree<'a, A> {
+        (self.run)(rng, size)
+    }
+
+    /// Run the generator, producing a value.
+    pub fn sample(&self, rng: &mut Rng, size: usize) -> A {
+        self.generate(rng, size).into_value()
+    }
+
+    /// Run the generator with a fixed size, regardless of the size it's given.
+    pub fn resize(self, size: usize) -> Self {
+        Self::from_tree(move |rng, _| self.generate(rng, size))
+    }
+
+    /// Turn off shrinking for the values of this generator.
+    pub fn no_shrink(self) -> Self {
+        Self::from_tree(move |rng, size| Tree::leaf(self.sample(rng, size)))
+    }
+}
+
+impl<'a, A> Clone for Gen<'a, A> {
+    fn clone(&self) -> Self {
+        Self {
+            run: self.run.clone(),
+        }
+    }
+}
+
+impl<'a, A> std::fmt::Debug for Gen<'a, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Gen<{}>", std::any::type_name::<A>())
+    }
+}
+
+impl<'a, A: 'a> Functor<'a, A> for Gen<'a, A> {
+    type Target<T: 'a> = Gen<'a, T>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        let f = Rc::new(f);
+        Gen::from_tree(move |rng, size| map_tree(self.generate(rng, size), f.clone()))
+    }
+}
+
+impl<'a, A: 'a> Pure<A> for Gen<'a, A>
+where
+    A: Clone,
+{
+    fn pure(value: A) -> Self {
+        Self::from_tree(move |_, _| Tree::leaf(value.clone()))
+    }
+}
+
+impl<'a, A: 'a> Apply<'a, A> for Gen<'a, A> {
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        f.bind(move |func: ApplyFn<'a, A, B>| self.clone().fmap(move |value| func.apply_fn(value)))
+    }
+}
+
+impl<'a, A: 'a> Bind<'a, A> for Gen<'a, A> {
+    fn bind<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> Self::Target<B> + 'a,
+    {
+        let f = Rc::new(f);
+        Gen::from_tree(move |rng, size| {
+            let left = self.generate(rng, size);
+            bind_tree(left, f.clone(), rng, size)
+        })
+    }
+}
+
+impl<'a, A: 'a> Alt<'a, A> for Gen<'a, A> {
+    /// Pick one of the two generators with equal probability, shrinking
+    /// towards the first.
+    fn alt(self, other: Self) -> Self {
+        one_of(vec![self, other])
+    }
+}
+
+/// An integer type which [`choose`](choose) can generate.
+pub trait Choose: Copy {
+    /// Convert the value into an `i128`.
+    fn to_i128(self) -> i128;
+
+    /// Convert an `i128`, which is known to be in range, into a value.
+    fn from_i128(value: i128) -> Self;
+}
+
+macro_rules! impl_choose {
+    ($($type:ty),*) => {
+        $(
+            impl Choose for $type {
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(value: i128) -> Self {
+                    value as $type
+                }
+            }
+        )*
+    };
+}
+
+impl_choose!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// The shrinks of `value` towards `target`: the target itself, then halving
+/// the distance until it's down to one.
+fn shrink_towards(target: i128, value: i128) -> Vec<i128> {
+    let mut shrinks = Vec::new();
+    let mut distance = value - target;
+    while distance != 0 {
+        shrinks.push(value - distance);
+        distance /= 2;
+    }
+    shrinks
+}
+
+/// Generate an integer in the inclusive range from `low` to `high`, shrinking
+/// towards `low`.
+///
+/// Panics if `low` is greater than `high`.
+pub fn choose<'a, A>(low: A, high: A) -> Gen<'a, A>
+where
+    A: Choose + 'a,
+{
+    let (low, high) = (low.to_i128(), high.to_i128());
+    assert!(low <= high, "choose called with an empty range");
+    Gen::from_tree(move |rng, _| {
+        Tree::unfold(rng.range_i128(low, high), move |value| {
+            shrink_towards(low, *value)
+        })
+        .fmap(A::from_i128)
+    })
+}
+
+/// Pick one of a list of values, shrinking towards the first.
+///
+/// Panics if the list is empty.
+pub fn elements<'a, A>(values: Vec<A>) -> Gen<'a, A>
+where
+    A: Clone + 'a,
+{
+    assert!(!values.is_empty(), "elements called with no values");
+    choose(0, values.len() - 1).fmap(move |index| values[index].clone())
+}
+
+/// Pick one of a list of generators, shrinking towards the first.
+///
+/// Panics if the list is empty.
+pub fn one_of<'a, A>(gens: Vec<Gen<'a, A>>) -> Gen<'a, A>
+where
+    A: 'a,
+{
+    assert!(!gens.is_empty(), "one_of called with no generators");
+    choose(0, gens.len() - 1).bind(move |index| gens[index].clone())
+}
+
+/// Pick one of a list of generators, with the probability of each being
+/// proportional to its weight, shrinking towards the first.
+///
+/// Panics if the weights add up to zero.
+pub fn frequency<'a, A>(gens: Vec<(usize, Gen<'a, A>)>) -> Gen<'a, A>
+where
+    A: 'a,
+{
+    let total: usize = gens.iter().map(|(weight, _)| weight).sum();
+    assert!(total > 0, "frequency called with no weight");
+    choose(0, total - 1).bind(move |mut pick| {
+        for (weight, gen) in &gens {
+            if pick < *weight {
+                return gen.clone();
+            }
+            pick -= weight;
+        }
+        unreachable!()
+    })
+}
+
+/// Generate a [`Vec`](Vec) of values, no longer than the current size.
+///
+/// It shrinks by removing elements, and then by shrinking the elements.
+pub fn vec_of<'a, A>(gen: Gen<'a, A>) -> Gen<'a, Vec<A>>
+where
+    A: Clone + 'a,
+{
+    Gen::from_tree(move |rng, size| {
+        let length = rng.below(size as u64 + 1);
+        vec_tree((0..length).map(|_| gen.generate(rng, size)).collect())
+    })
+}
+
+fn vec_tree<'a, A>(trees: Vec<Tree<'a, A>>) -> Tree<'a, Vec<A>>
+where
+    A: Clone + 'a,
+{
+    let value = trees.iter().map(|tree| tree.value.clone()).collect();
+    Tree::new(value, move || {
+        let mut children = Vec::new();
+        let mut chunk = trees.len();
+        while chunk > 0 {
+            for start in (0..=trees.len() - chunk).step_by(chunk) {
+                let mut rest = trees[..start].to_vec();
+                rest.extend_from_slice(&trees[start + chunk..]);
+                children.push(vec_tree(rest));
+            }
+            chunk /= 2;
+        }
+        for (index, tree) in trees.iter().enumerate() {
+            for child in tree.children() {
+                let mut shrunk = trees.clone();
+                shrunk[index] = child;
+                children.push(vec_tree(shrunk));
+            }
+        }
+        children
+    })
+}
+
+/// Construct a generator which depends on the current size.
+pub fn sized<'a, A, F>(f: F) -> Gen<'a, A>
+where
+    A: 'a,
+    F: Fn(usize) -> Gen<'a, A> + 'a,
+{
+    Gen::from_tree(move |rng, size| f(size).generate(rng, size))
+}
+
+/// A value for which a property failed, as found by [`check`](check).
+///
+/// The failure can be reproduced by running the generator with
+/// `Rng::new(seed)` at the given size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counterexample<A> {
+    /// The smallest failing value found.
+    pub value: A,
+    /// The seed of the test which failed.
+    pub seed: u64,
+    /// The size of the test which failed.
+    pub size: usize,
+    /// The number of tests run, including the failing one.
+    pub tests: usize,
+    /// The number of times the failing value was shrunk.
+    pub shrinks: usize,
+}
+
+impl<A: std::fmt::Debug> Display for Counterexample<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "property failed after {} tests and {} shrinks (seed {:#x}, size {}): {:?}",
+            self.tests, self.shrinks, self.seed, self.size, self.value
+        )
+    }
+}
+
+/// Check that a property holds for [`TESTS`](TESTS) values generated by
+/// `gen`, using the default seed.
+///
+/// If it fails, the failing value is shrunk as far as possible while still
+/// failing, and returned as a [`Counterexample`](Counterexample).
+pub fn check<'a, A, P>(gen: &Gen<'a, A>, property: P) -> Result<(), Counterexample<A>>
+where
+    A: Clone + 'a,
+    P: Fn(A) -> bool,
+{
+    check_with(&mut Rng::default(), TESTS, gen, property)
+}
+
+/// Check that a property holds for a given number of values generated by
+/// `gen`, using the given random number generator.
+pub fn check_with<'a, A, P>(
+    rng: &mut Rng,
+    tests: usize,
+    gen: &Gen<'a, A>,
+    property: P,
+) -> Result<(), Counterexample<A>>
+where
+    A: Clone + 'a,
+    P: Fn(A) -> bool,
+{
+    for test in 0..tests {
+        let seed = rng.next_u64();
+        let size = test * MAX_SIZE / tests;
+        let mut tree = gen.generate(&mut Rng::new(seed), size);
+        if property(tree.value.clone()) {
+            continue;
+        }
+        let mut shrinks = 0;
+        while shrinks < MAX_SHRINKS {
+            match tree
+                .children()
+                .into_iter()
+                .find(|child| !property(child.value.clone()))
+            {
+                Some(child) => {
+                    tree = child;
+                    shrinks += 1;
+                }
+                None => break,
+            }
+        }
+        return Err(Counterexample {
+            value: tree.value,
+            seed,
+            size,
+            tests: test + 1,
+            shrinks,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check, choose, elements, frequency, one_of, sized, vec_of, Rng};
+    use crate::{run, Alt, Functor, Gen, Pure};
+
+    #[test]
+    fn choose_stays_in_range() {
+        let gen = choose(-10i8, 10);
+        assert!(check(&gen, |x| (-10..=10).contains(&x)).is_ok());
+        let full = choose(u64::MIN, u64::MAX);
+        assert!(check(&full, |_| true).is_ok());
+    }
+
+    #[test]
+    fn shrinks_to_minimal_counterexample() {
+        let failure = check(&choose(0, 1000), |x| x < 123).unwrap_err();
+        assert_eq!(failure.value, 123);
+
+        let failure = check(&vec_of(choose(0, 100)), |xs| xs.iter().sum::<i32>() < 50).unwrap_err();
+        assert_eq!(failure.value, vec![50]);
+    }
+
+    #[test]
+    fn shrinks_through_bind() {
+        let gen: Gen<'_, (usize, String)> = run! {
+            n <= choose(1, 5);
+            s <= vec_of(elements(vec!['a', 'b', 'c'])).fmap(|cs| cs.into_iter().collect::<String>());
+            yield (n, s.repeat(n))
+        };
+        let failure = check(&gen, |(_, s)| !s.contains('c')).unwrap_err();
+        assert_eq!(failure.value, (1, "c".to_string()));
+    }
+
+    #[test]
+    fn choices() {
+        let gen = one_of(vec![Gen::pure(1), choose(10, 20)]);
+        assert!(check(&gen, |x| x == 1 || (10..=20).contains(&x)).is_ok());
+        assert_eq!(check(&gen, |x| x < 10).unwrap_err().value, 10);
+
+        let gen = frequency(vec![(0, Gen::pure(1)), (1, Gen::pure(2))]);
+        assert!(check(&gen, |x| x == 2).is_ok());
+
+        let gen = Gen::pure('a').alt(Gen::pure('b'));
+        assert_eq!(check(&gen, |c| c == 'a').unwrap_err().value, 'b');
+    }
+
+    #[test]
+    fn sizes() {
+        let gen = sized(Gen::pure);
+        assert_eq!(gen.sample(&mut Rng::default(), 7), 7);
+        assert_eq!(gen.resize(3).sample(&mut Rng::default(), 7), 3);
+        assert!(check(&vec_of(Gen::pure(())), |xs| xs.len() < 100).is_ok());
+    }
+}
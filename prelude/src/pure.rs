@@ -88,6 +88,39 @@ where
     }
 }
 
+impl<A> Pure<A> for Box<A> {
+    fn pure(value: A) -> Self {
+        Box::new(value)
+    }
+}
+
+impl<A> Pure<A> for std::rc::Rc<A> {
+    fn pure(value: A) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<A> Pure<A> for std::sync::Arc<A> {
+    fn pure(value: A) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<A> Pure<A> for std::task::Poll<A> {
+    fn pure(value: A) -> Self {
+        Self::Ready(value)
+    }
+}
+
+impl<W, A> Pure<A> for (W, A)
+where
+    W: crate::Monoid,
+{
+    fn pure(value: A) -> Self {
+        (W::default(), value)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{Functor, Pure};
@@ -0,0 +1,199 @@
+use std::rc::Rc;
+
+use crate::{apply::ApplyFn, Apply, Bind, Functor, Profunctor, Pure};
+
+/// A `Reader` monad.
+///
+/// This wraps a function `Fn(R) -> A`, which computes a result from a shared
+/// environment of type `R`. Chaining `Reader`s together with [`Bind`](Bind)
+/// passes the same environment to every computation in the chain, so you
+/// don't have to pass it around by hand.
+///
+/// The environment is passed by value, and it will be cloned for every step
+/// in a chain of computations. If your environment is expensive to clone,
+/// wrap it in an [`Rc`](Rc).
+///
+/// ```
+/// # use higher::{run, reader::Reader};
+/// #[derive(Clone)]
+/// struct Config {
+///     name: String,
+///     verbose: bool,
+/// }
+///
+/// let greeting = run! {
+///     name <= Reader::asks(|config: &Config| config.name.clone());
+///     verbose <= [name] Reader::asks(|config: &Config| config.verbose);
+///     [name] yield if verbose { format!("Hello {}!", name) } else { name.clone() }
+/// };
+///
+/// let config = Config { name: "Joe".to_string(), verbose: true };
+/// assert_eq!(greeting.run_reader(config), "Hello Joe!");
+/// ```
+pub struct Reader<'a, R, A> {
+    run: Rc<dyn Fn(R) -> A + 'a>,
+}
+
+impl<'a, R, A> Reader<'a, R, A> {
+    /// Construct a `Reader` from a function which computes a result from an
+    /// environment.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(R) -> A + 'a,
+    {
+        Self { run: Rc::new(f) }
+    }
+
+    /// Construct a `Reader` which returns the result of applying a function
+    /// to the environment.
+    pub fn asks<F>(f: F) -> Self
+    where
+        F: Fn(&R) -> A + 'a,
+    {
+        Self::new(move |env| f(&env))
+    }
+
+    /// Run the computation in an environment modified by the given function.
+    pub fn local<F>(self, f: F) -> Self
+    where
+        R: 'a,
+        A: 'a,
+        F: Fn(R) -> R + 'a,
+    {
+        Self::new(move |env| self.run_reader(f(env)))
+    }
+
+    /// Run the computation with the given environment.
+    pub fn run_reader(&self, env: R) -> A {
+        (self.run)(env)
+    }
+}
+
+impl<'a, R> Reader<'a, R, R> {
+    /// Construct a `Reader` which returns the environment.
+    pub fn ask() -> Self {
+        Self::new(|env| env)
+    }
+}
+
+impl<'a, R, A> Clone for Reader<'a, R, A> {
+    fn clone(&self) -> Self {
+        Self {
+            run: self.run.clone(),
+        }
+    }
+}
+
+impl<'a, R, A> std::fmt::Debug for Reader<'a, R, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Reader<{}, {}>",
+            std::any::type_name::<R>(),
+            std::any::type_name::<A>()
+        )
+    }
+}
+
+impl<'a, R: 'a, A: 'a> Functor<'a, A> for Reader<'a, R, A> {
+    type Target<T: 'a> = Reader<'a, R, T>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        Reader::new(move |env| f(self.run_reader(env)))
+    }
+}
+
+impl<'a, R: 'a, A: 'a> Pure<A> for Reader<'a, R, A>
+where
+    A: Clone,
+{
+    fn pure(value: A) -> Self {
+        Self::new(move |_| value.clone())
+    }
+}
+
+impl<'a, R: 'a, A: 'a> Apply<'a, A> for Reader<'a, R, A>
+where
+    R: Clone,
+{
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        Reader::new(move |env: R| f.run_reader(env.clone()).apply_fn(self.run_reader(env)))
+    }
+}
+
+impl<'a, R: 'a, A: 'a> Bind<'a, A> for Reader<'a, R, A>
+where
+    R: Clone,
+{
+    fn bind<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> Self::Target<B> + 'a,
+    {
+        Reader::new(move |env: R| f(self.run_reader(env.clone())).run_reader(env))
+    }
+}
+
+impl<'a, R: 'a, A: 'a> Profunctor<'a, R, A> for Reader<'a, R, A> {
+    type Target<T: 'a, U: 'a> = Reader<'a, T, U>;
+
+    fn dimap<Q: 'a, B: 'a, L, M>(self, left: L, right: M) -> Self::Target<Q, B>
+    where
+        L: Fn(Q) -> R + 'a,
+        M: Fn(A) -> B + 'a,
+    {
+        Reader::new(move |env| right(self.run_reader(left(env))))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use super::Reader;
+    use crate::{run, Functor, Profunctor};
+
+    #[derive(Debug)]
+    struct Env {
+        base: u32,
+        label: &'static str,
+    }
+
+    #[test]
+    fn ask_asks_local() {
+        let program = run! {
+            env <= Reader::asks(|env: &Rc<Env>| env.clone());
+            doubled <= [env] Reader::asks(|env: &Rc<Env>| env.base * 2).local(|env: Rc<Env>| {
+                Rc::new(Env { base: env.base + 1, label: env.label })
+            });
+            yield format!("{}: {} {}", env.label, env.base, doubled)
+        };
+        let env = Rc::new(Env {
+            base: 20,
+            label: "answer",
+        });
+        assert_eq!(program.run_reader(env), "answer: 20 42");
+    }
+
+    #[test]
+    fn functor() {
+        let reader = Reader::asks(|env: &u32| env + 1).fmap(|x| x * 2);
+        assert_eq!(reader.run_reader(20), 42);
+    }
+
+    #[test]
+    fn profunctor() {
+        let reader = Reader::asks(|env: &String| env.len());
+        let reader = reader.dimap(|n: usize| "x".repeat(n), |len| len * 2);
+        assert_eq!(reader.run_reader(21), 42);
+        let reader = Reader::ask().lcmap(|n: u32| n + 1);
+        assert_eq!(reader.run_reader(41), 42);
+    }
+}
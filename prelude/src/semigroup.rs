@@ -1,5 +1,7 @@
 use std::{
+    collections::{btree_map, hash_map, BTreeMap, HashMap},
     convert::Infallible,
+    hash::Hash,
     ops::{Deref, DerefMut},
 };
 
@@ -33,6 +35,54 @@ impl Semigroup for String {
     }
 }
 
+/// Maps combine as a union of their keys. When both maps have a value for the
+/// same key, the two values are combined using their own
+/// [`mappend`](Semigroup::mappend).
+impl<K, V> Semigroup for HashMap<K, V>
+where
+    K: Hash + Eq,
+    V: Semigroup,
+{
+    fn mappend(mut self, other: Self) -> Self {
+        for (key, value) in other {
+            match self.entry(key) {
+                hash_map::Entry::Occupied(entry) => {
+                    let (key, left) = entry.remove_entry();
+                    self.insert(key, left.mappend(value));
+                }
+                hash_map::Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+            }
+        }
+        self
+    }
+}
+
+/// Maps combine as a union of their keys. When both maps have a value for the
+/// same key, the two values are combined using their own
+/// [`mappend`](Semigroup::mappend).
+impl<K, V> Semigroup for BTreeMap<K, V>
+where
+    K: Ord,
+    V: Semigroup,
+{
+    fn mappend(mut self, other: Self) -> Self {
+        for (key, value) in other {
+            match self.entry(key) {
+                btree_map::Entry::Occupied(entry) => {
+                    let (key, left) = entry.remove_entry();
+                    self.insert(key, left.mappend(value));
+                }
+                btree_map::Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+            }
+        }
+        self
+    }
+}
+
 impl Semigroup for () {
     fn mappend(self, _other: Self) -> Self {}
 }
@@ -43,6 +93,37 @@ impl Semigroup for Infallible {
     }
 }
 
+/// Implement the accessors shared by the newtype wrappers below.
+macro_rules! impl_newtype {
+    ($type:ident) => {
+        impl<A> $type<A> {
+            pub fn unwrap(self) -> A {
+                self.0
+            }
+        }
+
+        impl<A> From<A> for $type<A> {
+            fn from(value: A) -> Self {
+                Self(value)
+            }
+        }
+
+        impl<A> Deref for $type<A> {
+            type Target = A;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<A> DerefMut for $type<A> {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+    };
+}
+
 /// Semigroup where [`mappend`](Semigroup::mappend) discards the second
 /// argument, always returning the first.
 ///
@@ -57,31 +138,7 @@ impl Semigroup for Infallible {
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct First<A>(pub A);
 
-impl<A> First<A> {
-    pub fn unwrap(self) -> A {
-        self.0
-    }
-}
-
-impl<A> From<A> for First<A> {
-    fn from(value: A) -> Self {
-        Self(value)
-    }
-}
-
-impl<A> Deref for First<A> {
-    type Target = A;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
-impl<A> DerefMut for First<A> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
-}
+impl_newtype!(First);
 
 impl<A> Semigroup for First<A> {
     fn mappend(self, _other: Self) -> Self {
@@ -103,34 +160,64 @@ impl<A> Semigroup for First<A> {
 #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct Last<A>(pub A);
 
-impl<A> Last<A> {
-    pub fn unwrap(self) -> A {
-        self.0
-    }
-}
+impl_newtype!(Last);
 
-impl<A> From<A> for Last<A> {
-    fn from(value: A) -> Self {
-        Self(value)
+impl<A> Semigroup for Last<A> {
+    fn mappend(self, other: Self) -> Self {
+        other
     }
 }
 
-impl<A> Deref for Last<A> {
-    type Target = A;
+/// Semigroup where [`mappend`](Semigroup::mappend) returns the larger of its
+/// two arguments.
+///
+/// This has no [`Default`](Default) and is therefore not a
+/// [`Monoid`](crate::Monoid), as there's no general identity value for it. Use
+/// [`Foldable1`](crate::Foldable1) to fold a non-empty collection of these.
+///
+/// ```
+/// # use higher::semigroup::{Semigroup, Max};
+/// # let x = 5;
+/// # let y = 8;
+/// # assert!(
+/// Max(x).mappend(Max(y)) == Max(x.max(y))
+/// # );
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Max<A>(pub A);
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
+impl_newtype!(Max);
 
-impl<A> DerefMut for Last<A> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+impl<A> Semigroup for Max<A>
+where
+    A: Ord,
+{
+    fn mappend(self, other: Self) -> Self {
+        Self(self.0.max(other.0))
     }
 }
 
-impl<A> Semigroup for Last<A> {
+/// Semigroup where [`mappend`](Semigroup::mappend) returns the smaller of its
+/// two arguments.
+///
+/// ```
+/// # use higher::semigroup::{Semigroup, Min};
+/// # let x = 5;
+/// # let y = 8;
+/// # assert!(
+/// Min(x).mappend(Min(y)) == Min(x.min(y))
+/// # );
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Min<A>(pub A);
+
+impl_newtype!(Min);
+
+impl<A> Semigroup for Min<A>
+where
+    A: Ord,
+{
     fn mappend(self, other: Self) -> Self {
-        other
+        Self(self.0.min(other.0))
     }
 }
@@ -0,0 +1,293 @@
+use std::{marker::PhantomData, rc::Rc};
+
+use crate::{
+    free::{FreeFunctor, FreeFunctorRef},
+    func::Func,
+    profunctor::{Choice, Closed, Profunctor, Strong},
+    Functor, Pure,
+};
+
+/// A function from `A` into a [`Functor`](Functor) `F` of `B`.
+///
+/// `Star` lifts a functor into a [`Profunctor`](Profunctor): it's covariant
+/// in `B` because it can [`fmap`](Functor::fmap) over the functor it returns.
+///
+/// A `Star` is [`Strong`](Strong) over any functor, and a
+/// [`Choice`](Choice) over any functor with [`Pure`](Pure), which it needs to
+/// pass the other side of a [`Result`](Result) through. It isn't
+/// [`Closed`](Closed): that would mean turning a function returning `F<B>`
+/// into an `F` of functions, which most functors, like [`Option`](Option) or
+/// [`Vec`](Vec), can't do without calling the function first.
+///
+/// ```
+/// # use higher::{star::Star, profunctor::Profunctor};
+/// let parse = Star::new(|s: &str| s.parse::<i32>().ok());
+/// let parse_doubled = parse.rmap(|x| x * 2);
+/// assert_eq!(parse_doubled.run("21"), Some(42));
+/// assert_eq!(parse_doubled.run("nope"), None);
+/// ```
+pub struct Star<'a, F, A, B> {
+    run: Rc<dyn Fn(A) -> F + 'a>,
+    _phantom: PhantomData<fn() -> B>,
+}
+
+impl<'a, F, A, B> Star<'a, F, A, B> {
+    /// Wrap a function returning a functor.
+    pub fn new<G>(f: G) -> Self
+    where
+        G: Fn(A) -> F + 'a,
+    {
+        Self {
+            run: Rc::new(f),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Run the function.
+    pub fn run(&self, value: A) -> F {
+        (self.run)(value)
+    }
+}
+
+impl<'a, F, A, B> Clone for Star<'a, F, A, B> {
+    fn clone(&self) -> Self {
+        Self {
+            run: self.run.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, F, A, B> std::fmt::Debug for Star<'a, F, A, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Star<{}, {}>",
+            std::any::type_name::<A>(),
+            std::any::type_name::<F>()
+        )
+    }
+}
+
+impl<'a, F, A, B> Profunctor<'a, A, B> for Star<'a, F, A, B>
+where
+    F: Functor<'a, B> + 'a,
+    A: 'a,
+    B: 'a,
+{
+    type Target<T: 'a, U: 'a> = Star<'a, F::Target<U>, T, U>;
+
+    fn dimap<C: 'a, D: 'a, L, R>(self, left: L, right: R) -> Self::Target<C, D>
+    where
+        L: Fn(C) -> A + 'a,
+        R: Fn(B) -> D + 'a,
+    {
+        let right = Rc::new(right);
+        Star::new(move |value| {
+            let right = right.clone();
+            self.run(left(value)).fmap(move |b| right(b))
+        })
+    }
+}
+
+impl<'a, F, A, B> Strong<'a, A, B> for Star<'a, F, A, B>
+where
+    F: Functor<'a, B> + 'a,
+    A: 'a,
+    B: 'a,
+{
+    fn first<C: Clone + 'a>(self) -> Self::Target<(A, C), (B, C)> {
+        Star::new(move |(a, c): (A, C)| self.run(a).fmap(move |b| (b, c.clone())))
+    }
+
+    fn second<C: Clone + 'a>(self) -> Self::Target<(C, A), (C, B)> {
+        Star::new(move |(c, a): (C, A)| self.run(a).fmap(move |b| (c.clone(), b)))
+    }
+}
+
+impl<'a, F, A, B> Choice<'a, A, B> for Star<'a, F, A, B>
+where
+    F: FreeFunctor<'a, B> + 'a,
+    F::Target<()>: Pure<()>,
+    A: 'a,
+    B: 'a,
+{
+    fn left<C: Clone + 'a>(self) -> Self::Target<Result<A, C>, Result<B, C>> {
+        Star::new(move |value: Result<A, C>| match value {
+            Ok(a) => F::fmap_target::<B, Result<B, C>, _>(self.run(a), Ok),
+            Err(c) => {
+                F::fmap_target::<(), Result<B, C>, _>(Pure::pure(()), move |()| Err(c.clone()))
+            }
+        })
+    }
+
+    fn right<C: Clone + 'a>(self) -> Self::Target<Result<C, A>, Result<C, B>> {
+        Star::new(move |value: Result<C, A>| match value {
+            Ok(c) => F::fmap_target::<(), Result<C, B>, _>(Pure::pure(()), move |()| Ok(c.clone())),
+            Err(a) => F::fmap_target::<B, Result<C, B>, _>(self.run(a), Err),
+        })
+    }
+}
+
+/// A function from a [`Functor`](Functor) `F` of `A` to `B`.
+///
+/// `Costar` is the dual of [`Star`](Star): it's contravariant in `A` because
+/// it can [`fmap`](Functor::fmap) over the functor it's given before passing
+/// it on.
+///
+/// A `Costar` is [`Closed`](Closed) over any functor which implements
+/// [`FreeFunctorRef`](FreeFunctorRef). It isn't [`Strong`](Strong) or a
+/// [`Choice`](Choice): passing the other half of a pair through needs a
+/// comonad to take it back out of the functor, and passing the other side of
+/// a [`Result`](Result) through needs to traverse the functor, and Rust has
+/// no way to ask for either of those over the whole family of `F`'s
+/// [`Target`](Functor::Target) types.
+///
+/// ```
+/// # use higher::{star::Costar, profunctor::Profunctor};
+/// let sum = Costar::new(|xs: Vec<i32>| xs.into_iter().sum::<i32>());
+/// let total_length = sum.lcmap(|s: &str| s.len() as i32);
+/// assert_eq!(total_length.run(vec!["ab", "cde"]), 5);
+/// ```
+pub struct Costar<'a, F, A, B> {
+    run: Rc<dyn Fn(F) -> B + 'a>,
+    _phantom: PhantomData<fn(A)>,
+}
+
+impl<'a, F, A, B> Costar<'a, F, A, B> {
+    /// Wrap a function taking a functor.
+    pub fn new<G>(f: G) -> Self
+    where
+        G: Fn(F) -> B + 'a,
+    {
+        Self {
+            run: Rc::new(f),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Run the function.
+    pub fn run(&self, value: F) -> B {
+        (self.run)(value)
+    }
+}
+
+impl<'a, F, A, B> Clone for Costar<'a, F, A, B> {
+    fn clone(&self) -> Self {
+        Self {
+            run: self.run.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, F, A, B> std::fmt::Debug for Costar<'a, F, A, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Costar<{}, {}>",
+            std::any::type_name::<F>(),
+            std::any::type_name::<B>()
+        )
+    }
+}
+
+impl<'a, F, A, B> Profunctor<'a, A, B> for Costar<'a, F, A, B>
+where
+    F: Functor<'a, A> + 'a,
+    A: 'a,
+    B: 'a,
+{
+    type Target<T: 'a, U: 'a> = Costar<'a, F::Target<T>, T, U>;
+
+    fn dimap<C: 'a, D: 'a, L, R>(self, left: L, right: R) -> Self::Target<C, D>
+    where
+        L: Fn(C) -> A + 'a,
+        R: Fn(B) -> D + 'a,
+    {
+        let left = Rc::new(left);
+        Costar::new(move |value: F::Target<C>| {
+            let left = left.clone();
+            right(self.run(value.fmap(move |c| left(c))))
+        })
+    }
+}
+
+impl<'a, F, A, B> Closed<'a, A, B> for Costar<'a, F, A, B>
+where
+    F: FreeFunctorRef<'a, A> + 'a,
+    A: 'a,
+    B: 'a,
+{
+    fn closed<X: Clone + 'a>(self) -> Self::Target<Func<'a, X, A>, Func<'a, X, B>> {
+        Costar::new(move |functions: F::Target<Func<'a, X, A>>| {
+            let this = self.clone();
+            let functions = Rc::new(functions);
+            Func::new(move |x: X| {
+                this.run(F::fmap_ref_target::<Func<'a, X, A>, A, _>(
+                    &*functions,
+                    move |function| function.call(x.clone()),
+                ))
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Costar, Star};
+    use crate::{
+        compose::Compose,
+        func::Func,
+        profunctor::{Choice, Closed, Profunctor, Strong},
+        Identity, Validation,
+    };
+
+    #[test]
+    fn star() {
+        let halve = Star::new(|x: i32| if x % 2 == 0 { Some(x / 2) } else { None });
+        assert_eq!(halve.clone().first::<char>().run((4, 'a')), Some((2, 'a')));
+        assert_eq!(halve.clone().left::<&str>().run(Ok(3)), None);
+        assert_eq!(halve.clone().left().run(Err("skip")), Some(Err("skip")));
+        assert_eq!(halve.right::<()>().run(Err(8)), Some(Err(4)));
+
+        let increment = Star::new(|x: i32| Identity(x + 1));
+        assert_eq!(increment.left::<()>().run(Ok(1)), Identity(Ok(2)));
+
+        let positive = Star::new(|x: i32| {
+            if x > 0 {
+                Validation::Success(x)
+            } else {
+                Validation::Failure(vec![x])
+            }
+        });
+        assert_eq!(
+            positive.clone().left::<&str>().run(Err("skip")),
+            Validation::Success(Err("skip"))
+        );
+        assert_eq!(
+            positive.right::<()>().run(Err(-1)),
+            Validation::Failure(vec![-1])
+        );
+
+        let spread =
+            Star::new(|x: i32| Compose::<Option<Vec<i32>>, Vec<i32>, i32>::new(Some(vec![x, -x])));
+        assert_eq!(
+            spread.left::<char>().run(Err('c')).get_compose(),
+            Some(vec![Err('c')])
+        );
+    }
+
+    #[test]
+    fn costar() {
+        let first = Costar::new(|xs: Vec<i32>| xs.first().copied());
+        let first = first.dimap(|s: String| s.len() as i32, |x| x.unwrap_or(-1));
+        assert_eq!(first.run(vec!["abc".to_string()]), 3);
+        assert_eq!(first.run(vec![]), -1);
+
+        let sum = Costar::new(|xs: Vec<i32>| xs.into_iter().sum::<i32>()).closed::<i32>();
+        let sums = sum.run(vec![Func::new(|x| x + 1), Func::new(|x| x * 10)]);
+        assert_eq!(sums.call(2), 23);
+        assert_eq!(sums.call(3), 34);
+    }
+}
@@ -0,0 +1,210 @@
+use std::rc::Rc;
+
+use crate::{apply::ApplyFn, Apply, Bind, Functor, Pure};
+
+/// A `State` monad.
+///
+/// This wraps a function `Fn(S) -> (A, S)`, which takes a state value and
+/// produces a result along with a new state. Chaining `State`s together with
+/// [`Bind`](Bind) threads the state through each computation in turn, so you
+/// don't have to pass it around by hand.
+///
+/// ```
+/// # use higher::{run, state::State};
+/// let counter = run! {
+///     x <= State::get();
+///     State::put(x + 1);
+///     y <= State::gets(|s: &u32| s * 2);
+///     yield (x, y)
+/// };
+/// assert_eq!(counter.run_state(5), ((5, 12), 6));
+/// ```
+pub struct State<'a, S, A> {
+    run: Rc<dyn Fn(S) -> (A, S) + 'a>,
+}
+
+impl<'a, S, A> State<'a, S, A> {
+    /// Construct a `State` from a function which takes a state and returns a
+    /// result and a new state.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(S) -> (A, S) + 'a,
+    {
+        Self { run: Rc::new(f) }
+    }
+
+    /// Construct a `State` which returns the result of applying a function to
+    /// the current state, leaving the state unchanged.
+    pub fn gets<F>(f: F) -> Self
+    where
+        F: Fn(&S) -> A + 'a,
+    {
+        Self::new(move |state| (f(&state), state))
+    }
+
+    /// Run the computation with the given initial state, returning the
+    /// result and the final state.
+    pub fn run_state(&self, state: S) -> (A, S) {
+        (self.run)(state)
+    }
+
+    /// Run the computation with the given initial state, returning only the
+    /// result.
+    pub fn eval_state(&self, state: S) -> A {
+        self.run_state(state).0
+    }
+
+    /// Run the computation with the given initial state, returning only the
+    /// final state.
+    pub fn exec_state(&self, state: S) -> S {
+        self.run_state(state).1
+    }
+}
+
+impl<'a, S> State<'a, S, S>
+where
+    S: Clone,
+{
+    /// Construct a `State` which returns the current state.
+    pub fn get() -> Self {
+        Self::new(|state: S| (state.clone(), state))
+    }
+}
+
+impl<'a, S> State<'a, S, ()> {
+    /// Construct a `State` which replaces the current state with the given
+    /// value.
+    pub fn put(state: S) -> Self
+    where
+        S: Clone + 'a,
+    {
+        Self::new(move |_| ((), state.clone()))
+    }
+
+    /// Construct a `State` which updates the current state using the given
+    /// function.
+    pub fn modify<F>(f: F) -> Self
+    where
+        F: Fn(S) -> S + 'a,
+    {
+        Self::new(move |state| ((), f(state)))
+    }
+}
+
+impl<'a, S, A> Clone for State<'a, S, A> {
+    fn clone(&self) -> Self {
+        Self {
+            run: self.run.clone(),
+        }
+    }
+}
+
+impl<'a, S, A> std::fmt::Debug for State<'a, S, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "State<{}, {}>",
+            std::any::type_name::<S>(),
+            std::any::type_name::<A>()
+        )
+    }
+}
+
+impl<'a, S: 'a, A: 'a> Functor<'a, A> for State<'a, S, A> {
+    type Target<T: 'a> = State<'a, S, T>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        State::new(move |state| {
+            let (a, state) = self.run_state(state);
+            (f(a), state)
+        })
+    }
+}
+
+impl<'a, S: 'a, A: 'a> Pure<A> for State<'a, S, A>
+where
+    A: Clone,
+{
+    fn pure(value: A) -> Self {
+        Self::new(move |state| (value.clone(), state))
+    }
+}
+
+impl<'a, S: 'a, A: 'a> Apply<'a, A> for State<'a, S, A> {
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        State::new(move |state| {
+            let (func, state) = f.run_state(state);
+            let (a, state) = self.run_state(state);
+            (func.apply_fn(a), state)
+        })
+    }
+}
+
+impl<'a, S: 'a, A: 'a> Bind<'a, A> for State<'a, S, A> {
+    fn bind<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> Self::Target<B> + 'a,
+    {
+        State::new(move |state| {
+            let (a, state) = self.run_state(state);
+            f(a).run_state(state)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::State;
+    use crate::{apply::lift2, run, Functor, Pure};
+
+    #[test]
+    fn get_put_modify() {
+        let program = run! {
+            State::modify(|s: Vec<u32>| s.into_iter().map(|x| x * 2).collect());
+            s <= State::gets(|s: &Vec<u32>| s.clone());
+            State::put(vec![s.len() as u32]);
+            [s] yield s
+        };
+        assert_eq!(program.run_state(vec![1, 2, 3]), (vec![2, 4, 6], vec![3]));
+    }
+
+    #[test]
+    fn label_tree() {
+        fn fresh<'a>() -> State<'a, usize, usize> {
+            run! {
+                n <= State::get();
+                State::put(n + 1);
+                yield n
+            }
+        }
+
+        let labels = run! {
+            a <= fresh();
+            b <= fresh();
+            c <= fresh();
+            yield vec![a, b, c]
+        };
+        assert_eq!(labels.eval_state(10), vec![10, 11, 12]);
+        assert_eq!(labels.exec_state(10), 13);
+    }
+
+    #[test]
+    fn functor_and_apply() {
+        let state = State::gets(|s: &i32| s * 2).fmap(|x| x + 1);
+        assert_eq!(state.run_state(5), (11, 5));
+        let sum = lift2(
+            &|a: i32, b: i32| a + b,
+            State::pure(2),
+            State::gets(|s: &i32| *s),
+        );
+        assert_eq!(sum.eval_state(40), 42);
+    }
+}
@@ -0,0 +1,182 @@
+use std::rc::Rc;
+
+use crate::{Comonad, Extend, Functor};
+
+/// A `Store` comonad.
+///
+/// A `Store` pairs a function `Fn(S) -> A`, which looks up a value at any
+/// position `S`, with a current position. [`extract`](Comonad::extract)
+/// looks up the value at the current position, and
+/// [`extend`](Extend::extend) runs a computation at every position, where
+/// each computation can [`peek`](Store::peek) at its neighbours.
+///
+/// This makes `Store` a good fit for things like cellular automata:
+///
+/// ```
+/// # use higher::{store::Store, Comonad, Extend};
+/// let cells = vec![false, true, true, false, true];
+/// let world = Store::new(move |i: usize| cells.get(i).copied().unwrap_or(false), 0);
+///
+/// // A cell is alive if exactly one of its neighbours is alive.
+/// let rule = |w: Store<'_, usize, bool>| {
+///     let left = w.pos().checked_sub(1).map(|i| w.peek(i)).unwrap_or(false);
+///     let right = w.peeks(|i| i + 1);
+///     left != right
+/// };
+/// let next = world.extend(rule);
+/// let row: Vec<bool> = (0..5).map(|i| next.clone().seek(i).extract()).collect();
+/// assert_eq!(row, vec![true, true, true, false, false]);
+/// ```
+pub struct Store<'a, S, A> {
+    peek: Rc<dyn Fn(S) -> A + 'a>,
+    pos: S,
+}
+
+impl<'a, S, A> Store<'a, S, A> {
+    /// Construct a `Store` from a lookup function and a current position.
+    pub fn new<F>(f: F, pos: S) -> Self
+    where
+        F: Fn(S) -> A + 'a,
+    {
+        Self {
+            peek: Rc::new(f),
+            pos,
+        }
+    }
+
+    /// Get the current position.
+    pub fn pos(&self) -> &S {
+        &self.pos
+    }
+
+    /// Look up the value at the given position.
+    pub fn peek(&self, pos: S) -> A {
+        (self.peek)(pos)
+    }
+
+    /// Look up the value at the position given by applying a function to the
+    /// current position.
+    pub fn peeks<F>(&self, f: F) -> A
+    where
+        F: FnOnce(&S) -> S,
+    {
+        self.peek(f(&self.pos))
+    }
+
+    /// Move to the given position.
+    pub fn seek(self, pos: S) -> Self {
+        Self {
+            peek: self.peek,
+            pos,
+        }
+    }
+
+    /// Move to the position given by applying a function to the current
+    /// position.
+    pub fn seeks<F>(self, f: F) -> Self
+    where
+        F: FnOnce(S) -> S,
+    {
+        Self {
+            peek: self.peek,
+            pos: f(self.pos),
+        }
+    }
+}
+
+impl<'a, S, A> Clone for Store<'a, S, A>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            peek: self.peek.clone(),
+            pos: self.pos.clone(),
+        }
+    }
+}
+
+impl<'a, S, A> std::fmt::Debug for Store<'a, S, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Store<{}, {}>",
+            std::any::type_name::<S>(),
+            std::any::type_name::<A>()
+        )
+    }
+}
+
+impl<'a, S: 'a, A: 'a> Functor<'a, A> for Store<'a, S, A> {
+    type Target<T: 'a> = Store<'a, S, T>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        let peek = self.peek;
+        Store::new(move |pos| f(peek(pos)), self.pos)
+    }
+}
+
+impl<'a, S: 'a, A: 'a> Extend<'a, A> for Store<'a, S, A> {
+    fn extend<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(Self) -> B + 'a,
+    {
+        let peek = self.peek;
+        Store::new(
+            move |pos| {
+                f(Store {
+                    peek: peek.clone(),
+                    pos,
+                })
+            },
+            self.pos,
+        )
+    }
+}
+
+impl<'a, S: 'a, A: 'a> Comonad<'a, A> for Store<'a, S, A> {
+    fn extract(self) -> A {
+        (self.peek)(self.pos)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Store;
+    use crate::{Comonad, Extend, Functor};
+
+    fn contents(w: &Store<'_, i32, i32>) -> Vec<i32> {
+        (-3..=3).map(|pos| w.peek(pos)).collect()
+    }
+
+    #[test]
+    fn comonad_laws() {
+        let w = Store::new(|pos: i32| pos * pos, 2);
+        let f = |w: Store<'_, i32, i32>| w.peeks(|pos| pos + 1) - w.extract();
+        let g = |w: Store<'_, i32, i32>| w.extract() * 10;
+
+        let left = w.clone().extend(|w| w.extract());
+        assert_eq!(contents(&left), contents(&w));
+        assert_eq!(left.pos(), w.pos());
+
+        assert_eq!(w.clone().extend(f).extract(), f(w.clone()));
+
+        let left = w.clone().extend(f).extend(g);
+        let right = w.extend(move |w| g(w.extend(f)));
+        assert_eq!(contents(&left), contents(&right));
+        assert_eq!(left.pos(), right.pos());
+    }
+
+    #[test]
+    fn functor_and_seek() {
+        let w = Store::new(|pos: i32| pos + 1, 0).fmap(|x| x * 10);
+        assert_eq!(w.clone().extract(), 10);
+        assert_eq!(w.clone().seek(4).extract(), 50);
+        assert_eq!(w.seeks(|pos| pos - 1).extract(), 0);
+    }
+}
@@ -0,0 +1,129 @@
+use std::rc::Rc;
+
+use crate::{Comonad, Extend, Functor, Monoid, Semigroup};
+
+/// A `Traced` comonad.
+///
+/// A `Traced` wraps a function `Fn(M) -> A` from a [`Monoid`](Monoid) `M`,
+/// which you can think of as a value which depends on a position relative to
+/// the current one. [`extract`](Comonad::extract) looks at the current
+/// position, which is the empty value of `M`, and computations run through
+/// [`extend`](Extend::extend) can [`trace`](Traced::trace) values at other
+/// positions, which are combined with their own using
+/// [`Semigroup::mappend`](Semigroup::mappend). It's the comonadic
+/// counterpart to [`Writer`](crate::Writer).
+///
+/// ```
+/// # use higher::{monoid::Additive, traced::Traced, Comonad, Extend};
+/// let position = Traced::new(|Additive(offset): Additive<i32>| 10 + offset);
+/// let velocity = position.extend(|w: Traced<'_, Additive<i32>, i32>| {
+///     w.trace(Additive(1)) - w.extract()
+/// });
+/// assert_eq!(velocity.extract(), 1);
+/// ```
+pub struct Traced<'a, M, A> {
+    run: Rc<dyn Fn(M) -> A + 'a>,
+}
+
+impl<'a, M, A> Traced<'a, M, A> {
+    /// Construct a `Traced` from a function which looks up a value at a
+    /// relative position.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(M) -> A + 'a,
+    {
+        Self { run: Rc::new(f) }
+    }
+
+    /// Look up the value at the given relative position.
+    pub fn trace(&self, position: M) -> A {
+        (self.run)(position)
+    }
+}
+
+impl<'a, M, A> Clone for Traced<'a, M, A> {
+    fn clone(&self) -> Self {
+        Self {
+            run: self.run.clone(),
+        }
+    }
+}
+
+impl<'a, M, A> std::fmt::Debug for Traced<'a, M, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Traced<{}, {}>",
+            std::any::type_name::<M>(),
+            std::any::type_name::<A>()
+        )
+    }
+}
+
+impl<'a, M: 'a, A: 'a> Functor<'a, A> for Traced<'a, M, A> {
+    type Target<T: 'a> = Traced<'a, M, T>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        Traced::new(move |position| f(self.trace(position)))
+    }
+}
+
+impl<'a, M: 'a, A: 'a> Extend<'a, A> for Traced<'a, M, A>
+where
+    M: Semigroup + Clone,
+{
+    fn extend<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(Self) -> B + 'a,
+    {
+        Traced::new(move |position: M| {
+            let this = self.clone();
+            f(Traced::new(move |other: M| {
+                this.trace(position.clone().mappend(other))
+            }))
+        })
+    }
+}
+
+impl<'a, M: 'a, A: 'a> Comonad<'a, A> for Traced<'a, M, A>
+where
+    M: Monoid + Clone,
+{
+    fn extract(self) -> A {
+        self.trace(M::default())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Traced;
+    use crate::{Comonad, Extend};
+
+    fn contents(w: &Traced<'_, String, String>) -> Vec<String> {
+        ["", "a", "bc"]
+            .iter()
+            .map(|position| w.trace(position.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn comonad_laws() {
+        let w = Traced::new(|position: String| format!("<{}>", position));
+        let f = |w: Traced<'_, String, String>| w.trace("x".to_string()) + &w.extract();
+        let g = |w: Traced<'_, String, String>| w.trace("y".to_string()).to_uppercase();
+
+        let left = w.clone().extend(|w| w.extract());
+        assert_eq!(contents(&left), contents(&w));
+
+        assert_eq!(w.clone().extend(f).extract(), f(w.clone()));
+
+        let left = w.clone().extend(f).extend(g);
+        let right = w.extend(move |w| g(w.extend(f)));
+        assert_eq!(contents(&left), contents(&right));
+    }
+}
@@ -0,0 +1,200 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{apply::ApplyFn, Apply, Bind, Functor, Pure};
+
+/// A single step of a running [`Trampoline`](Trampoline): either the
+/// computation has finished, or there's more work to do.
+enum Bounce<'a> {
+    Done,
+    More(Box<dyn FnOnce() -> Bounce<'a> + 'a>),
+}
+
+type Continuation<'a, A> = Box<dyn FnOnce(A) -> Bounce<'a> + 'a>;
+
+/// A `Trampoline` monad.
+///
+/// A `Trampoline` describes a computation which is run in constant stack
+/// space, no matter how many times it's been chained together with
+/// [`bind`](Bind::bind), or how deeply it recurses through
+/// [`suspend`](Trampoline::suspend). Instead of calling each step from the
+/// one before it, every step returns the next one to an evaluation loop in
+/// [`run`](Trampoline::run), which keeps bouncing until the computation is
+/// done.
+///
+/// A `Trampoline` can only be run once, so unlike [`State`](crate::State),
+/// [`Pure`](Pure) doesn't need its value to implement [`Clone`](Clone).
+///
+/// ```
+/// # use higher::{run, trampoline::Trampoline};
+/// fn even<'a>(n: u64) -> Trampoline<'a, bool> {
+///     if n == 0 {
+///         Trampoline::done(true)
+///     } else {
+///         Trampoline::suspend(move || odd(n - 1))
+///     }
+/// }
+///
+/// fn odd<'a>(n: u64) -> Trampoline<'a, bool> {
+///     if n == 0 {
+///         Trampoline::done(false)
+///     } else {
+///         Trampoline::suspend(move || even(n - 1))
+///     }
+/// }
+///
+/// let program = run! {
+///     a <= even(1_000_000);
+///     b <= odd(1_000_000);
+///     yield (a, b)
+/// };
+/// assert_eq!(program.run(), (true, false));
+/// ```
+pub struct Trampoline<'a, A> {
+    run: Box<dyn FnOnce(Continuation<'a, A>) -> Bounce<'a> + 'a>,
+}
+
+impl<'a, A: 'a> Trampoline<'a, A> {
+    fn new<F>(f: F) -> Self
+    where
+        F: FnOnce(Continuation<'a, A>) -> Bounce<'a> + 'a,
+    {
+        Self { run: Box::new(f) }
+    }
+
+    /// Construct a `Trampoline` which has finished with the given value.
+    pub fn done(value: A) -> Self {
+        Self::new(move |k| k(value))
+    }
+
+    /// Construct a `Trampoline` which will continue with the `Trampoline`
+    /// returned by the given function.
+    ///
+    /// The function isn't called until the evaluation loop gets to it, so
+    /// this is how you write a recursive function which doesn't grow the
+    /// stack.
+    pub fn suspend<F>(f: F) -> Self
+    where
+        F: FnOnce() -> Self + 'a,
+    {
+        Self::new(move |k| Bounce::More(Box::new(move || f().resume(k))))
+    }
+
+    /// Run the computation to completion and return its result.
+    pub fn run(self) -> A {
+        let result = Rc::new(RefCell::new(None));
+        let slot = result.clone();
+        let mut step = self.resume(Box::new(move |value| {
+            *slot.borrow_mut() = Some(value);
+            Bounce::Done
+        }));
+        while let Bounce::More(next) = step {
+            step = next();
+        }
+        let value = result.borrow_mut().take();
+        value.expect("Trampoline finished without producing a value")
+    }
+
+    fn resume(self, k: Continuation<'a, A>) -> Bounce<'a> {
+        (self.run)(k)
+    }
+
+    fn bind_once<B: 'a, F>(self, f: F) -> Trampoline<'a, B>
+    where
+        F: FnOnce(A) -> Trampoline<'a, B> + 'a,
+    {
+        Trampoline::new(move |k| {
+            Bounce::More(Box::new(move || {
+                self.resume(Box::new(move |value| {
+                    Bounce::More(Box::new(move || f(value).resume(k)))
+                }))
+            }))
+        })
+    }
+}
+
+impl<'a, A> std::fmt::Debug for Trampoline<'a, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Trampoline<{}>", std::any::type_name::<A>())
+    }
+}
+
+impl<'a, A: 'a> Functor<'a, A> for Trampoline<'a, A> {
+    type Target<T: 'a> = Trampoline<'a, T>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        self.bind_once(move |value| Trampoline::done(f(value)))
+    }
+}
+
+impl<'a, A: 'a> Pure<A> for Trampoline<'a, A> {
+    fn pure(value: A) -> Self {
+        Self::done(value)
+    }
+}
+
+impl<'a, A: 'a> Apply<'a, A> for Trampoline<'a, A> {
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        f.bind_once(move |func| self.fmap(move |value| func.apply_fn(value)))
+    }
+}
+
+impl<'a, A: 'a> Bind<'a, A> for Trampoline<'a, A> {
+    fn bind<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> Self::Target<B> + 'a,
+    {
+        self.bind_once(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Trampoline;
+    use crate::{apply::lift2, run, Bind, Functor};
+
+    #[test]
+    fn a_million_binds() {
+        let mut program = Trampoline::done(0u64);
+        for _ in 0..1_000_000 {
+            program = program.bind(|x| Trampoline::done(x + 1));
+        }
+        assert_eq!(program.run(), 1_000_000);
+    }
+
+    #[test]
+    fn deep_recursion() {
+        fn sum<'a>(n: u64) -> Trampoline<'a, u64> {
+            if n == 0 {
+                Trampoline::done(0)
+            } else {
+                Trampoline::suspend(move || sum(n - 1)).fmap(move |total| total + n)
+            }
+        }
+
+        assert_eq!(sum(1_000_000).run(), 500_000_500_000);
+    }
+
+    #[test]
+    fn functor_apply_bind() {
+        let program = run! {
+            x <= Trampoline::done(2);
+            y <= Trampoline::suspend(move || Trampoline::done(x * 20));
+            yield x + y
+        };
+        assert_eq!(program.run(), 42);
+        let sum = lift2(
+            &|a: u32, b: &str| format!("{}{}", a, b),
+            Trampoline::done(4).fmap(|x| x * 10),
+            Trampoline::done("2"),
+        );
+        assert_eq!(sum.run(), "402");
+    }
+}
@@ -0,0 +1,247 @@
+use std::{marker::PhantomData, rc::Rc};
+
+use crate::{apply::ApplyFn, Apply, Bind, Functor, Pure};
+
+use super::MonadTrans;
+
+/// A monad transformer which adds continuations to an inner monad.
+///
+/// This wraps a function which takes a continuation `Fn(A) -> M` and
+/// produces a final computation `M` in the inner monad, where `M` is of type
+/// `M<R>`. It works just like [`Cont`](crate::cont::Cont), except that the
+/// continuation and the final result live in the inner monad, so you can use
+/// its effects along the way.
+///
+/// Unlike [`StateT`](super::StateT), a `ContT` doesn't implement
+/// [`InnerMonad`](super::InnerMonad), so it should always be the outermost
+/// layer of a stack.
+///
+/// ```
+/// # use higher::{run, transformers::{ContT, MonadTrans}, Pure};
+/// fn checked<'a>(x: i32) -> ContT<'a, i32, Option<i32>, i32> {
+///     ContT::call_cc(move |exit| {
+///         run! {
+///             if x < 0 { exit(0) } else { ContT::pure(()) };
+///             yield x * 2
+///         }
+///     })
+/// }
+///
+/// let program = run! {
+///     x <= ContT::lift(Some(20));
+///     y <= checked(x);
+///     z <= checked(-x);
+///     yield y + z + 2
+/// };
+/// assert_eq!(program.eval_cont(), Some(42));
+/// ```
+pub struct ContT<'a, R, M, A> {
+    run: Rc<dyn Fn(Rc<dyn Fn(A) -> M + 'a>) -> M + 'a>,
+    result: PhantomData<R>,
+}
+
+impl<'a, R: 'a, M: 'a, A: 'a> ContT<'a, R, M, A> {
+    /// Construct a `ContT` from a function which takes a continuation and
+    /// produces a final computation in the inner monad.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(Rc<dyn Fn(A) -> M + 'a>) -> M + 'a,
+    {
+        Self {
+            run: Rc::new(f),
+            result: PhantomData,
+        }
+    }
+
+    /// Run the computation, passing its result to the given continuation.
+    pub fn run_cont<K>(&self, k: K) -> M
+    where
+        K: Fn(A) -> M + 'a,
+    {
+        (self.run)(Rc::new(k))
+    }
+
+    fn run_rc(&self, k: Rc<dyn Fn(A) -> M + 'a>) -> M {
+        (self.run)(k)
+    }
+
+    /// Lift a computation from the inner monad into a `ContT`, using a
+    /// function to construct the computation.
+    ///
+    /// Unlike [`MonadTrans::lift`](MonadTrans::lift), this doesn't require
+    /// the inner computation to implement [`Clone`](Clone).
+    pub fn lift_with<L, F>(f: F) -> Self
+    where
+        L: Bind<'a, A, Target<R> = M>,
+        F: Fn() -> L + 'a,
+    {
+        Self::new(move |k| f().bind::<R, _>(move |value| k(value)))
+    }
+
+    /// Call a function with the current continuation.
+    ///
+    /// This works just like [`Cont::call_cc`](crate::cont::Cont::call_cc).
+    pub fn call_cc<B: 'a, F>(f: F) -> Self
+    where
+        A: Clone,
+        F: Fn(Rc<dyn Fn(A) -> ContT<'a, R, M, B> + 'a>) -> Self + 'a,
+    {
+        Self::new(move |k| {
+            let exit = k.clone();
+            f(Rc::new(move |value: A| {
+                let exit = exit.clone();
+                ContT::new(move |_| exit(value.clone()))
+            }))
+            .run_rc(k)
+        })
+    }
+
+    /// Capture the continuation up to the nearest enclosing
+    /// [`reset`](ContT::reset).
+    ///
+    /// This works just like [`Cont::shift`](crate::cont::Cont::shift).
+    pub fn shift<F>(f: F) -> Self
+    where
+        M: Pure<R>,
+        F: Fn(Rc<dyn Fn(A) -> M + 'a>) -> ContT<'a, R, M, R> + 'a,
+    {
+        Self::new(move |k| f(k).eval_cont())
+    }
+}
+
+impl<'a, R: 'a, M: 'a> ContT<'a, R, M, R> {
+    /// Run the computation with a continuation which just wraps its result in
+    /// the inner monad, returning the final computation.
+    pub fn eval_cont(&self) -> M
+    where
+        M: Pure<R>,
+    {
+        self.run_cont(M::pure)
+    }
+
+    /// Delimit the continuations captured by any [`shift`](ContT::shift)
+    /// inside this computation, turning it into a computation which simply
+    /// produces its result.
+    pub fn reset<Q: 'a, N: 'a>(self) -> ContT<'a, Q, N, R>
+    where
+        M: Pure<R> + Bind<'a, R, Target<Q> = N>,
+    {
+        ContT::new(move |k| self.eval_cont().bind::<Q, _>(move |value| k(value)))
+    }
+}
+
+impl<'a, R, M, A> Clone for ContT<'a, R, M, A> {
+    fn clone(&self) -> Self {
+        Self {
+            run: self.run.clone(),
+            result: PhantomData,
+        }
+    }
+}
+
+impl<'a, R, M, A> std::fmt::Debug for ContT<'a, R, M, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ContT<{}, {}, {}>",
+            std::any::type_name::<R>(),
+            std::any::type_name::<M>(),
+            std::any::type_name::<A>()
+        )
+    }
+}
+
+impl<'a, A: 'a, R: 'a, M: 'a, N> MonadTrans<'a, A, N> for ContT<'a, R, M, A>
+where
+    N: Bind<'a, A, Target<R> = M> + Clone + 'a,
+{
+    fn lift(m: N) -> Self {
+        Self::lift_with(move || m.clone())
+    }
+}
+
+impl<'a, R: 'a, M: 'a, A: 'a> Functor<'a, A> for ContT<'a, R, M, A> {
+    type Target<T: 'a> = ContT<'a, R, M, T>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        let f = Rc::new(f);
+        ContT::new(move |k: Rc<dyn Fn(B) -> M + 'a>| {
+            let f = f.clone();
+            self.run_rc(Rc::new(move |value| k(f(value))))
+        })
+    }
+}
+
+impl<'a, R: 'a, M: 'a, A: 'a> Pure<A> for ContT<'a, R, M, A>
+where
+    A: Clone,
+{
+    fn pure(value: A) -> Self {
+        Self::new(move |k| k(value.clone()))
+    }
+}
+
+impl<'a, R: 'a, M: 'a, A: 'a> Apply<'a, A> for ContT<'a, R, M, A> {
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        ContT::new(move |k: Rc<dyn Fn(B) -> M + 'a>| {
+            let this = self.clone();
+            f.run_rc(Rc::new(move |func: ApplyFn<'a, A, B>| {
+                let k = k.clone();
+                this.run_rc(Rc::new(move |value| k(func.apply_fn(value))))
+            }))
+        })
+    }
+}
+
+impl<'a, R: 'a, M: 'a, A: 'a> Bind<'a, A> for ContT<'a, R, M, A> {
+    fn bind<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> Self::Target<B> + 'a,
+    {
+        let f = Rc::new(f);
+        ContT::new(move |k: Rc<dyn Fn(B) -> M + 'a>| {
+            let f = f.clone();
+            self.run_rc(Rc::new(move |value| f(value).run_rc(k.clone())))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ContT;
+    use crate::{run, transformers::MonadTrans, Functor, Pure};
+
+    #[test]
+    fn cont_over_vec() {
+        let program: ContT<'_, u32, Vec<u32>, u32> = run! {
+            x <= ContT::lift(vec![1, 2]);
+            y <= ContT::call_cc(move |exit| {
+                run! {
+                    if x > 1 { exit(x * 100) } else { ContT::pure(()) };
+                    yield x * 10
+                }
+            });
+            yield y + 1
+        };
+        assert_eq!(program.eval_cont(), vec![11, 201]);
+    }
+
+    #[test]
+    fn shift_and_reset() {
+        let inner: ContT<'_, u32, Vec<u32>, u32> = ContT::shift(|k| {
+            let both = [k(1), k(2)].concat();
+            ContT::lift(both)
+        })
+        .fmap(|x| x * 10);
+        let program: ContT<'_, u32, Vec<u32>, u32> = inner.reset().fmap(|x| x + 1);
+        assert_eq!(program.eval_cont(), vec![11, 21]);
+    }
+}
@@ -0,0 +1,147 @@
+//! Monad transformers.
+//!
+//! A monad transformer wraps another monad, the "inner" monad, adding an
+//! extra effect on top of it. For instance, [`OptionT`](OptionT) adds the
+//! possibility of failure to any inner monad, and [`StateT`](StateT) threads
+//! a state value through it. Because transformers are themselves monads, you
+//! can use them with the [`run!`](crate::run) macro, and you can stack them on
+//! top of each other to combine several effects.
+//!
+//! ```
+//! # use higher::{run, transformers::{MonadTrans, OptionT}};
+//! let program: OptionT<Vec<Option<u32>>> = run! {
+//!     x <= OptionT::lift(vec![1, 2]);
+//!     y <= OptionT::new(vec![Some(10), None]);
+//!     yield x + y
+//! };
+//! assert_eq!(program.run_option(), vec![Some(11), None, Some(12), None]);
+//! ```
+//!
+//! Computations in the inner monad can be lifted into a transformer using
+//! [`MonadTrans::lift`](MonadTrans::lift).
+
+use std::collections::{LinkedList, VecDeque};
+
+use crate::{free::FreeFunctor, Bind, Monoid, NonEmpty, Pure, Writer};
+
+pub mod option;
+#[doc(inline)]
+pub use self::option::OptionT;
+
+pub mod result;
+#[doc(inline)]
+pub use self::result::{ExceptT, ResultT};
+
+pub mod state;
+#[doc(inline)]
+pub use self::state::StateT;
+
+pub mod reader;
+#[doc(inline)]
+pub use self::reader::ReaderT;
+
+pub mod writer;
+#[doc(inline)]
+pub use self::writer::WriterT;
+
+pub mod cont;
+#[doc(inline)]
+pub use self::cont::ContT;
+
+/// An `InnerMonad` is a monad which can be used as the inner monad of a monad
+/// transformer.
+///
+/// A transformer needs to be able to [`pure`](Pure::pure) and
+/// [`bind`](Bind::bind) values of any type inside its inner monad, but Rust
+/// has no way to say that `M::Target<T>` implements [`Pure`](Pure) and
+/// [`Bind`](Bind) for every `T`, so this trait provides versions of those
+/// methods which operate on the whole family of [`Target`](crate::Functor::Target)
+/// types at once, in the same way as [`FreeFunctor`](FreeFunctor) does for
+/// [`fmap`](crate::Functor::fmap).
+///
+/// For most monads, implementing this is simply a matter of calling their
+/// existing [`Pure`](Pure) and [`Bind`](Bind) implementations.
+pub trait InnerMonad<'a, A: 'a>: FreeFunctor<'a, A> {
+    /// Wrap a value of any type in the inner monad.
+    ///
+    /// The value has to implement [`Clone`](Clone) because some inner
+    /// monads, like [`StateT`](StateT), produce it again every time they're
+    /// run.
+    fn pure_target<T: Clone + 'a>(value: T) -> Self::Target<T>;
+
+    /// Chain a computation of any type in the inner monad.
+    fn bind_target<T: 'a, U: 'a, F>(m: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> Self::Target<U> + 'a;
+}
+
+/// A `MonadTrans` is a monad transformer which can lift a computation in its
+/// inner monad `M` into itself.
+pub trait MonadTrans<'a, A: 'a, M>: Sized {
+    /// Lift a computation from the inner monad into the transformer.
+    fn lift(m: M) -> Self;
+}
+
+impl<'a, A: 'a> InnerMonad<'a, A> for Option<A> {
+    fn pure_target<T: Clone + 'a>(value: T) -> Self::Target<T> {
+        Some(value)
+    }
+
+    fn bind_target<T: 'a, U: 'a, F>(m: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> Self::Target<U> + 'a,
+    {
+        m.and_then(f)
+    }
+}
+
+impl<'a, A: 'a, E: 'a> InnerMonad<'a, A> for Result<A, E> {
+    fn pure_target<T: Clone + 'a>(value: T) -> Self::Target<T> {
+        Ok(value)
+    }
+
+    fn bind_target<T: 'a, U: 'a, F>(m: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> Self::Target<U> + 'a,
+    {
+        m.and_then(f)
+    }
+}
+
+impl<'a, A: 'a, W: 'a> InnerMonad<'a, A> for Writer<W, A>
+where
+    W: Monoid,
+{
+    fn pure_target<T: Clone + 'a>(value: T) -> Self::Target<T> {
+        Pure::pure(value)
+    }
+
+    fn bind_target<T: 'a, U: 'a, F>(m: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> Self::Target<U> + 'a,
+    {
+        m.bind(f)
+    }
+}
+
+macro_rules! impl_inner_monad_for_list {
+    ($type:ident) => {
+        impl<'a, A: 'a> InnerMonad<'a, A> for $type<A> {
+            fn pure_target<T: Clone + 'a>(value: T) -> Self::Target<T> {
+                Pure::pure(value)
+            }
+
+            fn bind_target<T: 'a, U: 'a, F>(m: Self::Target<T>, f: F) -> Self::Target<U>
+            where
+                F: Fn(T) -> Self::Target<U> + 'a,
+            {
+                m.bind(f)
+            }
+        }
+    };
+}
+
+impl_inner_monad_for_list!(Vec);
+impl_inner_monad_for_list!(VecDeque);
+impl_inner_monad_for_list!(LinkedList);
+impl_inner_monad_for_list!(NonEmpty);
@@ -0,0 +1,258 @@
+use crate::{
+    apply::ApplyFn, free::FreeFunctor, ApplicativeError, Apply, Bind, Functor, MonadReader,
+    MonadState, MonadWriter, Pure,
+};
+
+use super::{InnerMonad, MonadTrans};
+
+/// A monad transformer which adds failure to an inner monad.
+///
+/// `OptionT<M>` wraps a value `M` of type `M<Option<A>>`. Binding over it runs
+/// the inner monad's computations in sequence as long as they keep producing
+/// [`Some`](Option::Some), but as soon as one produces
+/// [`None`](Option::None), the rest are skipped.
+///
+/// ```
+/// # use higher::{run, transformers::{MonadTrans, OptionT}};
+/// let program: OptionT<Result<Option<u32>, String>> = run! {
+///     x <= OptionT::lift(Ok(2));
+///     y <= OptionT::new(Ok(None::<u32>));
+///     yield x + y
+/// };
+/// assert_eq!(program.run_option(), Ok(None));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionT<M> {
+    run: M,
+}
+
+impl<M> OptionT<M> {
+    /// Construct an `OptionT` from a computation in the inner monad.
+    pub fn new(run: M) -> Self {
+        Self { run }
+    }
+
+    /// Get the wrapped computation in the inner monad.
+    pub fn run_option(self) -> M {
+        self.run
+    }
+}
+
+impl<'a, A: 'a, M, N> MonadTrans<'a, A, M> for OptionT<N>
+where
+    M: Functor<'a, A, Target<Option<A>> = N>,
+{
+    fn lift(m: M) -> Self {
+        Self::new(m.fmap(Some))
+    }
+}
+
+impl<'a, A: 'a, M> Functor<'a, A> for OptionT<M>
+where
+    M: Functor<'a, Option<A>>,
+{
+    type Target<T: 'a> = OptionT<M::Target<Option<T>>>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        OptionT::new(self.run.fmap(move |value| value.map(&f)))
+    }
+}
+
+impl<'a, A: 'a, M> FreeFunctor<'a, A> for OptionT<M>
+where
+    M: FreeFunctor<'a, Option<A>>,
+{
+    fn fmap_target<T: 'a, U: 'a, F>(fa: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> U + 'a,
+    {
+        OptionT::new(M::fmap_target::<Option<T>, Option<U>, _>(
+            fa.run,
+            move |value| value.map(&f),
+        ))
+    }
+}
+
+impl<A, M> Pure<A> for OptionT<M>
+where
+    M: Pure<Option<A>>,
+{
+    fn pure(value: A) -> Self {
+        Self::new(M::pure(Some(value)))
+    }
+}
+
+impl<'a, A: 'a, M> Apply<'a, A> for OptionT<M>
+where
+    M: InnerMonad<'a, Option<A>> + Apply<'a, Option<A>>,
+{
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        let f = M::fmap_target::<_, ApplyFn<'a, Option<A>, Option<B>>, _>(
+            f.run,
+            |func: Option<ApplyFn<'a, A, B>>| {
+                ApplyFn::from(move |value: Option<A>| match (&func, value) {
+                    (Some(func), Some(value)) => Some(func.apply_fn(value)),
+                    _ => None,
+                })
+            },
+        );
+        OptionT::new(self.run.apply(f))
+    }
+}
+
+impl<'a, A: 'a, M> Bind<'a, A> for OptionT<M>
+where
+    M: InnerMonad<'a, Option<A>>,
+{
+    fn bind<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> Self::Target<B> + 'a,
+    {
+        OptionT::new(M::bind_target::<Option<A>, Option<B>, _>(
+            self.run,
+            move |value| match value {
+                Some(value) => f(value).run,
+                None => M::fmap_target::<(), Option<B>, _>(M::pure_target(()), |()| None),
+            },
+        ))
+    }
+}
+
+impl<'a, A: 'a, M> InnerMonad<'a, A> for OptionT<M>
+where
+    M: InnerMonad<'a, Option<A>>,
+{
+    fn pure_target<T: Clone + 'a>(value: T) -> Self::Target<T> {
+        OptionT::new(M::pure_target(Some(value)))
+    }
+
+    fn bind_target<T: 'a, U: 'a, F>(m: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> Self::Target<U> + 'a,
+    {
+        OptionT::new(M::bind_target::<Option<T>, Option<U>, _>(
+            m.run,
+            move |value| match value {
+                Some(value) => f(value).run,
+                None => M::fmap_target::<(), Option<U>, _>(M::pure_target(()), |()| None),
+            },
+        ))
+    }
+}
+
+impl<'a, A: 'a, M> ApplicativeError<'a, A, ()> for OptionT<M>
+where
+    A: Clone,
+    M: InnerMonad<'a, Option<A>> + Pure<Option<A>>,
+{
+    fn throw_error(_error: ()) -> Self {
+        Self::new(M::pure(None))
+    }
+
+    fn handle_error_with<F>(self, f: F) -> Self
+    where
+        F: Fn(()) -> Self + 'a,
+    {
+        Self::new(M::bind_target::<Option<A>, Option<A>, _>(
+            self.run,
+            move |value| match value {
+                Some(value) => M::pure_target::<Option<A>>(Some(value)),
+                None => f(()).run,
+            },
+        ))
+    }
+}
+
+impl<'a, A: 'a, S: 'a, M> MonadState<'a, A, S> for OptionT<M>
+where
+    M: InnerMonad<'a, Option<A>> + MonadState<'a, Option<A>, S>,
+{
+    fn get() -> Self::Target<S> {
+        OptionT::new(M::fmap_target::<S, Option<S>, _>(M::get(), Some))
+    }
+
+    fn put(state: S) -> Self::Target<()> {
+        OptionT::new(M::fmap_target::<(), Option<()>, _>(M::put(state), Some))
+    }
+
+    fn modify<F>(f: F) -> Self::Target<()>
+    where
+        F: Fn(S) -> S + 'a,
+    {
+        OptionT::new(M::fmap_target::<(), Option<()>, _>(M::modify(f), Some))
+    }
+}
+
+impl<'a, A: 'a, R: 'a, M> MonadReader<'a, A, R> for OptionT<M>
+where
+    M: InnerMonad<'a, Option<A>> + MonadReader<'a, Option<A>, R>,
+{
+    fn ask() -> Self::Target<R> {
+        OptionT::new(M::fmap_target::<R, Option<R>, _>(M::ask(), Some))
+    }
+
+    fn local<F>(self, f: F) -> Self
+    where
+        F: Fn(R) -> R + 'a,
+    {
+        Self::new(self.run.local(f))
+    }
+}
+
+impl<'a, A: 'a, W: 'a, M> MonadWriter<'a, A, W> for OptionT<M>
+where
+    M: InnerMonad<'a, Option<A>> + MonadWriter<'a, Option<A>, W>,
+{
+    fn tell(output: W) -> Self::Target<()> {
+        OptionT::new(M::fmap_target::<(), Option<()>, _>(M::tell(output), Some))
+    }
+
+    fn listen(self) -> Self::Target<(A, W)> {
+        OptionT::new(M::fmap_target::<(Option<A>, W), Option<(A, W)>, _>(
+            self.run.listen(),
+            |(value, output)| value.map(|value| (value, output)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::OptionT;
+    use crate::{apply::lift2, run, transformers::MonadTrans, ApplicativeError, Functor};
+
+    #[test]
+    fn bind_short_circuits() {
+        let program: OptionT<Vec<Option<u32>>> = run! {
+            x <= OptionT::new(vec![Some(1), None, Some(2)]);
+            y <= OptionT::lift(vec![10, 20]);
+            yield x + y
+        };
+        assert_eq!(
+            program.run_option(),
+            vec![Some(11), Some(21), None, Some(12), Some(22)]
+        );
+    }
+
+    #[test]
+    fn functor_and_apply() {
+        let value: OptionT<Result<Option<u32>, ()>> = OptionT::new(Ok(Some(2)));
+        assert_eq!(value.clone().fmap(|x| x * 2).run_option(), Ok(Some(4)));
+        let sum = lift2(&|a: u32, b: u32| a + b, value, OptionT::new(Ok(Some(40))));
+        assert_eq!(sum.run_option(), Ok(Some(42)));
+    }
+
+    #[test]
+    fn handle_error() {
+        let value: OptionT<Vec<Option<u32>>> = OptionT::throw_error(());
+        assert_eq!(value.clone().run_option(), vec![None]);
+        assert_eq!(value.handle_error(|_| 5).run_option(), vec![Some(5)]);
+    }
+}
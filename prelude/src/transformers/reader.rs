@@ -0,0 +1,339 @@
+use std::rc::Rc;
+
+use crate::{
+    apply::ApplyFn, free::FreeFunctor, ApplicativeError, Apply, Bind, Functor, MonadReader,
+    MonadState, MonadWriter, Pure,
+};
+
+use super::{InnerMonad, MonadTrans};
+
+/// A monad transformer which provides a shared environment to an inner
+/// monad.
+///
+/// This wraps a function `Fn(R) -> M`, where `M` is a computation of type
+/// `M<A>` in the inner monad. It works just like [`Reader`](crate::Reader),
+/// except that each step can also use the effects of the inner monad.
+///
+/// `ReaderT` can itself be the inner monad of another transformer, as long
+/// as the environment implements [`Clone`](Clone), so that a stack like
+/// `ResultT<E, ReaderT<R, M>>` can both fail and read the environment.
+///
+/// ```
+/// # use higher::{run, transformers::ReaderT};
+/// fn lookup<'a>(key: &'static str) -> ReaderT<'a, Vec<(&'static str, u32)>, Option<u32>> {
+///     ReaderT::new(move |env: Vec<(&'static str, u32)>| {
+///         env.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+///     })
+/// }
+///
+/// let program = run! {
+///     x <= lookup("x");
+///     y <= lookup("y");
+///     yield x + y
+/// };
+/// assert_eq!(program.run_reader(vec![("x", 2), ("y", 40)]), Some(42));
+/// assert_eq!(program.run_reader(vec![("x", 2)]), None);
+/// ```
+pub struct ReaderT<'a, R, M> {
+    run: Rc<dyn Fn(R) -> M + 'a>,
+}
+
+impl<'a, R: 'a, M: 'a> ReaderT<'a, R, M> {
+    /// Construct a `ReaderT` from a function which takes an environment and
+    /// returns a computation in the inner monad.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(R) -> M + 'a,
+    {
+        Self { run: Rc::new(f) }
+    }
+
+    /// Construct a `ReaderT` which returns the environment.
+    pub fn ask() -> Self
+    where
+        M: Pure<R>,
+    {
+        Self::new(M::pure)
+    }
+
+    /// Construct a `ReaderT` which returns the result of applying a function
+    /// to the environment.
+    pub fn asks<A, F>(f: F) -> Self
+    where
+        F: Fn(&R) -> A + 'a,
+        M: Pure<A>,
+    {
+        Self::new(move |env| M::pure(f(&env)))
+    }
+
+    /// Run the computation in an environment modified by the given function.
+    pub fn local<F>(self, f: F) -> Self
+    where
+        F: Fn(R) -> R + 'a,
+    {
+        Self::new(move |env| self.run_reader(f(env)))
+    }
+
+    /// Lift a computation from the inner monad into a `ReaderT`, using a
+    /// function to construct the computation.
+    ///
+    /// Unlike [`MonadTrans::lift`](MonadTrans::lift), this doesn't require
+    /// the inner computation to implement [`Clone`](Clone).
+    pub fn lift_with<F>(f: F) -> Self
+    where
+        F: Fn() -> M + 'a,
+    {
+        Self::new(move |_| f())
+    }
+
+    /// Run the computation with the given environment, returning the
+    /// computation in the inner monad.
+    pub fn run_reader(&self, env: R) -> M {
+        (self.run)(env)
+    }
+}
+
+impl<'a, R, M> Clone for ReaderT<'a, R, M> {
+    fn clone(&self) -> Self {
+        Self {
+            run: self.run.clone(),
+        }
+    }
+}
+
+impl<'a, R, M> std::fmt::Debug for ReaderT<'a, R, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ReaderT<{}, {}>",
+            std::any::type_name::<R>(),
+            std::any::type_name::<M>()
+        )
+    }
+}
+
+impl<'a, A: 'a, R: 'a, M> MonadTrans<'a, A, M> for ReaderT<'a, R, M>
+where
+    M: Functor<'a, A> + Clone + 'a,
+{
+    fn lift(m: M) -> Self {
+        Self::lift_with(move || m.clone())
+    }
+}
+
+impl<'a, A: 'a, R: 'a, M: 'a> Functor<'a, A> for ReaderT<'a, R, M>
+where
+    M: Functor<'a, A>,
+{
+    type Target<T: 'a> = ReaderT<'a, R, M::Target<T>>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        let f = Rc::new(f);
+        ReaderT::new(move |env| {
+            let f = f.clone();
+            self.run_reader(env).fmap(move |value| f(value))
+        })
+    }
+}
+
+impl<'a, A: 'a, R: 'a, M: 'a> FreeFunctor<'a, A> for ReaderT<'a, R, M>
+where
+    M: FreeFunctor<'a, A>,
+{
+    fn fmap_target<T: 'a, U: 'a, F>(fa: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> U + 'a,
+    {
+        let f = Rc::new(f);
+        ReaderT::new(move |env| {
+            let f = f.clone();
+            M::fmap_target::<T, U, _>(fa.run_reader(env), move |value| f(value))
+        })
+    }
+}
+
+impl<'a, A: 'a, R: 'a, M: 'a> Pure<A> for ReaderT<'a, R, M>
+where
+    A: Clone,
+    M: Pure<A>,
+{
+    fn pure(value: A) -> Self {
+        Self::new(move |_| M::pure(value.clone()))
+    }
+}
+
+impl<'a, A: 'a, R: 'a, M: 'a> Apply<'a, A> for ReaderT<'a, R, M>
+where
+    R: Clone,
+    M: Apply<'a, A>,
+{
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        ReaderT::new(move |env: R| self.run_reader(env.clone()).apply(f.run_reader(env)))
+    }
+}
+
+impl<'a, A: 'a, R: 'a, M: 'a> Bind<'a, A> for ReaderT<'a, R, M>
+where
+    R: Clone,
+    M: InnerMonad<'a, A>,
+{
+    fn bind<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> Self::Target<B> + 'a,
+    {
+        let f = Rc::new(f);
+        ReaderT::new(move |env: R| {
+            let f = f.clone();
+            let next = env.clone();
+            M::bind_target::<A, B, _>(self.run_reader(env), move |value| {
+                f(value).run_reader(next.clone())
+            })
+        })
+    }
+}
+
+impl<'a, A: 'a, R: 'a, M: 'a> InnerMonad<'a, A> for ReaderT<'a, R, M>
+where
+    R: Clone,
+    M: InnerMonad<'a, A>,
+{
+    fn pure_target<T: Clone + 'a>(value: T) -> Self::Target<T> {
+        ReaderT::new(move |_| M::pure_target(value.clone()))
+    }
+
+    fn bind_target<T: 'a, U: 'a, F>(m: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> Self::Target<U> + 'a,
+    {
+        let f = Rc::new(f);
+        ReaderT::new(move |env: R| {
+            let f = f.clone();
+            let next = env.clone();
+            M::bind_target::<T, U, _>(m.run_reader(env), move |value| {
+                f(value).run_reader(next.clone())
+            })
+        })
+    }
+}
+
+impl<'a, A: 'a, R: 'a, M: 'a> MonadReader<'a, A, R> for ReaderT<'a, R, M>
+where
+    R: Clone,
+    M: InnerMonad<'a, A>,
+{
+    fn ask() -> Self::Target<R> {
+        ReaderT::new(M::pure_target)
+    }
+
+    fn local<F>(self, f: F) -> Self
+    where
+        F: Fn(R) -> R + 'a,
+    {
+        ReaderT::local(self, f)
+    }
+}
+
+impl<'a, A: 'a, R: 'a, S: 'a, M: 'a> MonadState<'a, A, S> for ReaderT<'a, R, M>
+where
+    R: Clone,
+    S: Clone,
+    M: InnerMonad<'a, A> + MonadState<'a, A, S>,
+{
+    fn get() -> Self::Target<S> {
+        ReaderT::new(|_| M::get())
+    }
+
+    fn put(state: S) -> Self::Target<()> {
+        ReaderT::new(move |_| M::put(state.clone()))
+    }
+
+    fn modify<F>(f: F) -> Self::Target<()>
+    where
+        F: Fn(S) -> S + 'a,
+    {
+        let f = Rc::new(f);
+        ReaderT::new(move |_| {
+            let f = f.clone();
+            M::modify(move |state| f(state))
+        })
+    }
+}
+
+impl<'a, A: 'a, R: 'a, W: 'a, M: 'a> MonadWriter<'a, A, W> for ReaderT<'a, R, M>
+where
+    R: Clone,
+    W: Clone,
+    M: InnerMonad<'a, A> + MonadWriter<'a, A, W>,
+{
+    fn tell(output: W) -> Self::Target<()> {
+        ReaderT::new(move |_| M::tell(output.clone()))
+    }
+
+    fn listen(self) -> Self::Target<(A, W)> {
+        ReaderT::new(move |env| self.run_reader(env).listen())
+    }
+}
+
+impl<'a, A: 'a, R: 'a, E: 'a, M: 'a> ApplicativeError<'a, A, E> for ReaderT<'a, R, M>
+where
+    A: Clone,
+    R: Clone,
+    E: Clone,
+    M: ApplicativeError<'a, A, E>,
+{
+    fn throw_error(error: E) -> Self {
+        ReaderT::new(move |_| M::throw_error(error.clone()))
+    }
+
+    fn handle_error_with<F>(self, f: F) -> Self
+    where
+        F: Fn(E) -> Self + 'a,
+    {
+        let f = Rc::new(f);
+        ReaderT::new(move |env: R| {
+            let f = f.clone();
+            let retry = env.clone();
+            self.run_reader(env)
+                .handle_error_with(move |error| f(error).run_reader(retry.clone()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ReaderT;
+    use crate::{apply::lift2, run, transformers::MonadTrans, Functor};
+
+    #[test]
+    fn ask_asks_local() {
+        let program: ReaderT<'_, u32, Vec<u32>> = run! {
+            x <= ReaderT::<_, Vec<_>>::ask();
+            y <= ReaderT::<_, Vec<_>>::asks(|env: &u32| env * 2).local(|env| env + 1);
+            z <= ReaderT::lift(vec![0, 100]);
+            yield x + y + z
+        };
+        assert_eq!(program.run_reader(10), vec![32, 132]);
+    }
+
+    #[test]
+    fn functor_and_apply() {
+        let reader: ReaderT<'_, u32, Option<u32>> =
+            ReaderT::<_, Option<_>>::asks(|env: &u32| env + 1).fmap(|x| x * 2);
+        assert_eq!(reader.run_reader(20), Some(42));
+        let sum = lift2(
+            &|a: u32, b: u32| a + b,
+            reader,
+            ReaderT::new(|env: u32| env.checked_sub(20)),
+        );
+        assert_eq!(sum.run_reader(20), Some(42));
+        assert_eq!(sum.run_reader(10), None);
+    }
+}
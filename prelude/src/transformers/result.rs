@@ -0,0 +1,299 @@
+use std::marker::PhantomData;
+
+use crate::{
+    apply::ApplyFn, free::FreeFunctor, ApplicativeError, Apply, Bind, Functor, MonadReader,
+    MonadState, MonadWriter, Pure,
+};
+
+use super::{InnerMonad, MonadTrans};
+
+/// A monad transformer which adds errors to an inner monad.
+///
+/// `ResultT<E, M>` wraps a value `M` of type `M<Result<A, E>>`. Binding over
+/// it runs the inner monad's computations in sequence as long as they keep
+/// producing [`Ok`](Result::Ok), but as soon as one produces an
+/// [`Err`](Result::Err), the rest are skipped.
+///
+/// This is also available under its traditional name,
+/// [`ExceptT`](ExceptT).
+///
+/// ```
+/// # use higher::{run, transformers::{MonadTrans, ResultT}};
+/// fn parse(input: &str) -> ResultT<String, Vec<Result<u32, String>>> {
+///     ResultT::new(vec![input.parse().map_err(|_| format!("bad input: {}", input))])
+/// }
+///
+/// let program = run! {
+///     x <= parse("2");
+///     y <= ResultT::lift(vec![10, 20]);
+///     yield x + y
+/// };
+/// assert_eq!(program.run_result(), vec![Ok(12), Ok(22)]);
+///
+/// let program = run! {
+///     x <= parse("two");
+///     y <= ResultT::lift(vec![10, 20]);
+///     yield x + y
+/// };
+/// assert_eq!(program.run_result(), vec![Err("bad input: two".to_string())]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultT<E, M> {
+    run: M,
+    error: PhantomData<E>,
+}
+
+/// `ExceptT` is an alias for [`ResultT`](ResultT).
+pub type ExceptT<E, M> = ResultT<E, M>;
+
+impl<E, M> ResultT<E, M> {
+    /// Construct a `ResultT` from a computation in the inner monad.
+    pub fn new(run: M) -> Self {
+        Self {
+            run,
+            error: PhantomData,
+        }
+    }
+
+    /// Get the wrapped computation in the inner monad.
+    pub fn run_result(self) -> M {
+        self.run
+    }
+}
+
+impl<'a, A: 'a, E: 'a, M, N> MonadTrans<'a, A, M> for ResultT<E, N>
+where
+    M: Functor<'a, A, Target<Result<A, E>> = N>,
+{
+    fn lift(m: M) -> Self {
+        Self::new(m.fmap(Ok))
+    }
+}
+
+impl<'a, A: 'a, E: 'a, M> Functor<'a, A> for ResultT<E, M>
+where
+    M: Functor<'a, Result<A, E>>,
+{
+    type Target<T: 'a> = ResultT<E, M::Target<Result<T, E>>>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        ResultT::new(self.run.fmap(move |value| value.map(&f)))
+    }
+}
+
+impl<'a, A: 'a, E: 'a, M> FreeFunctor<'a, A> for ResultT<E, M>
+where
+    M: FreeFunctor<'a, Result<A, E>>,
+{
+    fn fmap_target<T: 'a, U: 'a, F>(fa: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> U + 'a,
+    {
+        ResultT::new(M::fmap_target::<Result<T, E>, Result<U, E>, _>(
+            fa.run,
+            move |value| value.map(&f),
+        ))
+    }
+}
+
+impl<A, E, M> Pure<A> for ResultT<E, M>
+where
+    M: Pure<Result<A, E>>,
+{
+    fn pure(value: A) -> Self {
+        Self::new(M::pure(Ok(value)))
+    }
+}
+
+impl<'a, A: 'a, E: 'a, M> Apply<'a, A> for ResultT<E, M>
+where
+    E: Clone,
+    M: InnerMonad<'a, Result<A, E>> + Apply<'a, Result<A, E>>,
+{
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        let f = M::fmap_target::<_, ApplyFn<'a, Result<A, E>, Result<B, E>>, _>(
+            f.run,
+            |func: Result<ApplyFn<'a, A, B>, E>| {
+                ApplyFn::from(move |value: Result<A, E>| match (&func, value) {
+                    (Ok(func), Ok(value)) => Ok(func.apply_fn(value)),
+                    (Err(error), _) => Err(error.clone()),
+                    (_, Err(error)) => Err(error),
+                })
+            },
+        );
+        ResultT::new(self.run.apply(f))
+    }
+}
+
+impl<'a, A: 'a, E: 'a, M> Bind<'a, A> for ResultT<E, M>
+where
+    E: Clone,
+    M: InnerMonad<'a, Result<A, E>>,
+{
+    fn bind<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> Self::Target<B> + 'a,
+    {
+        ResultT::new(M::bind_target::<Result<A, E>, Result<B, E>, _>(
+            self.run,
+            move |value| match value {
+                Ok(value) => f(value).run,
+                Err(error) => M::fmap_target::<E, Result<B, E>, _>(M::pure_target(error), Err),
+            },
+        ))
+    }
+}
+
+impl<'a, A: 'a, E: 'a, M> InnerMonad<'a, A> for ResultT<E, M>
+where
+    E: Clone,
+    M: InnerMonad<'a, Result<A, E>>,
+{
+    fn pure_target<T: Clone + 'a>(value: T) -> Self::Target<T> {
+        ResultT::new(M::pure_target(Ok(value)))
+    }
+
+    fn bind_target<T: 'a, U: 'a, F>(m: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> Self::Target<U> + 'a,
+    {
+        ResultT::new(M::bind_target::<Result<T, E>, Result<U, E>, _>(
+            m.run,
+            move |value| match value {
+                Ok(value) => f(value).run,
+                Err(error) => M::fmap_target::<E, Result<U, E>, _>(M::pure_target(error), Err),
+            },
+        ))
+    }
+}
+
+impl<'a, A: 'a, E: 'a, M> ApplicativeError<'a, A, E> for ResultT<E, M>
+where
+    A: Clone,
+    E: Clone,
+    M: InnerMonad<'a, Result<A, E>> + Pure<Result<A, E>>,
+{
+    fn throw_error(error: E) -> Self {
+        Self::new(M::pure(Err(error)))
+    }
+
+    fn handle_error_with<F>(self, f: F) -> Self
+    where
+        F: Fn(E) -> Self + 'a,
+    {
+        Self::new(M::bind_target::<Result<A, E>, Result<A, E>, _>(
+            self.run,
+            move |value| match value {
+                Ok(value) => M::pure_target::<Result<A, E>>(Ok(value)),
+                Err(error) => f(error).run,
+            },
+        ))
+    }
+}
+
+impl<'a, A: 'a, E: 'a, S: 'a, M> MonadState<'a, A, S> for ResultT<E, M>
+where
+    E: Clone,
+    M: InnerMonad<'a, Result<A, E>> + MonadState<'a, Result<A, E>, S>,
+{
+    fn get() -> Self::Target<S> {
+        ResultT::new(M::fmap_target::<S, Result<S, E>, _>(M::get(), Ok))
+    }
+
+    fn put(state: S) -> Self::Target<()> {
+        ResultT::new(M::fmap_target::<(), Result<(), E>, _>(M::put(state), Ok))
+    }
+
+    fn modify<F>(f: F) -> Self::Target<()>
+    where
+        F: Fn(S) -> S + 'a,
+    {
+        ResultT::new(M::fmap_target::<(), Result<(), E>, _>(M::modify(f), Ok))
+    }
+}
+
+impl<'a, A: 'a, E: 'a, R: 'a, M> MonadReader<'a, A, R> for ResultT<E, M>
+where
+    E: Clone,
+    M: InnerMonad<'a, Result<A, E>> + MonadReader<'a, Result<A, E>, R>,
+{
+    fn ask() -> Self::Target<R> {
+        ResultT::new(M::fmap_target::<R, Result<R, E>, _>(M::ask(), Ok))
+    }
+
+    fn local<F>(self, f: F) -> Self
+    where
+        F: Fn(R) -> R + 'a,
+    {
+        Self::new(self.run.local(f))
+    }
+}
+
+impl<'a, A: 'a, E: 'a, W: 'a, M> MonadWriter<'a, A, W> for ResultT<E, M>
+where
+    E: Clone,
+    M: InnerMonad<'a, Result<A, E>> + MonadWriter<'a, Result<A, E>, W>,
+{
+    fn tell(output: W) -> Self::Target<()> {
+        ResultT::new(M::fmap_target::<(), Result<(), E>, _>(M::tell(output), Ok))
+    }
+
+    fn listen(self) -> Self::Target<(A, W)> {
+        ResultT::new(M::fmap_target::<(Result<A, E>, W), Result<(A, W), E>, _>(
+            self.run.listen(),
+            |(value, output)| value.map(|value| (value, output)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ExceptT, ResultT};
+    use crate::{apply::lift2, run, transformers::MonadTrans, ApplicativeError, Functor};
+
+    #[test]
+    fn bind_short_circuits() {
+        let program: ResultT<&str, Vec<Result<u32, &str>>> = run! {
+            x <= ResultT::new(vec![Ok(1), Err("nope"), Ok(2)]);
+            y <= ResultT::lift(vec![10, 20]);
+            yield x + y
+        };
+        assert_eq!(
+            program.run_result(),
+            vec![Ok(11), Ok(21), Err("nope"), Ok(12), Ok(22)]
+        );
+    }
+
+    #[test]
+    fn functor_and_apply() {
+        let value: ExceptT<&str, Option<Result<u32, &str>>> = ResultT::new(Some(Ok(2)));
+        assert_eq!(value.clone().fmap(|x| x * 2).run_result(), Some(Ok(4)));
+        let sum = lift2(
+            &|a: u32, b: u32| a + b,
+            value.clone(),
+            ResultT::new(Some(Ok(40))),
+        );
+        assert_eq!(sum.run_result(), Some(Ok(42)));
+        let failed = lift2(&|a: u32, b: u32| a + b, value, ResultT::throw_error("fail"));
+        assert_eq!(failed.run_result(), Some(Err("fail")));
+    }
+
+    #[test]
+    fn handle_error() {
+        let value: ResultT<String, Vec<Result<usize, String>>> =
+            ResultT::throw_error("four".to_string());
+        assert_eq!(value.clone().run_result(), vec![Err("four".to_string())]);
+        assert_eq!(
+            value.handle_error(|error| error.len()).run_result(),
+            vec![Ok(4)]
+        );
+    }
+}
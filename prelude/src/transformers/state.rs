@@ -0,0 +1,385 @@
+use std::rc::Rc;
+
+use crate::{
+    apply::ApplyFn, free::FreeFunctor, ApplicativeError, Apply, Bind, Functor, MonadReader,
+    MonadState, MonadWriter, Pure,
+};
+
+use super::{InnerMonad, MonadTrans};
+
+/// A monad transformer which threads a state value through an inner monad.
+///
+/// This wraps a function `Fn(S) -> M`, where `M` is a computation of type
+/// `M<(A, S)>` in the inner monad, producing a result along with a new state.
+/// It works just like [`State`](crate::State), except that each step can also
+/// use the effects of the inner monad.
+///
+/// `StateT` can itself be the inner monad of another transformer, as long as
+/// the state implements [`Clone`](Clone), so that a stack like
+/// `OptionT<StateT<S, M>>` can both fail and use the state.
+///
+/// ```
+/// # use higher::{run, transformers::{MonadTrans, StateT}};
+/// let program: StateT<'_, u32, Vec<(u32, u32)>> = run! {
+///     x <= StateT::lift(vec![1, 2]);
+///     StateT::<_, Vec<_>>::modify(move |s| s + x);
+///     yield x * 10
+/// };
+/// assert_eq!(program.run_state(100), vec![(10, 101), (20, 102)]);
+/// ```
+pub struct StateT<'a, S, M> {
+    run: Rc<dyn Fn(S) -> M + 'a>,
+}
+
+impl<'a, S: 'a, M: 'a> StateT<'a, S, M> {
+    /// Construct a `StateT` from a function which takes a state and returns a
+    /// computation in the inner monad.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(S) -> M + 'a,
+    {
+        Self { run: Rc::new(f) }
+    }
+
+    /// Construct a `StateT` which returns the current state.
+    pub fn get() -> Self
+    where
+        S: Clone,
+        M: Pure<(S, S)>,
+    {
+        Self::new(|state: S| M::pure((state.clone(), state)))
+    }
+
+    /// Construct a `StateT` which returns the result of applying a function
+    /// to the current state, leaving the state unchanged.
+    pub fn gets<A, F>(f: F) -> Self
+    where
+        F: Fn(&S) -> A + 'a,
+        M: Pure<(A, S)>,
+    {
+        Self::new(move |state| M::pure((f(&state), state)))
+    }
+
+    /// Construct a `StateT` which replaces the current state with the given
+    /// value.
+    pub fn put(state: S) -> Self
+    where
+        S: Clone,
+        M: Pure<((), S)>,
+    {
+        Self::new(move |_| M::pure(((), state.clone())))
+    }
+
+    /// Construct a `StateT` which updates the current state using the given
+    /// function.
+    pub fn modify<F>(f: F) -> Self
+    where
+        F: Fn(S) -> S + 'a,
+        M: Pure<((), S)>,
+    {
+        Self::new(move |state| M::pure(((), f(state))))
+    }
+
+    /// Lift a computation from the inner monad into a `StateT`, using a
+    /// function to construct the computation.
+    ///
+    /// Unlike [`MonadTrans::lift`](MonadTrans::lift), this doesn't require
+    /// the inner computation to implement [`Clone`](Clone).
+    pub fn lift_with<A, L, F>(f: F) -> Self
+    where
+        A: 'a,
+        S: Clone,
+        L: Functor<'a, A, Target<(A, S)> = M>,
+        F: Fn() -> L + 'a,
+    {
+        Self::new(move |state: S| f().fmap(move |value| (value, state.clone())))
+    }
+
+    /// Run the computation with the given initial state, returning the
+    /// computation in the inner monad.
+    pub fn run_state(&self, state: S) -> M {
+        (self.run)(state)
+    }
+}
+
+impl<'a, S, M> Clone for StateT<'a, S, M> {
+    fn clone(&self) -> Self {
+        Self {
+            run: self.run.clone(),
+        }
+    }
+}
+
+impl<'a, S, M> std::fmt::Debug for StateT<'a, S, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "StateT<{}, {}>",
+            std::any::type_name::<S>(),
+            std::any::type_name::<M>()
+        )
+    }
+}
+
+impl<'a, A: 'a, S: 'a, M, N: 'a> MonadTrans<'a, A, M> for StateT<'a, S, N>
+where
+    S: Clone,
+    M: Functor<'a, A, Target<(A, S)> = N> + Clone + 'a,
+{
+    fn lift(m: M) -> Self {
+        Self::lift_with(move || m.clone())
+    }
+}
+
+impl<'a, A: 'a, S: 'a, M: 'a> Functor<'a, A> for StateT<'a, S, M>
+where
+    M: Functor<'a, (A, S)>,
+{
+    type Target<T: 'a> = StateT<'a, S, M::Target<(T, S)>>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        let f = Rc::new(f);
+        StateT::new(move |state| {
+            let f = f.clone();
+            self.run_state(state)
+                .fmap(move |(value, state)| (f(value), state))
+        })
+    }
+}
+
+impl<'a, A: 'a, S: 'a, M: 'a> FreeFunctor<'a, A> for StateT<'a, S, M>
+where
+    M: FreeFunctor<'a, (A, S)>,
+{
+    fn fmap_target<T: 'a, U: 'a, F>(fa: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> U + 'a,
+    {
+        let f = Rc::new(f);
+        StateT::new(move |state| {
+            let f = f.clone();
+            M::fmap_target::<(T, S), (U, S), _>(fa.run_state(state), move |(value, state)| {
+                (f(value), state)
+            })
+        })
+    }
+}
+
+impl<'a, A: 'a, S: 'a, M: 'a> Pure<A> for StateT<'a, S, M>
+where
+    A: Clone,
+    M: Pure<(A, S)>,
+{
+    fn pure(value: A) -> Self {
+        Self::new(move |state| M::pure((value.clone(), state)))
+    }
+}
+
+impl<'a, A: 'a, S: 'a, M: 'a> Apply<'a, A> for StateT<'a, S, M>
+where
+    M: InnerMonad<'a, (A, S)>,
+{
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        StateT::new(move |state| {
+            let this = self.clone();
+            M::bind_target::<(ApplyFn<'a, A, B>, S), (B, S), _>(
+                f.run_state(state),
+                move |(func, state)| {
+                    M::fmap_target::<(A, S), (B, S), _>(
+                        this.run_state(state),
+                        move |(value, state)| (func.apply_fn(value), state),
+                    )
+                },
+            )
+        })
+    }
+}
+
+impl<'a, A: 'a, S: 'a, M: 'a> Bind<'a, A> for StateT<'a, S, M>
+where
+    M: InnerMonad<'a, (A, S)>,
+{
+    fn bind<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> Self::Target<B> + 'a,
+    {
+        let f = Rc::new(f);
+        StateT::new(move |state| {
+            let f = f.clone();
+            M::bind_target::<(A, S), (B, S), _>(self.run_state(state), move |(value, state)| {
+                f(value).run_state(state)
+            })
+        })
+    }
+}
+
+impl<'a, A: 'a, S: 'a, M: 'a> InnerMonad<'a, A> for StateT<'a, S, M>
+where
+    S: Clone,
+    M: InnerMonad<'a, (A, S)>,
+{
+    fn pure_target<T: Clone + 'a>(value: T) -> Self::Target<T> {
+        StateT::new(move |state| M::pure_target((value.clone(), state)))
+    }
+
+    fn bind_target<T: 'a, U: 'a, F>(m: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> Self::Target<U> + 'a,
+    {
+        let f = Rc::new(f);
+        StateT::new(move |state| {
+            let f = f.clone();
+            M::bind_target::<(T, S), (U, S), _>(m.run_state(state), move |(value, state)| {
+                f(value).run_state(state)
+            })
+        })
+    }
+}
+
+impl<'a, A: 'a, S: 'a, M: 'a> MonadState<'a, A, S> for StateT<'a, S, M>
+where
+    S: Clone,
+    M: InnerMonad<'a, (A, S)>,
+{
+    fn get() -> Self::Target<S> {
+        StateT::new(|state: S| M::pure_target((state.clone(), state)))
+    }
+
+    fn put(state: S) -> Self::Target<()> {
+        StateT::new(move |_| M::pure_target(((), state.clone())))
+    }
+
+    fn modify<F>(f: F) -> Self::Target<()>
+    where
+        F: Fn(S) -> S + 'a,
+    {
+        StateT::new(move |state| M::pure_target(((), f(state))))
+    }
+}
+
+impl<'a, A: 'a, S: 'a, R: 'a, M: 'a> MonadReader<'a, A, R> for StateT<'a, S, M>
+where
+    S: Clone,
+    M: InnerMonad<'a, (A, S)> + MonadReader<'a, (A, S), R>,
+{
+    fn ask() -> Self::Target<R> {
+        StateT::new(|state: S| {
+            M::fmap_target::<R, (R, S), _>(M::ask(), move |env| (env, state.clone()))
+        })
+    }
+
+    fn local<F>(self, f: F) -> Self
+    where
+        F: Fn(R) -> R + 'a,
+    {
+        let f = Rc::new(f);
+        StateT::new(move |state| {
+            let f = f.clone();
+            self.run_state(state).local(move |env| f(env))
+        })
+    }
+}
+
+impl<'a, A: 'a, S: 'a, W: 'a, M: 'a> MonadWriter<'a, A, W> for StateT<'a, S, M>
+where
+    S: Clone,
+    W: Clone,
+    M: InnerMonad<'a, (A, S)> + MonadWriter<'a, (A, S), W>,
+{
+    fn tell(output: W) -> Self::Target<()> {
+        StateT::new(move |state: S| {
+            M::fmap_target::<(), ((), S), _>(M::tell(output.clone()), move |()| ((), state.clone()))
+        })
+    }
+
+    fn listen(self) -> Self::Target<(A, W)> {
+        StateT::new(move |state| {
+            M::fmap_target::<((A, S), W), ((A, W), S), _>(
+                self.run_state(state).listen(),
+                |((value, state), output)| ((value, output), state),
+            )
+        })
+    }
+}
+
+impl<'a, A: 'a, S: 'a, E: 'a, M: 'a> ApplicativeError<'a, A, E> for StateT<'a, S, M>
+where
+    A: Clone,
+    S: Clone,
+    E: Clone,
+    M: ApplicativeError<'a, (A, S), E>,
+{
+    fn throw_error(error: E) -> Self {
+        StateT::new(move |_| M::throw_error(error.clone()))
+    }
+
+    fn handle_error_with<F>(self, f: F) -> Self
+    where
+        F: Fn(E) -> Self + 'a,
+    {
+        let f = Rc::new(f);
+        StateT::new(move |state: S| {
+            let f = f.clone();
+            let retry = state.clone();
+            self.run_state(state)
+                .handle_error_with(move |error| f(error).run_state(retry.clone()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::StateT;
+    use crate::{apply::lift2, run, transformers::MonadTrans, Functor, Pure};
+
+    #[test]
+    fn state_over_option() {
+        fn pop<'a>() -> StateT<'a, Vec<u32>, Option<(u32, Vec<u32>)>> {
+            StateT::new(|mut stack: Vec<u32>| stack.pop().map(|value| (value, stack)))
+        }
+
+        let program = run! {
+            a <= pop();
+            b <= pop();
+            StateT::<_, Option<_>>::modify(move |mut stack: Vec<u32>| {
+                stack.push(a + b);
+                stack
+            });
+            yield a * b
+        };
+        assert_eq!(program.run_state(vec![1, 2, 3]), Some((6, vec![1, 5])));
+        assert_eq!(program.run_state(vec![1]), None);
+    }
+
+    #[test]
+    fn get_put_lift() {
+        let program: StateT<'_, u32, Vec<(u32, u32)>> = run! {
+            s <= StateT::<_, Vec<_>>::get();
+            x <= StateT::lift(vec![s, s + 1]);
+            StateT::<_, Vec<_>>::put(x * 2);
+            StateT::gets(|s: &u32| s + 1)
+        };
+        assert_eq!(program.run_state(5), vec![(11, 10), (13, 12)]);
+    }
+
+    #[test]
+    fn functor_and_apply() {
+        let state: StateT<'_, i32, Result<(i32, i32), ()>> =
+            StateT::<_, Result<_, _>>::gets(|s: &i32| s * 2).fmap(|x| x + 1);
+        assert_eq!(state.run_state(5), Ok((11, 5)));
+        let sum = lift2(
+            &|a: i32, b: i32| a + b,
+            StateT::<_, Result<_, ()>>::pure(2),
+            StateT::gets(|s: &i32| *s),
+        );
+        assert_eq!(sum.run_state(40), Ok((42, 40)));
+    }
+}
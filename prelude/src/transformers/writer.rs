@@ -0,0 +1,317 @@
+use crate::{
+    apply::ApplyFn, free::FreeFunctor, ApplicativeError, Apply, Bind, Functor, MonadReader,
+    MonadState, MonadWriter, Monoid, Pure, Semigroup,
+};
+
+use super::{InnerMonad, MonadTrans};
+
+/// A monad transformer which accumulates output alongside an inner monad.
+///
+/// `WriterT<W, M>` wraps a value `M` of type `M<(A, W)>`. It works just like
+/// [`Writer`](crate::Writer), except that each step can also use the effects
+/// of the inner monad.
+///
+/// ```
+/// # use higher::{run, transformers::{MonadTrans, WriterT}};
+/// let program: WriterT<Vec<String>, Vec<(u32, Vec<String>)>> = run! {
+///     x <= WriterT::lift(vec![1, 2]);
+///     WriterT::<_, Vec<_>>::tell(vec![format!("got {}", x)]);
+///     yield x * 10
+/// };
+/// assert_eq!(
+///     program.run_writer(),
+///     vec![(10, vec!["got 1".to_string()]), (20, vec!["got 2".to_string()])]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriterT<W, M> {
+    run: M,
+    output: std::marker::PhantomData<W>,
+}
+
+impl<W, M> WriterT<W, M> {
+    /// Construct a `WriterT` from a computation in the inner monad.
+    pub fn new(run: M) -> Self {
+        Self {
+            run,
+            output: std::marker::PhantomData,
+        }
+    }
+
+    /// Construct a `WriterT` which adds the given value to the output.
+    pub fn tell(output: W) -> Self
+    where
+        M: Pure<((), W)>,
+    {
+        Self::new(M::pure(((), output)))
+    }
+
+    /// Get the wrapped computation in the inner monad.
+    pub fn run_writer(self) -> M {
+        self.run
+    }
+}
+
+impl<'a, A: 'a, W: 'a, M, N> MonadTrans<'a, A, M> for WriterT<W, N>
+where
+    W: Monoid,
+    M: Functor<'a, A, Target<(A, W)> = N>,
+{
+    fn lift(m: M) -> Self {
+        Self::new(m.fmap(|value| (value, W::default())))
+    }
+}
+
+impl<'a, A: 'a, W: 'a, M> Functor<'a, A> for WriterT<W, M>
+where
+    M: Functor<'a, (A, W)>,
+{
+    type Target<T: 'a> = WriterT<W, M::Target<(T, W)>>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B + 'a,
+    {
+        WriterT::new(self.run.fmap(move |(value, output)| (f(value), output)))
+    }
+}
+
+impl<'a, A: 'a, W: 'a, M> FreeFunctor<'a, A> for WriterT<W, M>
+where
+    M: FreeFunctor<'a, (A, W)>,
+{
+    fn fmap_target<T: 'a, U: 'a, F>(fa: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> U + 'a,
+    {
+        WriterT::new(M::fmap_target::<(T, W), (U, W), _>(
+            fa.run,
+            move |(value, output)| (f(value), output),
+        ))
+    }
+}
+
+impl<A, W, M> Pure<A> for WriterT<W, M>
+where
+    W: Monoid,
+    M: Pure<(A, W)>,
+{
+    fn pure(value: A) -> Self {
+        Self::new(M::pure((value, W::default())))
+    }
+}
+
+impl<'a, A: 'a, W: 'a, M> Apply<'a, A> for WriterT<W, M>
+where
+    W: Semigroup + Clone,
+    M: InnerMonad<'a, (A, W)> + Apply<'a, (A, W)>,
+{
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        let f = M::fmap_target::<_, ApplyFn<'a, (A, W), (B, W)>, _>(
+            f.run,
+            |(func, left): (ApplyFn<'a, A, B>, W)| {
+                ApplyFn::from(move |(value, right): (A, W)| {
+                    (func.apply_fn(value), left.clone().mappend(right))
+                })
+            },
+        );
+        WriterT::new(self.run.apply(f))
+    }
+}
+
+impl<'a, A: 'a, W: 'a, M> Bind<'a, A> for WriterT<W, M>
+where
+    W: Semigroup + Clone,
+    M: InnerMonad<'a, (A, W)>,
+{
+    fn bind<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> Self::Target<B> + 'a,
+    {
+        WriterT::new(M::bind_target::<(A, W), (B, W), _>(
+            self.run,
+            move |(value, left)| {
+                M::fmap_target::<(B, W), (B, W), _>(f(value).run, move |(value, right)| {
+                    (value, left.clone().mappend(right))
+                })
+            },
+        ))
+    }
+}
+
+impl<'a, A: 'a, W: 'a, M> InnerMonad<'a, A> for WriterT<W, M>
+where
+    W: Monoid + Clone,
+    M: InnerMonad<'a, (A, W)>,
+{
+    fn pure_target<T: Clone + 'a>(value: T) -> Self::Target<T> {
+        WriterT::new(M::pure_target((value, W::default())))
+    }
+
+    fn bind_target<T: 'a, U: 'a, F>(m: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> Self::Target<U> + 'a,
+    {
+        WriterT::new(M::bind_target::<(T, W), (U, W), _>(
+            m.run,
+            move |(value, left)| {
+                M::fmap_target::<(U, W), (U, W), _>(f(value).run, move |(value, right)| {
+                    (value, left.clone().mappend(right))
+                })
+            },
+        ))
+    }
+}
+
+impl<'a, A: 'a, W: 'a, M> MonadWriter<'a, A, W> for WriterT<W, M>
+where
+    W: Monoid + Clone,
+    M: InnerMonad<'a, (A, W)>,
+{
+    fn tell(output: W) -> Self::Target<()> {
+        WriterT::new(M::pure_target(((), output)))
+    }
+
+    fn listen(self) -> Self::Target<(A, W)> {
+        WriterT::new(M::fmap_target::<(A, W), ((A, W), W), _>(
+            self.run,
+            |(value, output)| ((value, output.clone()), output),
+        ))
+    }
+}
+
+impl<'a, A: 'a, W: 'a, S: 'a, M> MonadState<'a, A, S> for WriterT<W, M>
+where
+    W: Monoid + Clone,
+    M: InnerMonad<'a, (A, W)> + MonadState<'a, (A, W), S>,
+{
+    fn get() -> Self::Target<S> {
+        WriterT::new(M::fmap_target::<S, (S, W), _>(M::get(), |state| {
+            (state, W::default())
+        }))
+    }
+
+    fn put(state: S) -> Self::Target<()> {
+        WriterT::new(M::fmap_target::<(), ((), W), _>(M::put(state), |()| {
+            ((), W::default())
+        }))
+    }
+
+    fn modify<F>(f: F) -> Self::Target<()>
+    where
+        F: Fn(S) -> S + 'a,
+    {
+        WriterT::new(M::fmap_target::<(), ((), W), _>(M::modify(f), |()| {
+            ((), W::default())
+        }))
+    }
+}
+
+impl<'a, A: 'a, W: 'a, R: 'a, M> MonadReader<'a, A, R> for WriterT<W, M>
+where
+    W: Monoid + Clone,
+    M: InnerMonad<'a, (A, W)> + MonadReader<'a, (A, W), R>,
+{
+    fn ask() -> Self::Target<R> {
+        WriterT::new(M::fmap_target::<R, (R, W), _>(M::ask(), |env| {
+            (env, W::default())
+        }))
+    }
+
+    fn local<F>(self, f: F) -> Self
+    where
+        F: Fn(R) -> R + 'a,
+    {
+        Self::new(self.run.local(f))
+    }
+}
+
+impl<'a, A: 'a, W: 'a, E: 'a, M> ApplicativeError<'a, A, E> for WriterT<W, M>
+where
+    W: Monoid,
+    M: ApplicativeError<'a, (A, W), E>,
+{
+    fn throw_error(error: E) -> Self {
+        Self::new(M::throw_error(error))
+    }
+
+    fn handle_error_with<F>(self, f: F) -> Self
+    where
+        F: Fn(E) -> Self + 'a,
+    {
+        Self::new(self.run.handle_error_with(move |error| f(error).run))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::WriterT;
+    use crate::{
+        apply::lift2,
+        monoid::Additive,
+        run,
+        transformers::{MonadTrans, OptionT},
+        Functor,
+    };
+
+    #[test]
+    fn writer_over_option() {
+        fn checked_div(a: u32, b: u32) -> WriterT<Vec<String>, Option<(u32, Vec<String>)>> {
+            WriterT::new(
+                a.checked_div(b)
+                    .map(|value| (value, vec![format!("{} / {} = {}", a, b, value)])),
+            )
+        }
+
+        let program = run! {
+            x <= checked_div(100, 5);
+            checked_div(x, 2)
+        };
+        assert_eq!(
+            program.run_writer(),
+            Some((
+                10,
+                vec!["100 / 5 = 20".to_string(), "20 / 2 = 10".to_string()]
+            ))
+        );
+        let program = run! {
+            x <= checked_div(100, 0);
+            checked_div(x, 2)
+        };
+        assert_eq!(program.run_writer(), None);
+    }
+
+    #[test]
+    fn functor_and_apply() {
+        let left: WriterT<Additive<u32>, Vec<(u32, Additive<u32>)>> =
+            WriterT::new(vec![(1, Additive(1)), (2, Additive(2))]);
+        let right = WriterT::new(vec![(10, Additive(10))]);
+        let sum = lift2(&|a: u32, b: u32| a + b, left.clone(), right);
+        assert_eq!(
+            sum.run_writer(),
+            vec![(11, Additive(11)), (12, Additive(12))]
+        );
+        assert_eq!(
+            left.fmap(|x| x * 2).run_writer(),
+            vec![(2, Additive(1)), (4, Additive(2))]
+        );
+    }
+
+    #[test]
+    fn stacked() {
+        let program: OptionT<WriterT<Vec<&str>, Vec<(Option<u32>, Vec<&str>)>>> = run! {
+            x <= OptionT::new(WriterT::new(vec![(Some(1), vec!["one"]), (None, vec!["none"])]));
+            OptionT::lift(WriterT::<_, Vec<_>>::tell(vec!["lifted"]));
+            yield x + 1
+        };
+        assert_eq!(
+            program.run_option().run_writer(),
+            vec![(Some(2), vec!["one", "lifted"]), (None, vec!["none"])]
+        );
+    }
+}
@@ -1,20 +1,23 @@
 use std::{
-    collections::{LinkedList, VecDeque},
+    collections::{BTreeMap, HashMap, LinkedList, VecDeque},
     convert::identity,
+    hash::Hash,
     iter,
+    task::Poll,
 };
 
 use crate::{
     apply::{lift2, ApplyFn},
-    Applicative, Foldable, Functor, Pure,
+    constant::Const,
+    Applicative, Foldable, Functor, Monoid, Pure,
 };
 
 pub trait Traversable<'a, A: 'a>: Functor<'a, A> + Foldable<'a, A> {
-    fn traverse<B: 'a, M: 'a, F: 'a>(self, f: F) -> M::Target<Self::Target<B>>
+    fn traverse<B, M, F>(self, f: F) -> M::Target<Self::Target<B>>
     where
-        B: Clone,
-        M: Applicative<'a, B>,
-        F: Fn(A) -> M,
+        B: Clone + 'a,
+        M: Applicative<'a, B> + 'a,
+        F: Fn(A) -> M + 'a,
 
         M::Target<Self::Target<B>>: Applicative<'a, Self::Target<B>, Target<Self::Target<B>> = M::Target<Self::Target<B>>>
             + Applicative<
@@ -25,11 +28,11 @@ pub trait Traversable<'a, A: 'a>: Functor<'a, A> + Foldable<'a, A> {
                 >,
             >;
 
-    fn sequence<B: 'a>(self) -> A::Target<Self::Target<B>>
+    fn sequence<B>(self) -> A::Target<Self::Target<B>>
     where
         Self: Sized,
         A: Applicative<'a, B>,
-        B: Clone,
+        B: Clone + 'a,
 
         A::Target<Self::Target<B>>: Applicative<'a, Self::Target<B>, Target<Self::Target<B>> = A::Target<Self::Target<B>>>
             + Applicative<
@@ -45,13 +48,13 @@ pub trait Traversable<'a, A: 'a>: Functor<'a, A> + Foldable<'a, A> {
 }
 
 impl<'a, A: 'a> Traversable<'a, A> for Option<A> {
-    fn traverse<B: 'a, M, F: 'a>(self, f: F) -> M::Target<Self::Target<B>>
+    fn traverse<B: 'a, M, F>(self, f: F) -> M::Target<Self::Target<B>>
     where
         Self::Target<B>: Traversable<'a, B>,
         M: Applicative<'a, B>,
 
         M::Target<Self::Target<B>>: Applicative<'a, Self::Target<B>>,
-        F: Fn(A) -> M,
+        F: Fn(A) -> M + 'a,
     {
         match self {
             None => Pure::pure(None),
@@ -61,13 +64,13 @@ impl<'a, A: 'a> Traversable<'a, A> for Option<A> {
 }
 
 impl<'a, A: 'a, E: 'a> Traversable<'a, A> for Result<A, E> {
-    fn traverse<B: 'a, M, F: 'a>(self, f: F) -> M::Target<Self::Target<B>>
+    fn traverse<B: 'a, M, F>(self, f: F) -> M::Target<Self::Target<B>>
     where
         Self::Target<B>: Traversable<'a, B>,
         M: Applicative<'a, B>,
 
         M::Target<Self::Target<B>>: Applicative<'a, Self::Target<B>>,
-        F: Fn(A) -> M,
+        F: Fn(A) -> M + 'a,
     {
         match self {
             Err(e) => Pure::pure(Err(e)),
@@ -79,11 +82,54 @@ impl<'a, A: 'a, E: 'a> Traversable<'a, A> for Result<A, E> {
 /// A default implementation of [`traverse`](traverse) for anything that
 /// implements [`Foldable`](Foldable), [`Default`](Default) and
 /// [`Extend`](Extend).
-pub fn traverse_extend<'a, A: 'a, B: 'a, L: 'a, M: 'a, F: 'a>(f: F, l: L) -> M::Target<L::Target<B>>
+impl<'a, A: 'a> Traversable<'a, A> for Poll<A> {
+    fn traverse<B: 'a, M, F>(self, f: F) -> M::Target<Self::Target<B>>
+    where
+        Self::Target<B>: Traversable<'a, B>,
+        M: Applicative<'a, B>,
+
+        M::Target<Self::Target<B>>: Applicative<'a, Self::Target<B>>,
+        F: Fn(A) -> M + 'a,
+    {
+        match self {
+            Poll::Pending => Pure::pure(Poll::Pending),
+            Poll::Ready(a) => f(a).fmap(Poll::Ready),
+        }
+    }
+}
+
+impl<'a, A: 'a> Traversable<'a, A> for Box<A> {
+    fn traverse<B: 'a, M, F>(self, f: F) -> M::Target<Self::Target<B>>
+    where
+        Self::Target<B>: Traversable<'a, B>,
+        M: Applicative<'a, B>,
+
+        M::Target<Self::Target<B>>: Applicative<'a, Self::Target<B>>,
+        F: Fn(A) -> M + 'a,
+    {
+        f(*self).fmap(Box::new)
+    }
+}
+
+impl<'a, W: Clone + 'a, A: 'a> Traversable<'a, A> for (W, A) {
+    fn traverse<B: 'a, M, F>(self, f: F) -> M::Target<Self::Target<B>>
+    where
+        Self::Target<B>: Traversable<'a, B>,
+        M: Applicative<'a, B>,
+
+        M::Target<Self::Target<B>>: Applicative<'a, Self::Target<B>>,
+        F: Fn(A) -> M + 'a,
+    {
+        let (w, a) = self;
+        f(a).fmap(move |b| (w.clone(), b))
+    }
+}
+
+pub fn traverse_extend<'a, A: 'a, B: 'a, L, M, F>(f: F, l: L) -> M::Target<L::Target<B>>
 where
-    L: Traversable<'a, A>,
+    L: Traversable<'a, A> + 'a,
     L::Target<B>: Foldable<'a, B> + Extend<B> + Default + Clone,
-    M: Applicative<'a, B>,
+    M: Applicative<'a, B> + 'a,
 
     M::Target<L::Target<B>>: Applicative<'a, L::Target<B>, Target<L::Target<B>> = M::Target<L::Target<B>>>
         + Applicative<
@@ -91,7 +137,7 @@ where
             L::Target<B>,
             Target<ApplyFn<'a, B, L::Target<B>>> = M::Target<ApplyFn<'a, B, L::Target<B>>>,
         >,
-    F: Fn(A) -> M,
+    F: Fn(A) -> M + 'a,
 {
     fn snoc<L: Extend<A>, A>(mut l: L, a: A) -> L {
         l.extend(iter::once(a));
@@ -104,14 +150,28 @@ where
     )
 }
 
+/// A default implementation of [`fold_map`](Foldable::fold_map) for anything
+/// that implements [`Traversable`](Traversable), which traverses it with the
+/// [`Const`](Const) applicative.
+pub fn fold_map_default<'a, A: 'a, T, M, F>(f: F, t: T) -> M
+where
+    T: Traversable<'a, A>,
+    T::Target<()>: 'a,
+    M: Monoid + 'a,
+    F: Fn(A) -> M + 'a,
+{
+    t.traverse::<(), Const<M, ()>, _>(move |a| Const::new(f(a)))
+        .get_const()
+}
+
 macro_rules! impl_traversable_for_extendable {
     ($type:ident) => {
         impl<'a, A: 'a> Traversable<'a, A> for $type<A> {
-            fn traverse<B: 'a, M: 'a, F: 'a>(self, f: F) -> M::Target<Self::Target<B>>
+            fn traverse<B, M, F>(self, f: F) -> M::Target<Self::Target<B>>
             where
                 Self::Target<B>: Foldable<'a, B>,
-                M: Applicative<'a, B>,
-                B: Clone,
+                M: Applicative<'a, B> + 'a,
+                B: Clone + 'a,
 
                 M::Target<Self::Target<B>>: Applicative<
                         'a,
@@ -124,7 +184,7 @@ macro_rules! impl_traversable_for_extendable {
                             ApplyFn<'a, B, Self::Target<B>>,
                         >,
                     >,
-                F: Fn(A) -> M,
+                F: Fn(A) -> M + 'a,
             {
                 traverse_extend(f, self)
             }
@@ -136,9 +196,54 @@ impl_traversable_for_extendable!(Vec);
 impl_traversable_for_extendable!(VecDeque);
 impl_traversable_for_extendable!(LinkedList);
 
+macro_rules! impl_traversable_for_map {
+    ($type:ident, $($key_bound:tt)*) => {
+        impl<'a, K: $($key_bound)* + Clone + 'a, V: 'a> Traversable<'a, V> for $type<K, V> {
+            fn traverse<B, M, F>(self, f: F) -> M::Target<Self::Target<B>>
+            where
+                M: Applicative<'a, B> + 'a,
+                B: Clone + 'a,
+
+                M::Target<Self::Target<B>>: Applicative<
+                        'a,
+                        Self::Target<B>,
+                        Target<Self::Target<B>> = M::Target<Self::Target<B>>,
+                    > + Applicative<
+                        'a,
+                        Self::Target<B>,
+                        Target<ApplyFn<'a, B, Self::Target<B>>> = M::Target<
+                            ApplyFn<'a, B, Self::Target<B>>,
+                        >,
+                    >,
+                F: Fn(V) -> M + 'a,
+            {
+                let mut result: M::Target<$type<K, B>> = Pure::pure($type::new());
+                for (key, value) in self {
+                    let insert = result.fmap(move |map: $type<K, B>| {
+                        let key = key.clone();
+                        ApplyFn::from(move |b| {
+                            let mut map = map.clone();
+                            map.insert(key.clone(), b);
+                            map
+                        })
+                    });
+                    result = f(value).apply(insert);
+                }
+                result
+            }
+        }
+    };
+}
+
+impl_traversable_for_map!(HashMap, Hash + Eq);
+impl_traversable_for_map!(BTreeMap, Ord);
+
 #[cfg(test)]
 mod test {
-    use super::Traversable;
+    use std::{collections::BTreeMap, task::Poll};
+
+    use super::{fold_map_default, Traversable};
+    use crate::{identity::Identity, monoid::Additive};
 
     #[test]
     fn sequence_option() {
@@ -153,4 +258,36 @@ mod test {
         let n: Option<Vec<i32>> = m.traverse(|a| Some(a * 2));
         assert_eq!(n, Some(vec![2, 4, 6]));
     }
+
+    #[test]
+    fn traverse_map_values() {
+        let m = BTreeMap::from([("a", 1), ("b", 2)]);
+        let n: Result<_, i32> = m.clone().traverse(|x| Ok(x * 2));
+        assert_eq!(n, Ok(BTreeMap::from([("a", 2), ("b", 4)])));
+        let n = m.traverse(|x| if x > 1 { Some(x) } else { None });
+        assert_eq!(n, None);
+    }
+
+    #[test]
+    fn traverse_wrappers() {
+        assert_eq!(Box::new(2).traverse(|x| Some(x * 2)), Some(Box::new(4)));
+        assert_eq!(
+            ("w", 2).traverse(|x| vec![x, -x]),
+            vec![("w", 2), ("w", -2)]
+        );
+        let ready = Poll::Ready(2).traverse(|x| if x > 1 { Ok(x) } else { Err(x) });
+        assert_eq!(ready, Ok(Poll::Ready(2)));
+        let pending: Option<Poll<i32>> = Poll::Pending.traverse(|x: i32| Some(x));
+        assert_eq!(pending, Some(Poll::Pending));
+    }
+
+    #[test]
+    fn fold_map_through_const() {
+        let sum: Additive<i32> = fold_map_default(Additive, vec![1, 2, 3]);
+        assert_eq!(sum, Additive(6));
+        let text: String = fold_map_default(|x: i32| x.to_string(), Some(5));
+        assert_eq!(text, "5");
+        let text: String = fold_map_default(|x: i32| x.to_string(), Identity(7));
+        assert_eq!(text, "7");
+    }
 }
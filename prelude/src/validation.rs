@@ -0,0 +1,322 @@
+use crate::{
+    apply::ApplyFn, free::FreeFunctor, Applicative, ApplicativeError, Apply, Bifunctor, Foldable,
+    Functor, Monoid, Pure, Semigroup, Traversable,
+};
+
+/// A `Validation` is like a [`Result`](Result) which collects every error it
+/// encounters instead of stopping at the first one.
+///
+/// Where [`Result`](Result)'s [`Apply`](Apply) implementation short circuits on
+/// the first [`Err`](Result::Err), `Validation`'s combines the errors from both
+/// sides using [`Semigroup::mappend`](Semigroup::mappend). This makes it
+/// useful for checking a bunch of independent things and reporting all the
+/// failures at once.
+///
+/// Because of this, `Validation` doesn't implement [`Bind`](crate::Bind): a
+/// computation which depends on the result of a previous one can't run if the
+/// previous one failed, so there would be no way to accumulate its errors. If
+/// you need to sequence validations, convert to a [`Result`](Result) and back.
+///
+/// ```
+/// # use higher::{apply::lift2, validation::Validation};
+/// fn positive(n: i32) -> Validation<Vec<String>, i32> {
+///     if n > 0 {
+///         Validation::Success(n)
+///     } else {
+///         Validation::Failure(vec![format!("{} is not positive", n)])
+///     }
+/// }
+///
+/// let add = |a, b| a + b;
+/// assert_eq!(lift2(&add, positive(2), positive(3)), Validation::Success(5));
+/// assert_eq!(
+///     lift2(&add, positive(-2), positive(-3)),
+///     Validation::Failure(vec![
+///         "-2 is not positive".to_string(),
+///         "-3 is not positive".to_string(),
+///     ])
+/// );
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Validation<E, A> {
+    Failure(E),
+    Success(A),
+}
+
+impl<E, A> Validation<E, A> {
+    /// Test whether the validation succeeded.
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Success(_))
+    }
+
+    /// Test whether the validation failed.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Failure(_))
+    }
+
+    /// Convert the validation into a [`Result`](Result).
+    pub fn into_result(self) -> Result<A, E> {
+        self.into()
+    }
+}
+
+impl<E, A> From<Result<A, E>> for Validation<E, A> {
+    fn from(result: Result<A, E>) -> Self {
+        match result {
+            Ok(value) => Self::Success(value),
+            Err(error) => Self::Failure(error),
+        }
+    }
+}
+
+impl<E, A> From<Validation<E, A>> for Result<A, E> {
+    fn from(validation: Validation<E, A>) -> Self {
+        match validation {
+            Validation::Success(value) => Ok(value),
+            Validation::Failure(error) => Err(error),
+        }
+    }
+}
+
+impl<'a, A: 'a, E> Functor<'a, A> for Validation<E, A> {
+    type Target<T: 'a> = Validation<E, T>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B,
+    {
+        match self {
+            Self::Success(value) => Validation::Success(f(value)),
+            Self::Failure(error) => Validation::Failure(error),
+        }
+    }
+}
+
+impl<'a, A: 'a, E> FreeFunctor<'a, A> for Validation<E, A> {
+    fn fmap_target<T: 'a, U: 'a, F>(fa: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> U + 'a,
+    {
+        fa.fmap(f)
+    }
+}
+
+impl<'a, E: 'a, A: 'a> Bifunctor<'a, E, A> for Validation<E, A> {
+    type Target<T: 'a, U: 'a> = Validation<T, U>;
+
+    fn bimap<C: 'a, D: 'a, L, R>(self, left: L, right: R) -> Self::Target<C, D>
+    where
+        L: Fn(E) -> C + 'a,
+        R: Fn(A) -> D + 'a,
+    {
+        match self {
+            Self::Failure(error) => Validation::Failure(left(error)),
+            Self::Success(value) => Validation::Success(right(value)),
+        }
+    }
+}
+
+impl<E, A> Pure<A> for Validation<E, A> {
+    fn pure(value: A) -> Self {
+        Self::Success(value)
+    }
+}
+
+impl<'a, A: 'a, E> Apply<'a, A> for Validation<E, A>
+where
+    E: Semigroup,
+{
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        match (f, self) {
+            (Validation::Success(f), Self::Success(value)) => {
+                Validation::Success(f.apply_fn(value))
+            }
+            (Validation::Failure(left), Self::Failure(right)) => {
+                Validation::Failure(left.mappend(right))
+            }
+            (Validation::Failure(error), _) | (_, Self::Failure(error)) => {
+                Validation::Failure(error)
+            }
+        }
+    }
+}
+
+impl<'a, A: 'a, E> Foldable<'a, A> for Validation<E, A> {
+    fn foldr<B, F>(self, f: F, init: B) -> B
+    where
+        B: 'a,
+        F: Fn(A, B) -> B + 'a,
+    {
+        match self {
+            Self::Success(value) => f(value, init),
+            Self::Failure(_) => init,
+        }
+    }
+
+    fn foldr_ref<B, F>(&'a self, f: F, init: B) -> B
+    where
+        B: 'a,
+        F: Fn(&'a A, B) -> B + 'a,
+    {
+        match self {
+            Self::Success(value) => f(value, init),
+            Self::Failure(_) => init,
+        }
+    }
+
+    fn foldl<B, F>(self, f: F, init: B) -> B
+    where
+        B: 'a,
+        F: Fn(B, A) -> B + 'a,
+    {
+        match self {
+            Self::Success(value) => f(init, value),
+            Self::Failure(_) => init,
+        }
+    }
+
+    fn foldl_ref<B, F>(&'a self, f: F, init: B) -> B
+    where
+        B: 'a,
+        F: Fn(B, &'a A) -> B + 'a,
+    {
+        match self {
+            Self::Success(value) => f(init, value),
+            Self::Failure(_) => init,
+        }
+    }
+
+    fn fold_map<F, M>(self, f: F) -> M
+    where
+        F: Fn(A) -> M + 'a,
+        M: Monoid,
+    {
+        match self {
+            Self::Success(value) => f(value),
+            Self::Failure(_) => M::default(),
+        }
+    }
+
+    fn fold_map_ref<F, M>(&'a self, f: F) -> M
+    where
+        F: Fn(&'a A) -> M + 'a,
+        M: Monoid,
+    {
+        match self {
+            Self::Success(value) => f(value),
+            Self::Failure(_) => M::default(),
+        }
+    }
+}
+
+impl<'a, A: 'a, E: 'a> Traversable<'a, A> for Validation<E, A> {
+    fn traverse<B: 'a, M, F>(self, f: F) -> M::Target<Self::Target<B>>
+    where
+        Self::Target<B>: Traversable<'a, B>,
+        M: Applicative<'a, B>,
+
+        M::Target<Self::Target<B>>: Applicative<'a, Self::Target<B>>,
+        F: Fn(A) -> M + 'a,
+    {
+        match self {
+            Self::Failure(error) => Pure::pure(Validation::Failure(error)),
+            Self::Success(value) => f(value).fmap(Validation::Success),
+        }
+    }
+}
+
+impl<'a, A: 'a, E: 'a> ApplicativeError<'a, A, E> for Validation<E, A> {
+    fn throw_error(error: E) -> Self {
+        Self::Failure(error)
+    }
+
+    fn handle_error_with<F>(self, f: F) -> Self
+    where
+        F: Fn(E) -> Self + 'a,
+    {
+        match self {
+            Self::Failure(error) => f(error),
+            success => success,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Validation;
+    use crate::{apply::lift2, ApplicativeError, Bifunctor, Functor, Traversable};
+
+    fn non_empty(name: &'static str, value: &str) -> Validation<Vec<String>, String> {
+        if value.is_empty() {
+            Validation::Failure(vec![format!("{} must not be empty", name)])
+        } else {
+            Validation::Success(value.to_string())
+        }
+    }
+
+    #[test]
+    fn apply_accumulates_errors() {
+        let join = |a: String, b: String| format!("{}@{}", a, b);
+        assert_eq!(
+            lift2(&join, non_empty("user", "joe"), non_empty("host", "erlang")),
+            Validation::Success("joe@erlang".to_string())
+        );
+        assert_eq!(
+            lift2(&join, non_empty("user", ""), non_empty("host", "erlang")),
+            Validation::Failure(vec!["user must not be empty".to_string()])
+        );
+        assert_eq!(
+            lift2(&join, non_empty("user", ""), non_empty("host", "")),
+            Validation::Failure(vec![
+                "user must not be empty".to_string(),
+                "host must not be empty".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn traverse_accumulates_errors() {
+        let values = vec!["a", "", "c", ""];
+        let result: Validation<Vec<String>, Vec<String>> =
+            values.traverse(|value| non_empty("value", value));
+        assert_eq!(
+            result,
+            Validation::Failure(vec![
+                "value must not be empty".to_string(),
+                "value must not be empty".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn functor_and_bifunctor() {
+        let success: Validation<String, i32> = Validation::Success(2);
+        assert_eq!(success.fmap(|x| x + 3), Validation::Success(5));
+        let failure: Validation<String, i32> = Validation::Failure("nope".to_string());
+        assert_eq!(
+            failure.bimap(|e| e.len(), |x| x + 3),
+            Validation::Failure(4)
+        );
+    }
+
+    #[test]
+    fn result_conversions() {
+        let result: Result<i32, String> = Err("nope".to_string());
+        let validation = Validation::from(result.clone());
+        assert!(validation.is_failure());
+        assert_eq!(validation.into_result(), result);
+        assert_eq!(
+            Validation::<String, i32>::from_result(Ok(5)),
+            Validation::Success(5)
+        );
+        assert_eq!(
+            Validation::<String, i32>::throw_error("nope".to_string())
+                .handle_error(|e| e.len() as i32),
+            Validation::Success(4)
+        );
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{apply::ApplyFn, free::FreeFunctor, Apply, Bind, Functor, Monoid, Pure, Semigroup};
+
+/// A `Writer` monad.
+///
+/// A `Writer` pairs a result value with an output value which accumulates as
+/// you chain computations together. The output can be any
+/// [`Monoid`](Monoid): [`Bind`](Bind) combines the output of each step using
+/// [`Semigroup::mappend`](Semigroup::mappend), and [`Pure`](Pure) starts with
+/// the empty output from [`Default`](Default).
+///
+/// This is useful for things like logging from pure code without any side
+/// effects:
+///
+/// ```
+/// # use higher::{run, writer::Writer};
+/// fn half(n: u32) -> Writer<Vec<String>, u32> {
+///     run! {
+///         Writer::tell(vec![format!("halving {}", n)]);
+///         yield n / 2
+///     }
+/// }
+///
+/// let result = run! {
+///     x <= half(32);
+///     half(x)
+/// };
+/// assert_eq!(
+///     result.run_writer(),
+///     (8, vec!["halving 32".to_string(), "halving 16".to_string()])
+/// );
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Writer<W, A> {
+    value: A,
+    output: W,
+}
+
+impl<W, A> Writer<W, A> {
+    /// Construct a `Writer` from a result value and an output value.
+    pub fn new(value: A, output: W) -> Self {
+        Self { value, output }
+    }
+
+    /// Get the result value and the accumulated output.
+    pub fn run_writer(self) -> (A, W) {
+        (self.value, self.output)
+    }
+
+    /// Get the accumulated output, discarding the result value.
+    pub fn exec_writer(self) -> W {
+        self.output
+    }
+
+    /// Add the accumulated output to the result value.
+    pub fn listen(self) -> Writer<W, (A, W)>
+    where
+        W: Clone,
+    {
+        let output = self.output.clone();
+        Writer::new((self.value, self.output), output)
+    }
+
+    /// Modify the accumulated output using the given function.
+    pub fn censor<F>(self, f: F) -> Self
+    where
+        F: FnOnce(W) -> W,
+    {
+        Self::new(self.value, f(self.output))
+    }
+}
+
+impl<W> Writer<W, ()> {
+    /// Construct a `Writer` which adds the given value to the output.
+    pub fn tell(output: W) -> Self {
+        Self::new((), output)
+    }
+}
+
+impl<W, A, F> Writer<W, (A, F)>
+where
+    F: FnOnce(W) -> W,
+{
+    /// Modify the accumulated output using the function contained in the
+    /// result value.
+    pub fn pass(self) -> Writer<W, A> {
+        let (value, f) = self.value;
+        Writer::new(value, f(self.output))
+    }
+}
+
+impl<'a, W, A: 'a> Functor<'a, A> for Writer<W, A> {
+    type Target<T: 'a> = Writer<W, T>;
+
+    fn fmap<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> B,
+    {
+        Writer::new(f(self.value), self.output)
+    }
+}
+
+impl<'a, W, A: 'a> FreeFunctor<'a, A> for Writer<W, A> {
+    fn fmap_target<T: 'a, U: 'a, F>(fa: Self::Target<T>, f: F) -> Self::Target<U>
+    where
+        F: Fn(T) -> U + 'a,
+    {
+        fa.fmap(f)
+    }
+}
+
+impl<W, A> Pure<A> for Writer<W, A>
+where
+    W: Monoid,
+{
+    fn pure(value: A) -> Self {
+        Self::new(value, W::default())
+    }
+}
+
+impl<'a, W, A: 'a> Apply<'a, A> for Writer<W, A>
+where
+    W: Semigroup,
+{
+    fn apply<B>(self, f: Self::Target<ApplyFn<'a, A, B>>) -> Self::Target<B>
+    where
+        B: 'a,
+    {
+        Writer::new(f.value.apply_fn(self.value), f.output.mappend(self.output))
+    }
+}
+
+impl<'a, W, A: 'a> Bind<'a, A> for Writer<W, A>
+where
+    W: Semigroup,
+{
+    fn bind<B, F>(self, f: F) -> Self::Target<B>
+    where
+        B: 'a,
+        F: Fn(A) -> Self::Target<B>,
+    {
+        let next = f(self.value);
+        Writer::new(next.value, self.output.mappend(next.output))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Writer;
+    use crate::{apply::lift2, monoid::Additive, run, Functor};
+
+    #[test]
+    fn tell_string() {
+        let writer = run! {
+            Writer::tell("Hello ".to_string());
+            Writer::tell("Joe".to_string());
+            yield 5
+        };
+        assert_eq!(writer.run_writer(), (5, "Hello Joe".to_string()));
+    }
+
+    #[test]
+    fn count_steps() {
+        fn step(n: u64) -> Writer<Additive<u64>, u64> {
+            Writer::new(n + 1, Additive(1))
+        }
+        let writer = run! {
+            x <= step(0);
+            y <= step(x);
+            step(y)
+        };
+        assert_eq!(writer.run_writer(), (3, Additive(3)));
+    }
+
+    #[test]
+    fn listen_pass_censor() {
+        let writer = Writer::new(1, vec!["one"]);
+        assert_eq!(
+            writer.clone().listen().run_writer(),
+            ((1, vec!["one"]), vec!["one"])
+        );
+        assert_eq!(
+            writer
+                .clone()
+                .fmap(|x| (x, |w: Vec<&'static str>| [w.clone(), w].concat()))
+                .pass()
+                .run_writer(),
+            (1, vec!["one", "one"])
+        );
+        assert_eq!(writer.censor(|_| vec![]).exec_writer(), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn apply() {
+        let sum = lift2(
+            &|a: i32, b: i32| a + b,
+            Writer::new(2, vec!["two"]),
+            Writer::new(40, vec!["forty"]),
+        );
+        assert_eq!(sum.run_writer(), (42, vec!["two", "forty"]));
+    }
+}